@@ -1,11 +1,22 @@
-use crate::handler::{accept_gov, add_lock_setting, set_gov, set_lock_status};
+use crate::handler::{
+    accept_gov, add_lock_setting, clear_expired_delegations, delegate_boost, disable_lock_setting,
+    enable_lock_setting, extend_lock, increase_lock_amount, lock, receive_delegation_receipt,
+    set_delegation_nft, set_gov, set_pool_lock_settings, sync_delegation, unlock,
+    update_lock_setting, validate_lock_setting,
+};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::querier::{get_boost_config, get_unlock_time, get_user_boost, get_user_lock_status};
-use crate::state::{store_boost_config, BoostConfig};
+use crate::querier::{
+    get_boost_config, get_delegation, get_pool_lock_settings, get_unlock_time, get_user_boost,
+    get_user_delegations, get_user_lock_history, get_user_lock_status, get_user_working_balance,
+};
+use crate::state::{read_legacy_boost_config, store_boost_config, BoostConfig};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
-use cw2::set_contract_version;
+use cosmwasm_std::{
+    attr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "kryptonite.finance:cw20-ve-seilor-boost";
@@ -21,12 +32,20 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let gov = msg.gov.unwrap_or_else(|| info.sender.clone());
+    deps.api.addr_validate(msg.ve_seilor_addr.as_str())?;
 
-    let config = BoostConfig {
+    let mut config = BoostConfig {
         gov: gov,
-        ve_seilor_lock_settings: msg.ve_seilor_lock_settings,
+        ve_seilor_addr: msg.ve_seilor_addr,
+        ve_seilor_lock_settings: vec![],
         new_gov: None,
+        disabled_lock_settings: vec![],
+        delegation_nft_addr: None,
     };
+    for setting in msg.ve_seilor_lock_settings {
+        validate_lock_setting(&config, None, &setting)?;
+        config.ve_seilor_lock_settings.push(setting);
+    }
 
     store_boost_config(deps.storage, &config)?;
     Ok(Response::new().add_attributes(vec![
@@ -42,12 +61,33 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             duration,
             mining_boost,
         } => add_lock_setting(deps, info, duration, mining_boost),
+        ExecuteMsg::UpdateLockSetting {
+            index,
+            duration,
+            mining_boost,
+        } => update_lock_setting(deps, info, index as usize, duration, mining_boost),
+        ExecuteMsg::DisableLockSetting { index } => disable_lock_setting(deps, info, index),
+        ExecuteMsg::EnableLockSetting { index } => enable_lock_setting(deps, info, index),
         ExecuteMsg::SetGov { gov } => set_gov(deps, info, gov),
         ExecuteMsg::AcceptGov {} => accept_gov(deps, info),
-        ExecuteMsg::SetLockStatus { index } => {
-            let _index = index as usize;
-            set_lock_status(deps, env, info, _index)
-        }
+        ExecuteMsg::Lock { index, amount } => lock(deps, env, info, index as usize, amount),
+        ExecuteMsg::ExtendLock { index } => extend_lock(deps, env, info, index as usize),
+        ExecuteMsg::IncreaseLockAmount { amount } => increase_lock_amount(deps, env, info, amount),
+        ExecuteMsg::Unlock {} => unlock(deps, env, info),
+        ExecuteMsg::SetDelegationNft { nft_addr } => set_delegation_nft(deps, info, nft_addr),
+        ExecuteMsg::DelegateBoost {
+            receiver,
+            boost,
+            expire_time,
+            mint_receipt,
+        } => delegate_boost(deps, env, info, receiver, boost, expire_time, mint_receipt),
+        ExecuteMsg::SyncDelegation { id } => sync_delegation(deps, env, id),
+        ExecuteMsg::ClearExpiredDelegations { user } => clear_expired_delegations(deps, env, user),
+        ExecuteMsg::ReceiveNft(msg) => receive_delegation_receipt(deps, env, info, msg),
+        ExecuteMsg::SetPoolLockSettings {
+            pool,
+            lock_settings,
+        } => set_pool_lock_settings(deps, info, pool, lock_settings),
     }
 }
 
@@ -60,26 +100,94 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             user,
             user_updated_at,
             finish_at,
+            pool,
         } => to_binary(&get_user_boost(
             deps,
             env,
             user,
             user_updated_at,
             finish_at,
+            pool,
         )?),
         QueryMsg::GetBoostConfig {} => to_binary(&get_boost_config(deps)?),
+        QueryMsg::GetUserWorkingBalance {
+            user,
+            balance,
+            total_supply,
+        } => to_binary(&get_user_working_balance(
+            deps,
+            user,
+            balance,
+            total_supply,
+        )?),
+        QueryMsg::GetDelegation { id } => to_binary(&get_delegation(deps, id)?),
+        QueryMsg::GetUserDelegations { user } => to_binary(&get_user_delegations(deps, user)?),
+        QueryMsg::GetPoolLockSettings { pool } => to_binary(&get_pool_lock_settings(deps, pool)?),
+        QueryMsg::GetUserLockHistory {
+            user,
+            start_after,
+            limit,
+        } => to_binary(&get_user_lock_history(deps, user, start_after, limit)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = get_contract_version(deps.storage)?;
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from contract {}",
+            contract_version.contract
+        )));
+    }
+    let stored_version = parse_version(&contract_version.version)?;
+    if stored_version > parse_version(CONTRACT_VERSION)? {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from version {} to older version {}",
+            contract_version.version, CONTRACT_VERSION
+        )));
+    }
+
+    let mut attrs = vec![
+        attr("action", "migrate"),
+        attr("from_version", contract_version.version),
+        attr("to_version", CONTRACT_VERSION),
+    ];
+    // before 0.2.0 the config had no ve_seilor and locks held no ve_seilor,
+    // lock statuses read their locked_amount as zero
+    if stored_version < Version::new(0, 2, 0) {
+        let ve_seilor_addr = msg.ve_seilor_addr.ok_or_else(|| {
+            StdError::generic_err("ve_seilor_addr is required to migrate from this version")
+        })?;
+        deps.api.addr_validate(ve_seilor_addr.as_str())?;
+        let legacy = read_legacy_boost_config(deps.storage)?;
+        store_boost_config(
+            deps.storage,
+            &BoostConfig {
+                gov: legacy.gov,
+                ve_seilor_addr: ve_seilor_addr.clone(),
+                ve_seilor_lock_settings: legacy.ve_seilor_lock_settings,
+                new_gov: legacy.new_gov,
+                disabled_lock_settings: vec![],
+                delegation_nft_addr: None,
+            },
+        )?;
+        attrs.push(attr("ve_seilor_addr", ve_seilor_addr));
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attributes(attrs))
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Addr, Uint128};
     #[test]
     fn test_instantiate() {
         let mut deps = mock_dependencies();
@@ -87,6 +195,7 @@ mod tests {
         let info = mock_info("creator", &[]);
         let msg = InstantiateMsg {
             gov: None,
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
             ve_seilor_lock_settings: vec![],
         };
         let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
@@ -95,4 +204,86 @@ mod tests {
         assert_eq!(res.attributes[0], ("action", "instantiate"));
         assert_eq!(res.attributes[1], ("owner", "creator"));
     }
+
+    #[test]
+    fn test_migrate_from_legacy_config() {
+        let mut deps = mock_dependencies();
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+        deps.as_mut().storage.set(
+            b"boost_config",
+            br#"{"gov":"gov","ve_seilor_lock_settings":[],"new_gov":null}"#,
+        );
+
+        let err = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                ve_seilor_addr: None,
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("ve_seilor_addr is required"));
+
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                ve_seilor_addr: Some(Addr::unchecked("ve_seilor")),
+            },
+        )
+        .unwrap();
+        let config = crate::state::read_boost_config(deps.as_ref().storage).unwrap();
+        assert_eq!(config.gov, Addr::unchecked("gov"));
+        assert_eq!(config.ve_seilor_addr, Addr::unchecked("ve_seilor"));
+
+        // a repeat migrate leaves the config alone
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                ve_seilor_addr: Some(Addr::unchecked("other")),
+            },
+        )
+        .unwrap();
+        let config = crate::state::read_boost_config(deps.as_ref().storage).unwrap();
+        assert_eq!(config.ve_seilor_addr, Addr::unchecked("ve_seilor"));
+
+        // a lock kept from before 0.2.0 holds no ve_seilor, it neither boosts nor delegates
+        let now = mock_env().block.time.seconds();
+        crate::state::store_user_lock_status(
+            deps.as_mut().storage,
+            Addr::unchecked("tom"),
+            &crate::state::LockStatus {
+                unlock_time: Uint128::from(now + 1000),
+                duration: Uint128::from(31536000u128),
+                mining_boost: Uint128::from(100000000u128),
+                locked_amount: Uint128::zero(),
+            },
+        )
+        .unwrap();
+        let user_boost = get_user_boost(
+            deps.as_ref(),
+            mock_env(),
+            Addr::unchecked("tom"),
+            Uint128::from(now),
+            Uint128::from(now + 1000),
+            None,
+        )
+        .unwrap()
+        .user_boost;
+        assert_eq!(user_boost, Uint128::zero());
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("tom", &[]),
+            ExecuteMsg::DelegateBoost {
+                receiver: Addr::unchecked("jerry"),
+                boost: Uint128::from(1u128),
+                expire_time: Uint128::from(now + 100),
+                mint_receipt: false,
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("no active lock"));
+    }
 }
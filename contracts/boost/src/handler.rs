@@ -3,11 +3,44 @@
 // esLBRLockSettings.push(setting);
 // }
 
+use crate::querier::{query_receipt_owner, query_ve_seilor_balance};
 use crate::state::{
-    read_boost_config, read_user_lock_status, store_boost_config, store_user_lock_status,
-    VeSeilorLockSetting,
+    may_read_delegation, next_delegation_id, read_boost_config, read_delegation,
+    read_user_delegated, read_user_lock_status, read_user_received, remove_delegation,
+    remove_pool_lock_settings, remove_user_lock_status, store_boost_config, store_delegation,
+    store_pool_lock_settings, store_user_lock_history, store_user_lock_status,
+    update_delegation_receiver, BoostConfig, Delegation, LockHistory, LockStatus,
+    VeSeilorLockSetting, MAX_ACTIVE_DELEGATIONS, MAX_MINING_BOOST,
+};
+use crate::third_msg::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Storage, Uint128, WasmMsg,
 };
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128};
+
+// Function to check a lock setting against the ceiling and the other enabled lock settings
+pub fn validate_lock_setting(
+    config: &BoostConfig,
+    index: Option<usize>,
+    setting: &VeSeilorLockSetting,
+) -> StdResult<()> {
+    if setting.duration.is_zero() {
+        return Err(StdError::generic_err("duration = 0"));
+    }
+    if setting.mining_boost.gt(&Uint128::new(MAX_MINING_BOOST)) {
+        return Err(StdError::generic_err("mining boost exceeds the ceiling"));
+    }
+    let duplicated = config
+        .ve_seilor_lock_settings
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != index && !config.disabled_lock_settings.contains(&(*i as u32)))
+        .any(|(_, other)| other.duration == setting.duration);
+    if duplicated {
+        return Err(StdError::generic_err("duplicate lock duration"));
+    }
+    Ok(())
+}
 
 pub fn add_lock_setting(
     deps: DepsMut,
@@ -20,20 +53,98 @@ pub fn add_lock_setting(
         return Err(StdError::generic_err("unauthorized"));
     }
 
-    let mut ve_seilor_lock_settings = config.ve_seilor_lock_settings;
-    ve_seilor_lock_settings.push(VeSeilorLockSetting {
+    let setting = VeSeilorLockSetting {
         duration,
         mining_boost,
-    });
-    config.ve_seilor_lock_settings = ve_seilor_lock_settings;
+    };
+    validate_lock_setting(&config, None, &setting)?;
+    let index = config.ve_seilor_lock_settings.len();
+    config.ve_seilor_lock_settings.push(setting);
     store_boost_config(deps.storage, &config)?;
     Ok(Response::new().add_attributes(vec![
         ("action", "add_lock_setting"),
+        ("index", index.to_string().as_str()),
         ("duration", duration.to_string().as_str()),
         ("mining_boost", mining_boost.to_string().as_str()),
     ]))
 }
 
+// Function to change a lock setting, users already locked keep the terms they locked with
+pub fn update_lock_setting(
+    deps: DepsMut,
+    info: MessageInfo,
+    index: usize,
+    duration: Uint128,
+    mining_boost: Uint128,
+) -> StdResult<Response> {
+    let mut config = read_boost_config(deps.storage)?;
+    if info.sender != config.gov {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if index >= config.ve_seilor_lock_settings.len() {
+        return Err(StdError::generic_err("lock setting not found"));
+    }
+
+    let setting = VeSeilorLockSetting {
+        duration,
+        mining_boost,
+    };
+    validate_lock_setting(&config, Some(index), &setting)?;
+    config.ve_seilor_lock_settings[index] = setting;
+    store_boost_config(deps.storage, &config)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_lock_setting"),
+        ("index", index.to_string().as_str()),
+        ("duration", duration.to_string().as_str()),
+        ("mining_boost", mining_boost.to_string().as_str()),
+    ]))
+}
+
+// Function to retire a lock setting, it can no longer be chosen by lock or extend_lock.
+// This is how a setting is removed: ids are positions in the list, so the entry itself stays
+pub fn disable_lock_setting(deps: DepsMut, info: MessageInfo, index: u32) -> StdResult<Response> {
+    let mut config = read_boost_config(deps.storage)?;
+    if info.sender != config.gov {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if index as usize >= config.ve_seilor_lock_settings.len() {
+        return Err(StdError::generic_err("lock setting not found"));
+    }
+    if config.disabled_lock_settings.contains(&index) {
+        return Err(StdError::generic_err("lock setting already disabled"));
+    }
+
+    config.disabled_lock_settings.push(index);
+    store_boost_config(deps.storage, &config)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "disable_lock_setting"),
+        ("index", index.to_string().as_str()),
+    ]))
+}
+
+pub fn enable_lock_setting(deps: DepsMut, info: MessageInfo, index: u32) -> StdResult<Response> {
+    let mut config = read_boost_config(deps.storage)?;
+    if info.sender != config.gov {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if !config.disabled_lock_settings.contains(&index) {
+        return Err(StdError::generic_err("lock setting not disabled"));
+    }
+
+    config.disabled_lock_settings.retain(|i| *i != index);
+    let setting = config
+        .ve_seilor_lock_settings
+        .get(index as usize)
+        .cloned()
+        .ok_or_else(|| StdError::generic_err("lock setting not found"))?;
+    validate_lock_setting(&config, Some(index as usize), &setting)?;
+    store_boost_config(deps.storage, &config)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "enable_lock_setting"),
+        ("index", index.to_string().as_str()),
+    ]))
+}
+
 pub fn set_gov(deps: DepsMut, info: MessageInfo, gov: Addr) -> StdResult<Response> {
     let mut config = read_boost_config(deps.storage)?;
     if info.sender != config.gov {
@@ -66,34 +177,462 @@ pub fn accept_gov(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
         ("gov", config.gov.to_string().as_str()),
     ]))
 }
-// Function to set the user's lock status
-pub fn set_lock_status(
+// Function to read the lock setting chosen by the user
+fn read_lock_setting(storage: &dyn Storage, index: usize) -> StdResult<VeSeilorLockSetting> {
+    let config = read_boost_config(storage)?;
+    let setting = config
+        .ve_seilor_lock_settings
+        .get(index)
+        .cloned()
+        .ok_or_else(|| StdError::generic_err("lock setting not found"))?;
+    if config.disabled_lock_settings.contains(&(index as u32)) {
+        return Err(StdError::generic_err("lock setting disabled"));
+    }
+    Ok(setting)
+}
+
+// Function to log the user's lock status after a lock action
+fn record_lock_history(
+    storage: &mut dyn Storage,
+    env: &Env,
+    user: Addr,
+    action: &str,
+    user_status: &LockStatus,
+) -> StdResult<()> {
+    let history = LockHistory {
+        action: action.to_string(),
+        timestamp: Uint128::from(env.block.time.seconds()),
+        unlock_time: user_status.unlock_time,
+        duration: user_status.duration,
+        mining_boost: user_status.mining_boost,
+        locked_amount: user_status.locked_amount,
+    };
+    store_user_lock_history(storage, user, &history)?;
+    Ok(())
+}
+
+// Function to make sure the user holds enough ve_seilor to back the lock
+fn check_ve_seilor_balance(deps: Deps, user: Addr, locked_amount: Uint128) -> StdResult<()> {
+    let config = read_boost_config(deps.storage)?;
+    let balance = query_ve_seilor_balance(deps, config.ve_seilor_addr, user)?;
+    if balance.lt(&locked_amount) {
+        return Err(StdError::generic_err("insufficient ve_seilor balance"));
+    }
+    Ok(())
+}
+
+// Function to lock ve_seilor for the duration of a lock setting and receive its mining boost
+pub fn lock(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     index: usize,
+    amount: Uint128,
 ) -> StdResult<Response> {
     let sender = info.sender;
-    let config = read_boost_config(deps.storage)?;
-    let setting: VeSeilorLockSetting = config.ve_seilor_lock_settings[index].clone();
+    if amount.is_zero() {
+        return Err(StdError::generic_err("amount = 0"));
+    }
+    let setting = read_lock_setting(deps.storage, index)?;
+    let user_status = read_user_lock_status(deps.storage, sender.clone())?;
+    if !user_status.locked_amount.is_zero() {
+        return Err(StdError::generic_err(
+            "lock already exists, extend it or increase its amount instead",
+        ));
+    }
+    check_ve_seilor_balance(deps.as_ref(), sender.clone(), amount)?;
+
+    let current_time = Uint128::from(env.block.time.seconds());
+    let user_status = LockStatus {
+        unlock_time: current_time.checked_add(setting.duration)?,
+        duration: setting.duration,
+        mining_boost: setting.mining_boost,
+        locked_amount: amount,
+    };
+    store_user_lock_status(deps.storage, sender.clone(), &user_status)?;
+    record_lock_history(deps.storage, &env, sender.clone(), "lock", &user_status)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lock"),
+        ("user", sender.to_string().as_str()),
+        ("index", index.to_string().as_str()),
+        ("amount", amount.to_string().as_str()),
+        ("unlock_time", user_status.unlock_time.to_string().as_str()),
+    ]))
+}
+
+// Function to move an existing lock to a longer (or equal) lock setting, restarting its duration
+pub fn extend_lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: usize,
+) -> StdResult<Response> {
+    let sender = info.sender;
+    let setting = read_lock_setting(deps.storage, index)?;
     let mut user_status = read_user_lock_status(deps.storage, sender.clone())?;
-    if user_status
-        .unlock_time
-        .gt(&Uint128::from(env.block.time.seconds()))
-    {
-        if user_status.duration.gt(&setting.duration) {
-            return Err(StdError::generic_err("Your lock-in period has not ended, and the term can only be extended, not reduced."));
-        }
+    if user_status.locked_amount.is_zero() {
+        return Err(StdError::generic_err("no lock found"));
     }
-    user_status.unlock_time = Uint128::from(env.block.time.seconds())
-        .checked_add(setting.duration)
-        .unwrap();
+    let current_time = Uint128::from(env.block.time.seconds());
+    let unlock_time = current_time.checked_add(setting.duration)?;
+    if user_status.duration.gt(&setting.duration) || user_status.unlock_time.gt(&unlock_time) {
+        return Err(StdError::generic_err(
+            "Your lock-in period has not ended, and the term can only be extended, not reduced.",
+        ));
+    }
+    // the locked ve_seilor could have been unstaked from fund before this contract knew about it
+    check_ve_seilor_balance(deps.as_ref(), sender.clone(), user_status.locked_amount)?;
+    // a tier lowered by gov can't leave the active delegations above what the sender has
+    let delegated = read_user_delegated(deps.storage, sender.clone())?;
+    let delegated = prune_expired_delegations(deps.storage, delegated, current_time);
+    let mut delegated_boost = Uint128::zero();
+    for (_, delegation) in delegated.iter() {
+        delegated_boost = delegated_boost.checked_add(delegation.boost)?;
+    }
+    if delegated_boost.gt(&setting.mining_boost) {
+        return Err(StdError::generic_err(
+            "delegated boost exceeds the mining boost of the lock",
+        ));
+    }
+
+    user_status.unlock_time = unlock_time;
     user_status.duration = setting.duration;
     user_status.mining_boost = setting.mining_boost;
     store_user_lock_status(deps.storage, sender.clone(), &user_status)?;
+    record_lock_history(
+        deps.storage,
+        &env,
+        sender.clone(),
+        "extend_lock",
+        &user_status,
+    )?;
     Ok(Response::new().add_attributes(vec![
-        ("action", "set_lock_status"),
+        ("action", "extend_lock"),
         ("user", sender.to_string().as_str()),
         ("index", index.to_string().as_str()),
+        ("unlock_time", unlock_time.to_string().as_str()),
+    ]))
+}
+
+// Function to add ve_seilor to an active lock, keeping its unlock time
+pub fn increase_lock_amount(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let sender = info.sender;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("amount = 0"));
+    }
+    let mut user_status = read_user_lock_status(deps.storage, sender.clone())?;
+    if user_status.locked_amount.is_zero() {
+        return Err(StdError::generic_err("no lock found"));
+    }
+    if user_status
+        .unlock_time
+        .le(&Uint128::from(env.block.time.seconds()))
+    {
+        return Err(StdError::generic_err("lock expired"));
+    }
+    let locked_amount = user_status.locked_amount.checked_add(amount)?;
+    check_ve_seilor_balance(deps.as_ref(), sender.clone(), locked_amount)?;
+
+    user_status.locked_amount = locked_amount;
+    store_user_lock_status(deps.storage, sender.clone(), &user_status)?;
+    record_lock_history(
+        deps.storage,
+        &env,
+        sender.clone(),
+        "increase_lock_amount",
+        &user_status,
+    )?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "increase_lock_amount"),
+        ("user", sender.to_string().as_str()),
+        ("amount", amount.to_string().as_str()),
+        ("locked_amount", locked_amount.to_string().as_str()),
+    ]))
+}
+
+// Function to release an expired lock, the user loses the mining boost
+pub fn unlock(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let sender = info.sender;
+    let user_status = read_user_lock_status(deps.storage, sender.clone())?;
+    if user_status.locked_amount.is_zero() {
+        return Err(StdError::generic_err("no lock found"));
+    }
+    if user_status
+        .unlock_time
+        .gt(&Uint128::from(env.block.time.seconds()))
+    {
+        return Err(StdError::generic_err("not unlocked yet"));
+    }
+    remove_user_lock_status(deps.storage, sender.clone());
+    let released = read_user_lock_status(deps.storage, sender.clone())?;
+    record_lock_history(deps.storage, &env, sender.clone(), "unlock", &released)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "unlock"),
+        ("user", sender.to_string().as_str()),
+        ("amount", user_status.locked_amount.to_string().as_str()),
+    ]))
+}
+
+pub fn set_delegation_nft(
+    deps: DepsMut,
+    info: MessageInfo,
+    nft_addr: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config = read_boost_config(deps.storage)?;
+    if info.sender != config.gov {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if let Some(nft_addr) = nft_addr.clone() {
+        deps.api.addr_validate(nft_addr.as_str())?;
+    }
+
+    config.delegation_nft_addr = nft_addr.clone();
+    store_boost_config(deps.storage, &config)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_delegation_nft"),
+        (
+            "nft_addr",
+            nft_addr
+                .map(|addr| addr.to_string())
+                .unwrap_or_default()
+                .as_str(),
+        ),
+    ]))
+}
+
+// Function to give a staking pool its own lock settings, an empty list puts it back on the default ones
+pub fn set_pool_lock_settings(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool: Addr,
+    lock_settings: Vec<VeSeilorLockSetting>,
+) -> StdResult<Response> {
+    let config = read_boost_config(deps.storage)?;
+    if info.sender != config.gov {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    deps.api.addr_validate(pool.as_str())?;
+
+    if lock_settings.is_empty() {
+        remove_pool_lock_settings(deps.storage, pool.clone());
+    } else {
+        // checked like the default lock settings, all of them enabled
+        let mut pool_config = BoostConfig {
+            ve_seilor_lock_settings: vec![],
+            disabled_lock_settings: vec![],
+            ..config
+        };
+        for setting in lock_settings.iter() {
+            validate_lock_setting(&pool_config, None, setting)?;
+            pool_config.ve_seilor_lock_settings.push(setting.clone());
+        }
+        store_pool_lock_settings(deps.storage, pool.clone(), &lock_settings)?;
+    }
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_pool_lock_settings"),
+        ("pool", pool.to_string().as_str()),
+        ("lock_settings", lock_settings.len().to_string().as_str()),
+    ]))
+}
+
+// Drops the expired delegations of a list, returning the ones still active
+fn prune_expired_delegations(
+    storage: &mut dyn Storage,
+    delegations: Vec<(u64, Delegation)>,
+    current_time: Uint128,
+) -> Vec<(u64, Delegation)> {
+    let (active, expired): (Vec<_>, Vec<_>) = delegations
+        .into_iter()
+        .partition(|(_, delegation)| delegation.expire_time.gt(&current_time));
+    for (id, delegation) in expired {
+        remove_delegation(storage, id, &delegation);
+    }
+    active
+}
+
+// Function to lend part of the sender's mining boost to another user, at most until the sender's unlock time
+pub fn delegate_boost(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receiver: Addr,
+    boost: Uint128,
+    expire_time: Uint128,
+    mint_receipt: bool,
+) -> StdResult<Response> {
+    let sender = info.sender;
+    deps.api.addr_validate(receiver.as_str())?;
+    if receiver == sender {
+        return Err(StdError::generic_err("cannot delegate to yourself"));
+    }
+    if boost.is_zero() {
+        return Err(StdError::generic_err("boost = 0"));
+    }
+    let current_time = Uint128::from(env.block.time.seconds());
+    let user_status = read_user_lock_status(deps.storage, sender.clone())?;
+    if user_status.locked_amount.is_zero() || user_status.unlock_time.le(&current_time) {
+        return Err(StdError::generic_err("no active lock"));
+    }
+    if expire_time.le(&current_time) || expire_time.gt(&user_status.unlock_time) {
+        return Err(StdError::generic_err(
+            "expire time must be within the lock period",
+        ));
+    }
+
+    let delegated = read_user_delegated(deps.storage, sender.clone())?;
+    let delegated = prune_expired_delegations(deps.storage, delegated, current_time);
+    if delegated.len() >= MAX_ACTIVE_DELEGATIONS {
+        return Err(StdError::generic_err("too many delegations"));
+    }
+    let mut delegated_boost = boost;
+    for (_, delegation) in delegated.iter() {
+        delegated_boost = delegated_boost.checked_add(delegation.boost)?;
+    }
+    if delegated_boost.gt(&user_status.mining_boost) {
+        return Err(StdError::generic_err("insufficient boost to delegate"));
+    }
+    let received = read_user_received(deps.storage, receiver.clone())?;
+    let received = prune_expired_delegations(deps.storage, received, current_time);
+    if received.len() >= MAX_ACTIVE_DELEGATIONS {
+        return Err(StdError::generic_err("too many delegations"));
+    }
+
+    let receipt_addr = if mint_receipt {
+        let config = read_boost_config(deps.storage)?;
+        Some(
+            config
+                .delegation_nft_addr
+                .ok_or_else(|| StdError::generic_err("delegation nft not set"))?,
+        )
+    } else {
+        None
+    };
+    let id = next_delegation_id(deps.storage)?;
+    let delegation = Delegation {
+        delegator: sender.clone(),
+        receiver: receiver.clone(),
+        boost,
+        expire_time,
+        receipt_addr: receipt_addr.clone(),
+    };
+    store_delegation(deps.storage, id, &delegation)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let Some(receipt_addr) = receipt_addr {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: receipt_addr.to_string(),
+            msg: to_binary(&Cw721ExecuteMsg::Mint {
+                token_id: id.to_string(),
+                owner: receiver.to_string(),
+                token_uri: None,
+                extension: None,
+            })?,
+            funds: vec![],
+        }));
+    }
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "delegate_boost"),
+        ("id", id.to_string().as_str()),
+        ("delegator", sender.to_string().as_str()),
+        ("receiver", receiver.to_string().as_str()),
+        ("boost", boost.to_string().as_str()),
+        ("expire_time", expire_time.to_string().as_str()),
+    ]))
+}
+
+// Function to hand a delegation to the current owner of its receipt, callable by anyone after a transfer
+pub fn sync_delegation(deps: DepsMut, env: Env, id: u64) -> StdResult<Response> {
+    let delegation = read_delegation(deps.storage, id)?;
+    let receipt_addr = delegation
+        .receipt_addr
+        .clone()
+        .ok_or_else(|| StdError::generic_err("delegation has no receipt"))?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    if delegation.expire_time.le(&current_time) {
+        return Err(StdError::generic_err("delegation expired"));
+    }
+    let owner = query_receipt_owner(deps.as_ref(), receipt_addr, id)?;
+    if owner == delegation.receiver {
+        return Err(StdError::generic_err("delegation already synced"));
+    }
+
+    let received = read_user_received(deps.storage, owner.clone())?;
+    let received = prune_expired_delegations(deps.storage, received, current_time);
+    if received.len() >= MAX_ACTIVE_DELEGATIONS {
+        return Err(StdError::generic_err("too many delegations"));
+    }
+    update_delegation_receiver(deps.storage, id, &delegation, owner.clone())?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "sync_delegation"),
+        ("id", id.to_string().as_str()),
+        ("from", delegation.receiver.to_string().as_str()),
+        ("to", owner.to_string().as_str()),
+    ]))
+}
+
+// Function to drop the expired delegations a user has lent out or received, callable by anyone
+pub fn clear_expired_delegations(deps: DepsMut, env: Env, user: Addr) -> StdResult<Response> {
+    let current_time = Uint128::from(env.block.time.seconds());
+    let delegated = read_user_delegated(deps.storage, user.clone())?;
+    let delegated_count = delegated.len();
+    let delegated = prune_expired_delegations(deps.storage, delegated, current_time);
+    let received = read_user_received(deps.storage, user.clone())?;
+    let received_count = received.len();
+    let received = prune_expired_delegations(deps.storage, received, current_time);
+    let cleared = delegated_count - delegated.len() + received_count - received.len();
+    Ok(Response::new().add_attributes(vec![
+        ("action", "clear_expired_delegations"),
+        ("user", user.to_string().as_str()),
+        ("cleared", cleared.to_string().as_str()),
+    ]))
+}
+
+// Function to burn the receipt of an expired delegation, its holder sends it to boost with SendNft
+pub fn receive_delegation_receipt(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> StdResult<Response> {
+    let id: u64 = msg
+        .token_id
+        .parse()
+        .map_err(|_| StdError::generic_err("invalid delegation receipt"))?;
+    match may_read_delegation(deps.storage, id)? {
+        Some(delegation) => {
+            if delegation.receipt_addr != Some(info.sender.clone()) {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            let current_time = Uint128::from(env.block.time.seconds());
+            if delegation.expire_time.gt(&current_time) {
+                return Err(StdError::generic_err("delegation not expired"));
+            }
+            remove_delegation(deps.storage, id, &delegation);
+        }
+        // already dropped when it expired
+        None => {
+            let config = read_boost_config(deps.storage)?;
+            if config.delegation_nft_addr != Some(info.sender.clone()) {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+        }
+    }
+
+    let burn_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Burn {
+            token_id: msg.token_id.clone(),
+        })?,
+        funds: vec![],
+    });
+    Ok(Response::new().add_message(burn_msg).add_attributes(vec![
+        ("action", "burn_delegation_receipt"),
+        ("id", msg.token_id.as_str()),
+        ("sender", msg.sender.as_str()),
     ]))
 }
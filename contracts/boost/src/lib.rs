@@ -2,6 +2,7 @@ pub mod state;
 pub mod msg;
 mod handler;
 mod querier;
+mod third_msg;
 pub mod contract;
 
 #[cfg(test)]
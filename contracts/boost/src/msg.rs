@@ -1,4 +1,5 @@
 use crate::state::VeSeilorLockSetting;
+use crate::third_msg::Cw721ReceiveMsg;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128};
 
@@ -7,11 +8,13 @@ pub struct LockStatusResponse {
     pub unlock_time: Uint128,
     pub duration: Uint128,
     pub mining_boost: Uint128,
+    pub locked_amount: Uint128,
 }
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub gov: Option<Addr>,
+    pub ve_seilor_addr: Addr,
     pub ve_seilor_lock_settings: Vec<VeSeilorLockSetting>,
 }
 
@@ -21,12 +24,51 @@ pub enum ExecuteMsg {
         duration: Uint128,
         mining_boost: Uint128,
     },
+    UpdateLockSetting {
+        index: u32,
+        duration: Uint128,
+        mining_boost: Uint128,
+    },
+    DisableLockSetting {
+        index: u32,
+    },
+    EnableLockSetting {
+        index: u32,
+    },
     SetGov {
         gov: Addr,
     },
     AcceptGov {},
-    SetLockStatus {
+    Lock {
         index: u32,
+        amount: Uint128,
+    },
+    ExtendLock {
+        index: u32,
+    },
+    IncreaseLockAmount {
+        amount: Uint128,
+    },
+    Unlock {},
+    SetDelegationNft {
+        nft_addr: Option<Addr>,
+    },
+    DelegateBoost {
+        receiver: Addr,
+        boost: Uint128,
+        expire_time: Uint128,
+        mint_receipt: bool,
+    },
+    SyncDelegation {
+        id: u64,
+    },
+    ClearExpiredDelegations {
+        user: Addr,
+    },
+    ReceiveNft(Cw721ReceiveMsg),
+    SetPoolLockSettings {
+        pool: Addr,
+        lock_settings: Vec<VeSeilorLockSetting>,
     },
 }
 
@@ -42,16 +84,38 @@ pub enum QueryMsg {
         user: Addr,
         user_updated_at: Uint128,
         finish_at: Uint128,
+        pool: Option<Addr>,
     },
     #[returns(GetBoostConfigResponse)]
     GetBoostConfig {},
+    #[returns(GetUserWorkingBalanceResponse)]
+    GetUserWorkingBalance {
+        user: Addr,
+        balance: Uint128,
+        total_supply: Uint128,
+    },
+    #[returns(DelegationResponse)]
+    GetDelegation { id: u64 },
+    #[returns(UserDelegationsResponse)]
+    GetUserDelegations { user: Addr },
+    #[returns(PoolLockSettingsResponse)]
+    GetPoolLockSettings { pool: Addr },
+    #[returns(LockHistoryResponse)]
+    GetUserLockHistory {
+        user: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub struct GetBoostConfigResponse {
     pub gov: Addr,
+    pub ve_seilor_addr: Addr,
     pub ve_seilor_lock_settings: Vec<VeSeilorLockSetting>,
     pub new_gov: Option<Addr>,
+    pub disabled_lock_settings: Vec<u32>,
+    pub delegation_nft_addr: Option<Addr>,
 }
 
 #[cw_serde]
@@ -65,4 +129,52 @@ pub struct GetUserBoostResponse {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct GetUserWorkingBalanceResponse {
+    pub working_balance: Uint128,
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub id: u64,
+    pub delegator: Addr,
+    pub receiver: Addr,
+    pub boost: Uint128,
+    pub expire_time: Uint128,
+    pub receipt_addr: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct UserDelegationsResponse {
+    pub delegated: Vec<DelegationResponse>,
+    pub received: Vec<DelegationResponse>,
+}
+
+#[cw_serde]
+pub struct PoolLockSettingsResponse {
+    pub pool: Addr,
+    // Empty when the pool uses the default lock settings
+    pub lock_settings: Vec<VeSeilorLockSetting>,
+}
+
+#[cw_serde]
+pub struct LockHistoryItem {
+    pub id: u64,
+    pub action: String,
+    pub timestamp: Uint128,
+    pub unlock_time: Uint128,
+    pub duration: Uint128,
+    pub mining_boost: Uint128,
+    pub locked_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct LockHistoryResponse {
+    pub history: Vec<LockHistoryItem>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    // Required when migrating from a version before 0.2.0, whose config has no ve_seilor
+    #[serde(default)]
+    pub ve_seilor_addr: Option<Addr>,
+}
@@ -1,8 +1,16 @@
 use crate::msg::{
-    GetBoostConfigResponse, GetUnlockTimeResponse, GetUserBoostResponse, LockStatusResponse,
+    DelegationResponse, GetBoostConfigResponse, GetUnlockTimeResponse, GetUserBoostResponse,
+    GetUserWorkingBalanceResponse, LockHistoryItem, LockHistoryResponse, LockStatusResponse,
+    PoolLockSettingsResponse, UserDelegationsResponse,
 };
-use crate::state::{read_user_lock_status, LockStatus};
-use cosmwasm_std::{Addr, Deps, Env, StdResult, Uint128};
+use crate::state::{
+    read_boost_config, read_delegation, read_pool_lock_settings, read_user_delegated,
+    read_user_lock_history, read_user_lock_status, read_user_received, Delegation, LockStatus,
+    TOKENLESS_PRODUCTION,
+};
+use crate::third_msg::{Cw721QueryMsg, OwnerOfResponse};
+use cosmwasm_std::{to_binary, Addr, Deps, Env, QueryRequest, StdResult, Uint128, WasmQuery};
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
 // Function to get the user's unlock time
 pub fn get_unlock_time(deps: Deps, user: Addr) -> StdResult<GetUnlockTimeResponse> {
@@ -18,6 +26,7 @@ pub fn get_user_lock_status(deps: Deps, user: Addr) -> StdResult<LockStatusRespo
         unlock_time: user_lock_status.unlock_time,
         duration: user_lock_status.duration,
         mining_boost: user_lock_status.mining_boost,
+        locked_amount: user_lock_status.locked_amount,
     })
 }
 
@@ -25,6 +34,9 @@ pub fn get_user_lock_status(deps: Deps, user: Addr) -> StdResult<LockStatusRespo
  * @notice calculate the user's mining boost based on their lock status
  * @dev Based on the user's userUpdatedAt time, finishAt time, and the current time,
  * there are several scenarios that could occur, including no acceleration, full acceleration, and partial acceleration.
+ * When the querying pool has its own lock settings, the lock's mining boost is taken from them.
+ * Boost lent to other users is taken off the user's own boost, boost received is added on top of it,
+ * both scaled to the delegator's boost in the pool.
  */
 pub fn get_user_boost(
     deps: Deps,
@@ -32,12 +44,89 @@ pub fn get_user_boost(
     user: Addr,
     user_updated_at: Uint128,
     finish_at: Uint128,
+    pool: Option<Addr>,
 ) -> StdResult<GetUserBoostResponse> {
-    let user_lock_status = read_user_lock_status(deps.storage, user)?;
+    let user_lock_status = read_user_lock_status(deps.storage, user.clone())?;
     let current_time = Uint128::from(env.block.time.seconds());
-    let boost_end_time = user_lock_status.unlock_time;
-    let max_boost = user_lock_status.mining_boost;
 
+    let mut user_boost = calc_boost(
+        pool_mining_boost(deps, pool.clone(), &user_lock_status)?,
+        user_lock_status.unlock_time,
+        user_updated_at,
+        finish_at,
+        current_time,
+    );
+    for (_, delegation) in read_user_delegated(deps.storage, user.clone())? {
+        user_boost = user_boost.saturating_sub(calc_boost(
+            delegation_pool_boost(deps, pool.clone(), &delegation)?,
+            delegation.expire_time,
+            user_updated_at,
+            finish_at,
+            current_time,
+        ));
+    }
+    for (_, delegation) in read_user_received(deps.storage, user)? {
+        user_boost += calc_boost(
+            delegation_pool_boost(deps, pool.clone(), &delegation)?,
+            delegation.expire_time,
+            user_updated_at,
+            finish_at,
+            current_time,
+        );
+    }
+    return Ok(GetUserBoostResponse { user_boost });
+}
+
+// The lock's mining boost in a pool, the pool's lock setting with the longest duration the lock covers
+fn pool_mining_boost(
+    deps: Deps,
+    pool: Option<Addr>,
+    user_lock_status: &LockStatus,
+) -> StdResult<Uint128> {
+    // a lock kept from before 0.2.0 holds no ve_seilor and boosts nothing
+    if user_lock_status.locked_amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    let pool_lock_settings = match pool {
+        Some(pool) => read_pool_lock_settings(deps.storage, pool)?,
+        None => None,
+    };
+    Ok(match pool_lock_settings {
+        Some(lock_settings) => lock_settings
+            .iter()
+            .filter(|setting| setting.duration.le(&user_lock_status.duration))
+            .max_by_key(|setting| setting.duration)
+            .map(|setting| setting.mining_boost)
+            .unwrap_or_default(),
+        None => user_lock_status.mining_boost,
+    })
+}
+
+// A delegation lends a share of the delegator's default mining boost, in a pool it is worth
+// the same share of the delegator's boost there so the pool never sees more boost than was locked
+fn delegation_pool_boost(
+    deps: Deps,
+    pool: Option<Addr>,
+    delegation: &Delegation,
+) -> StdResult<Uint128> {
+    let delegator_status = read_user_lock_status(deps.storage, delegation.delegator.clone())?;
+    if delegator_status.mining_boost.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    let pool_boost = pool_mining_boost(deps, pool, &delegator_status)?;
+    Ok(delegation
+        .boost
+        .multiply_ratio(pool_boost, delegator_status.mining_boost))
+}
+
+// Average of max_boost over [user_updated_at, min(current_time, finish_at)] when it stops at boost_end_time
+fn calc_boost(
+    max_boost: Uint128,
+    boost_end_time: Uint128,
+    user_updated_at: Uint128,
+    finish_at: Uint128,
+    current_time: Uint128,
+) -> Uint128 {
     let user_boost;
     if user_updated_at.ge(&boost_end_time) || user_updated_at.ge(&finish_at) {
         user_boost = Uint128::zero();
@@ -58,14 +147,131 @@ pub fn get_user_boost(
                 .unwrap();
         }
     }
-    return Ok(GetUserBoostResponse { user_boost });
+    user_boost
 }
 
 pub fn get_boost_config(deps: Deps) -> StdResult<GetBoostConfigResponse> {
     let boost_config = crate::state::read_boost_config(deps.storage)?;
     Ok(GetBoostConfigResponse {
         gov: boost_config.gov,
+        ve_seilor_addr: boost_config.ve_seilor_addr,
         ve_seilor_lock_settings: boost_config.ve_seilor_lock_settings,
         new_gov: boost_config.new_gov,
+        disabled_lock_settings: boost_config.disabled_lock_settings,
+        delegation_nft_addr: boost_config.delegation_nft_addr,
+    })
+}
+
+// Returns the user's ve_seilor balance, the upper bound of what can be locked
+pub fn query_ve_seilor_balance(deps: Deps, ve_seilor_addr: Addr, user: Addr) -> StdResult<Uint128> {
+    let res: BalanceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: ve_seilor_addr.to_string(),
+        msg: to_binary(&Cw20QueryMsg::Balance {
+            address: user.to_string(),
+        })?,
+    }))?;
+    Ok(res.balance)
+}
+
+/**
+ * @notice calculate the user's working balance, Curve style
+ * @dev working_balance = min(0.4 * balance + 0.6 * total_supply * user_ve / total_ve, balance),
+ * where balance and total_supply are the user's and the pool's staked amounts.
+ */
+pub fn get_user_working_balance(
+    deps: Deps,
+    user: Addr,
+    balance: Uint128,
+    total_supply: Uint128,
+) -> StdResult<GetUserWorkingBalanceResponse> {
+    let config = read_boost_config(deps.storage)?;
+    let user_ve = query_ve_seilor_balance(deps, config.ve_seilor_addr.clone(), user)?;
+    let token_info: TokenInfoResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.ve_seilor_addr.to_string(),
+            msg: to_binary(&Cw20QueryMsg::TokenInfo {})?,
+        }))?;
+    let total_ve = token_info.total_supply;
+
+    let mut working_balance = balance.multiply_ratio(TOKENLESS_PRODUCTION, 100u128);
+    if !total_ve.is_zero() {
+        working_balance += total_supply
+            .multiply_ratio(user_ve, total_ve)
+            .multiply_ratio(100u128 - TOKENLESS_PRODUCTION, 100u128);
+    }
+    Ok(GetUserWorkingBalanceResponse {
+        working_balance: working_balance.min(balance),
     })
 }
+
+fn delegation_response(id: u64, delegation: Delegation) -> DelegationResponse {
+    DelegationResponse {
+        id,
+        delegator: delegation.delegator,
+        receiver: delegation.receiver,
+        boost: delegation.boost,
+        expire_time: delegation.expire_time,
+        receipt_addr: delegation.receipt_addr,
+    }
+}
+
+pub fn get_delegation(deps: Deps, id: u64) -> StdResult<DelegationResponse> {
+    let delegation = read_delegation(deps.storage, id)?;
+    Ok(delegation_response(id, delegation))
+}
+
+pub fn get_user_delegations(deps: Deps, user: Addr) -> StdResult<UserDelegationsResponse> {
+    let delegated = read_user_delegated(deps.storage, user.clone())?
+        .into_iter()
+        .map(|(id, delegation)| delegation_response(id, delegation))
+        .collect();
+    let received = read_user_received(deps.storage, user)?
+        .into_iter()
+        .map(|(id, delegation)| delegation_response(id, delegation))
+        .collect();
+    Ok(UserDelegationsResponse {
+        delegated,
+        received,
+    })
+}
+
+// Returns the current owner of a delegation receipt
+pub fn query_receipt_owner(deps: Deps, nft_addr: Addr, id: u64) -> StdResult<Addr> {
+    let res: OwnerOfResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: nft_addr.to_string(),
+        msg: to_binary(&Cw721QueryMsg::OwnerOf {
+            token_id: id.to_string(),
+            include_expired: None,
+        })?,
+    }))?;
+    deps.api.addr_validate(&res.owner)
+}
+
+pub fn get_pool_lock_settings(deps: Deps, pool: Addr) -> StdResult<PoolLockSettingsResponse> {
+    let lock_settings = read_pool_lock_settings(deps.storage, pool.clone())?.unwrap_or_default();
+    Ok(PoolLockSettingsResponse {
+        pool,
+        lock_settings,
+    })
+}
+
+pub fn get_user_lock_history(
+    deps: Deps,
+    user: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LockHistoryResponse> {
+    let history = read_user_lock_history(deps.storage, user, start_after, limit)?
+        .into_iter()
+        .map(|(id, history)| LockHistoryItem {
+            id,
+            action: history.action,
+            timestamp: history.timestamp,
+            unlock_time: history.unlock_time,
+            duration: history.duration,
+            mining_boost: history.mining_boost,
+            locked_amount: history.locked_amount,
+        })
+        .collect();
+    Ok(LockHistoryResponse { history })
+}
@@ -1,8 +1,23 @@
-use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+// Share (in percent) of the staked balance that always earns rewards in working balance mode,
+// the rest has to be earned with ve_seilor
+pub const TOKENLESS_PRODUCTION: u128 = 40u128;
+
+// Highest mining boost a lock setting can grant, 1e8 = 100%
+pub const MAX_MINING_BOOST: u128 = 1000000000u128;
+
+// Cap on the delegations a user can have out or received at the same time,
+// keeps the iteration in get_user_boost bounded
+pub const MAX_ACTIVE_DELEGATIONS: usize = 10;
+
+// settings for pagination
+pub const MAX_LIMIT: u32 = 30;
+pub const DEFAULT_LIMIT: u32 = 10;
+
 // Define a struct for the lock settings
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VeSeilorLockSetting {
@@ -16,10 +31,52 @@ pub struct LockStatus {
     pub unlock_time: Uint128,
     pub duration: Uint128,
     pub mining_boost: Uint128,
+    // Amount of ve_seilor committed to the lock, fund refuses to unstake it before unlock
+    #[serde(default)]
+    pub locked_amount: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BoostConfig {
+    pub gov: Addr,
+    pub ve_seilor_addr: Addr,
+    // Lock settings are never removed from the list, a lock setting id is its position in it,
+    // disabling a setting is how it is retired
+    pub ve_seilor_lock_settings: Vec<VeSeilorLockSetting>,
+    pub new_gov: Option<Addr>,
+    // Ids of the lock settings that can no longer be chosen
+    #[serde(default)]
+    pub disabled_lock_settings: Vec<u32>,
+    // CW721 contract minting a receipt per delegation, boost must be its minter
+    #[serde(default)]
+    pub delegation_nft_addr: Option<Addr>,
+}
+
+// Snapshot of a user's lock status taken after each lock action
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockHistory {
+    pub action: String,
+    pub timestamp: Uint128,
+    pub unlock_time: Uint128,
+    pub duration: Uint128,
+    pub mining_boost: Uint128,
+    #[serde(default)]
+    pub locked_amount: Uint128,
+}
+
+// Part of a delegator's mining boost lent to a receiver until expire_time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Delegation {
+    pub delegator: Addr,
+    pub receiver: Addr,
+    pub boost: Uint128,
+    pub expire_time: Uint128,
+    pub receipt_addr: Option<Addr>,
+}
+
+// Boost config as stored before the lock was backed by ve_seilor, only read by migrate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyBoostConfig {
     pub gov: Addr,
     pub ve_seilor_lock_settings: Vec<VeSeilorLockSetting>,
     pub new_gov: Option<Addr>,
@@ -27,8 +84,25 @@ pub struct BoostConfig {
 
 const BOOST_CONFIG: Item<BoostConfig> = Item::new("boost_config");
 
+const LEGACY_BOOST_CONFIG: Item<LegacyBoostConfig> = Item::new("boost_config");
+
 const USER_LOCK_STATUS: Map<Addr, LockStatus> = Map::new("user_lock_status");
 
+// Lock settings of a staking pool replacing the default ones when the pool queries the user's boost
+const POOL_LOCK_SETTINGS: Map<Addr, Vec<VeSeilorLockSetting>> = Map::new("pool_lock_settings");
+
+const USER_LOCK_HISTORY_COUNT: Map<Addr, u64> = Map::new("user_lock_history_count");
+
+const USER_LOCK_HISTORY: Map<(Addr, u64), LockHistory> = Map::new("user_lock_history");
+
+const DELEGATION_COUNT: Item<u64> = Item::new("delegation_count");
+
+const DELEGATIONS: Map<u64, Delegation> = Map::new("delegations");
+
+const USER_DELEGATED: Map<(Addr, u64), bool> = Map::new("user_delegated");
+
+const USER_RECEIVED: Map<(Addr, u64), bool> = Map::new("user_received");
+
 pub fn store_boost_config(storage: &mut dyn Storage, boost_config: &BoostConfig) -> StdResult<()> {
     BOOST_CONFIG.save(storage, boost_config)?;
     Ok(())
@@ -38,6 +112,10 @@ pub fn read_boost_config(storage: &dyn Storage) -> StdResult<BoostConfig> {
     BOOST_CONFIG.load(storage)
 }
 
+pub fn read_legacy_boost_config(storage: &dyn Storage) -> StdResult<LegacyBoostConfig> {
+    LEGACY_BOOST_CONFIG.load(storage)
+}
+
 pub fn store_user_lock_status(
     storage: &mut dyn Storage,
     user: Addr,
@@ -47,10 +125,134 @@ pub fn store_user_lock_status(
     Ok(())
 }
 
+pub fn remove_user_lock_status(storage: &mut dyn Storage, user: Addr) {
+    USER_LOCK_STATUS.remove(storage, user);
+}
+
 pub fn read_user_lock_status(storage: &dyn Storage, user: Addr) -> StdResult<LockStatus> {
-    Ok(USER_LOCK_STATUS.load(storage, user).unwrap_or(LockStatus {
-        unlock_time: Uint128::zero(),
-        duration: Uint128::zero(),
-        mining_boost: Uint128::zero(),
-    }))
+    Ok(USER_LOCK_STATUS
+        .may_load(storage, user)?
+        .unwrap_or(LockStatus {
+            unlock_time: Uint128::zero(),
+            duration: Uint128::zero(),
+            mining_boost: Uint128::zero(),
+            locked_amount: Uint128::zero(),
+        }))
+}
+
+pub fn store_pool_lock_settings(
+    storage: &mut dyn Storage,
+    pool: Addr,
+    lock_settings: &Vec<VeSeilorLockSetting>,
+) -> StdResult<()> {
+    POOL_LOCK_SETTINGS.save(storage, pool, lock_settings)?;
+    Ok(())
+}
+
+pub fn remove_pool_lock_settings(storage: &mut dyn Storage, pool: Addr) {
+    POOL_LOCK_SETTINGS.remove(storage, pool);
+}
+
+pub fn read_pool_lock_settings(
+    storage: &dyn Storage,
+    pool: Addr,
+) -> StdResult<Option<Vec<VeSeilorLockSetting>>> {
+    POOL_LOCK_SETTINGS.may_load(storage, pool)
+}
+
+pub fn store_user_lock_history(
+    storage: &mut dyn Storage,
+    user: Addr,
+    history: &LockHistory,
+) -> StdResult<u64> {
+    let id = USER_LOCK_HISTORY_COUNT
+        .may_load(storage, user.clone())?
+        .unwrap_or(0u64)
+        + 1;
+    USER_LOCK_HISTORY_COUNT.save(storage, user.clone(), &id)?;
+    USER_LOCK_HISTORY.save(storage, (user, id), history)?;
+    Ok(id)
+}
+
+pub fn read_user_lock_history(
+    storage: &dyn Storage,
+    user: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, LockHistory)>> {
+    let start = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    USER_LOCK_HISTORY
+        .prefix(user)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn next_delegation_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = DELEGATION_COUNT.may_load(storage)?.unwrap_or(0u64) + 1;
+    DELEGATION_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn store_delegation(
+    storage: &mut dyn Storage,
+    id: u64,
+    delegation: &Delegation,
+) -> StdResult<()> {
+    DELEGATIONS.save(storage, id, delegation)?;
+    USER_DELEGATED.save(storage, (delegation.delegator.clone(), id), &true)?;
+    USER_RECEIVED.save(storage, (delegation.receiver.clone(), id), &true)?;
+    Ok(())
+}
+
+pub fn remove_delegation(storage: &mut dyn Storage, id: u64, delegation: &Delegation) {
+    DELEGATIONS.remove(storage, id);
+    USER_DELEGATED.remove(storage, (delegation.delegator.clone(), id));
+    USER_RECEIVED.remove(storage, (delegation.receiver.clone(), id));
+}
+
+// Moves a delegation to a new receiver, used when its receipt changes hands
+pub fn update_delegation_receiver(
+    storage: &mut dyn Storage,
+    id: u64,
+    delegation: &Delegation,
+    receiver: Addr,
+) -> StdResult<Delegation> {
+    USER_RECEIVED.remove(storage, (delegation.receiver.clone(), id));
+    let mut delegation = delegation.clone();
+    delegation.receiver = receiver;
+    store_delegation(storage, id, &delegation)?;
+    Ok(delegation)
+}
+
+pub fn read_delegation(storage: &dyn Storage, id: u64) -> StdResult<Delegation> {
+    DELEGATIONS.load(storage, id)
+}
+
+pub fn may_read_delegation(storage: &dyn Storage, id: u64) -> StdResult<Option<Delegation>> {
+    DELEGATIONS.may_load(storage, id)
+}
+
+pub fn read_user_delegated(storage: &dyn Storage, user: Addr) -> StdResult<Vec<(u64, Delegation)>> {
+    read_user_delegations(storage, USER_DELEGATED, user)
+}
+
+pub fn read_user_received(storage: &dyn Storage, user: Addr) -> StdResult<Vec<(u64, Delegation)>> {
+    read_user_delegations(storage, USER_RECEIVED, user)
+}
+
+fn read_user_delegations(
+    storage: &dyn Storage,
+    index: Map<(Addr, u64), bool>,
+    user: Addr,
+) -> StdResult<Vec<(u64, Delegation)>> {
+    index
+        .prefix(user)
+        .keys(storage, None, None, Order::Ascending)
+        .map(|id| {
+            let id = id?;
+            Ok((id, DELEGATIONS.load(storage, id)?))
+        })
+        .collect()
 }
@@ -2,11 +2,21 @@
 mod tests {
     use crate::contract::{execute, instantiate};
     use crate::msg::{ExecuteMsg, GetBoostConfigResponse, InstantiateMsg, LockStatusResponse};
-    use crate::querier::{get_boost_config, get_user_lock_status};
-    use crate::state::VeSeilorLockSetting;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use crate::querier::{
+        get_boost_config, get_pool_lock_settings, get_user_boost, get_user_delegations,
+        get_user_lock_history, get_user_lock_status, get_user_working_balance,
+    };
+    use crate::state::{VeSeilorLockSetting, MAX_MINING_BOOST};
+    use crate::third_msg::{Cw721ExecuteMsg, Cw721ReceiveMsg};
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
     use cosmwasm_std::StdError::GenericErr;
-    use cosmwasm_std::{Addr, Uint128};
+    use cosmwasm_std::{
+        from_binary, to_binary, Addr, Binary, ContractResult, CosmosMsg, OwnedDeps, Response,
+        StdResult, SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
     fn ve_seilor_lock_settings() -> Vec<VeSeilorLockSetting> {
         vec![
@@ -35,6 +45,7 @@ mod tests {
 
         let _msg = InstantiateMsg {
             gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
             ve_seilor_lock_settings: ve_seilor_lock_settings(),
         };
         let _info = mock_info("creator", &[]);
@@ -46,8 +57,11 @@ mod tests {
             get_boost_config(deps.as_ref()).unwrap(),
             GetBoostConfigResponse {
                 gov: Addr::unchecked("gov"),
+                ve_seilor_addr: Addr::unchecked("ve_seilor"),
                 ve_seilor_lock_settings: ve_seilor_lock_settings(),
                 new_gov: None,
+                disabled_lock_settings: vec![],
+                delegation_nft_addr: None,
             }
         );
     }
@@ -58,6 +72,7 @@ mod tests {
 
         let _msg = InstantiateMsg {
             gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
             ve_seilor_lock_settings: ve_seilor_lock_settings(),
         };
         let _info = mock_info("creator", &[]);
@@ -69,8 +84,11 @@ mod tests {
             get_boost_config(deps.as_ref()).unwrap(),
             GetBoostConfigResponse {
                 gov: Addr::unchecked("gov"),
+                ve_seilor_addr: Addr::unchecked("ve_seilor"),
                 ve_seilor_lock_settings: ve_seilor_lock_settings(),
                 new_gov: None,
+                disabled_lock_settings: vec![],
+                delegation_nft_addr: None,
             }
         );
 
@@ -86,6 +104,7 @@ mod tests {
             get_boost_config(deps.as_ref()).unwrap(),
             GetBoostConfigResponse {
                 gov: Addr::unchecked("gov"),
+                ve_seilor_addr: Addr::unchecked("ve_seilor"),
                 ve_seilor_lock_settings: vec![
                     VeSeilorLockSetting {
                         duration: Uint128::from(2592000u128),
@@ -109,35 +128,83 @@ mod tests {
                     },
                 ],
                 new_gov: None,
+                disabled_lock_settings: vec![],
+                delegation_nft_addr: None,
             }
         );
     }
 
+    fn mock_ve_seilor(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        balance: Uint128,
+        total_supply: Uint128,
+    ) {
+        deps.querier.update_wasm(move |query| {
+            let res = match query {
+                WasmQuery::Smart { msg, .. } => match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { .. } => to_binary(&BalanceResponse { balance }),
+                    Cw20QueryMsg::TokenInfo {} => to_binary(&TokenInfoResponse {
+                        name: "Ve seilor token".to_string(),
+                        symbol: "VESEILOR".to_string(),
+                        decimals: 6,
+                        total_supply,
+                    }),
+                    _ => panic!("unexpected query"),
+                },
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+    }
+
+    fn mock_ve_seilor_balance(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        balance: Uint128,
+    ) {
+        mock_ve_seilor(deps, balance, balance);
+    }
+
+    fn assert_generic_err(res: StdResult<Response>, expected: &str) {
+        match res.unwrap_err() {
+            GenericErr { msg, .. } => assert_eq!(msg, expected.to_string()),
+            _ => panic!("unexpected error"),
+        }
+    }
+
     #[test]
-    fn test_set_lock_status() {
+    fn test_lock() {
         let mut deps = mock_dependencies();
 
         let _msg = InstantiateMsg {
             gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
             ve_seilor_lock_settings: ve_seilor_lock_settings(),
         };
         let _info = mock_info("creator", &[]);
         let _res = instantiate(deps.as_mut(), mock_env(), _info, _msg).unwrap();
-        assert_eq!(0, _res.messages.len());
+        mock_ve_seilor_balance(&mut deps, Uint128::from(1000u128));
 
-        // Verify the config is stored correctly
-        assert_eq!(
-            get_boost_config(deps.as_ref()).unwrap(),
-            GetBoostConfigResponse {
-                gov: Addr::unchecked("gov"),
-                ve_seilor_lock_settings: ve_seilor_lock_settings(),
-                new_gov: None,
-            }
-        );
+        // cannot lock more than the ve_seilor balance
+        let _msg = ExecuteMsg::Lock {
+            index: 0,
+            amount: Uint128::from(1001u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(_res, "insufficient ve_seilor balance");
 
-        let _msg = ExecuteMsg::SetLockStatus { index: 0 };
-        let _info = mock_info("lucky", &[]);
-        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        // unknown lock setting
+        let _msg = ExecuteMsg::Lock {
+            index: 4,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(_res, "lock setting not found");
+
+        let _msg = ExecuteMsg::Lock {
+            index: 0,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
         assert_eq!(0, _res.messages.len());
 
         // unlock_time = block.time.seconds(1_571_797_419) + duration
@@ -147,33 +214,691 @@ mod tests {
                 unlock_time: Uint128::from(1574389419u128),
                 duration: Uint128::from(2592000u128),
                 mining_boost: Uint128::from(20000000u128),
+                locked_amount: Uint128::from(100u128),
             }
         );
 
-        let _msg = ExecuteMsg::SetLockStatus { index: 1 };
-        let _info = mock_info("lucky", &[]);
-        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        // a second lock must go through extend_lock / increase_lock_amount
+        let _msg = ExecuteMsg::Lock {
+            index: 1,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(
+            _res,
+            "lock already exists, extend it or increase its amount instead",
+        );
+
+        let _msg = ExecuteMsg::ExtendLock { index: 1 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+        assert_eq!(0, _res.messages.len());
+
+        let _msg = ExecuteMsg::IncreaseLockAmount {
+            amount: Uint128::from(400u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
         assert_eq!(0, _res.messages.len());
 
-        // unlock_time = block.time.seconds(1_571_797_419) + duration
         assert_eq!(
             get_user_lock_status(deps.as_ref(), Addr::unchecked("lucky")).unwrap(),
             LockStatusResponse {
                 unlock_time: Uint128::from(1579573419u128),
                 duration: Uint128::from(7776000u128),
                 mining_boost: Uint128::from(30000000u128),
+                locked_amount: Uint128::from(500u128),
             }
         );
 
         // negative test case with lower duration
-        let _msg = ExecuteMsg::SetLockStatus { index: 0 };
-        let _info = mock_info("lucky", &[]);
-        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap_err();
-        match _res {
-            GenericErr { msg, .. } => {
-                assert_eq!(msg, "Your lock-in period has not ended, and the term can only be extended, not reduced.".to_string())
+        let _msg = ExecuteMsg::ExtendLock { index: 0 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(
+            _res,
+            "Your lock-in period has not ended, and the term can only be extended, not reduced.",
+        );
+
+        let _msg = ExecuteMsg::IncreaseLockAmount {
+            amount: Uint128::from(501u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(_res, "insufficient ve_seilor balance");
+
+        // unlock before the unlock time
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lucky", &[]),
+            ExecuteMsg::Unlock {},
+        );
+        assert_generic_err(_res, "not unlocked yet");
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(7776000u64);
+        let _res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("lucky", &[]),
+            ExecuteMsg::Unlock {},
+        )
+        .unwrap();
+        assert_eq!(0, _res.messages.len());
+
+        assert_eq!(
+            get_user_lock_status(deps.as_ref(), Addr::unchecked("lucky")).unwrap(),
+            LockStatusResponse {
+                unlock_time: Uint128::zero(),
+                duration: Uint128::zero(),
+                mining_boost: Uint128::zero(),
+                locked_amount: Uint128::zero(),
             }
-            _ => panic!("Set lock status return error"),
-        }
+        );
+        let _res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("lucky", &[]),
+            ExecuteMsg::Unlock {},
+        );
+        assert_generic_err(_res, "no lock found");
+    }
+
+    #[test]
+    fn test_get_user_working_balance() {
+        let mut deps = mock_dependencies();
+
+        let _msg = InstantiateMsg {
+            gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
+            ve_seilor_lock_settings: ve_seilor_lock_settings(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        // no ve_seilor: 40% of the balance
+        mock_ve_seilor(&mut deps, Uint128::zero(), Uint128::zero());
+        let res = get_user_working_balance(
+            deps.as_ref(),
+            Addr::unchecked("lucky"),
+            Uint128::from(1000u128),
+            Uint128::from(10000u128),
+        )
+        .unwrap();
+        assert_eq!(res.working_balance, Uint128::from(400u128));
+
+        // 5% of ve_seilor: 400 + 0.6 * 10000 * 5% = 700
+        mock_ve_seilor(&mut deps, Uint128::from(50u128), Uint128::from(1000u128));
+        let res = get_user_working_balance(
+            deps.as_ref(),
+            Addr::unchecked("lucky"),
+            Uint128::from(1000u128),
+            Uint128::from(10000u128),
+        )
+        .unwrap();
+        assert_eq!(res.working_balance, Uint128::from(700u128));
+
+        // capped at the balance
+        mock_ve_seilor(&mut deps, Uint128::from(500u128), Uint128::from(1000u128));
+        let res = get_user_working_balance(
+            deps.as_ref(),
+            Addr::unchecked("lucky"),
+            Uint128::from(1000u128),
+            Uint128::from(10000u128),
+        )
+        .unwrap();
+        assert_eq!(res.working_balance, Uint128::from(1000u128));
+    }
+
+    #[test]
+    fn test_manage_lock_settings() {
+        let mut deps = mock_dependencies();
+
+        let _msg = InstantiateMsg {
+            gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
+            ve_seilor_lock_settings: ve_seilor_lock_settings(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        mock_ve_seilor_balance(&mut deps, Uint128::from(1000u128));
+
+        let _msg = ExecuteMsg::Lock {
+            index: 0,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+        let lucky_status = get_user_lock_status(deps.as_ref(), Addr::unchecked("lucky")).unwrap();
+
+        // only gov
+        let _msg = ExecuteMsg::UpdateLockSetting {
+            index: 0,
+            duration: Uint128::from(2592000u128),
+            mining_boost: Uint128::from(25000000u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(_res, "unauthorized");
+
+        let _msg = ExecuteMsg::UpdateLockSetting {
+            index: 9,
+            duration: Uint128::from(2592000u128),
+            mining_boost: Uint128::from(25000000u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg);
+        assert_generic_err(_res, "lock setting not found");
+
+        let _msg = ExecuteMsg::UpdateLockSetting {
+            index: 0,
+            duration: Uint128::from(7776000u128),
+            mining_boost: Uint128::from(25000000u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg);
+        assert_generic_err(_res, "duplicate lock duration");
+
+        let _msg = ExecuteMsg::AddLockSetting {
+            duration: Uint128::from(61536000u128),
+            mining_boost: Uint128::from(MAX_MINING_BOOST + 1),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg);
+        assert_generic_err(_res, "mining boost exceeds the ceiling");
+
+        let _msg = ExecuteMsg::UpdateLockSetting {
+            index: 0,
+            duration: Uint128::from(2592000u128),
+            mining_boost: Uint128::from(25000000u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        assert_eq!(
+            get_boost_config(deps.as_ref())
+                .unwrap()
+                .ve_seilor_lock_settings[0],
+            VeSeilorLockSetting {
+                duration: Uint128::from(2592000u128),
+                mining_boost: Uint128::from(25000000u128),
+            }
+        );
+        // users already locked keep their terms
+        assert_eq!(
+            get_user_lock_status(deps.as_ref(), Addr::unchecked("lucky")).unwrap(),
+            lucky_status
+        );
+
+        let _msg = ExecuteMsg::DisableLockSetting { index: 0 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        assert_eq!(
+            get_boost_config(deps.as_ref())
+                .unwrap()
+                .disabled_lock_settings,
+            vec![0u32]
+        );
+        let _msg = ExecuteMsg::Lock {
+            index: 0,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("tom", &[]), _msg);
+        assert_generic_err(_res, "lock setting disabled");
+
+        // a disabled duration can be reused by a new lock setting
+        let _msg = ExecuteMsg::AddLockSetting {
+            duration: Uint128::from(2592000u128),
+            mining_boost: Uint128::from(30000000u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        let _msg = ExecuteMsg::Lock {
+            index: 4,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("tom", &[]), _msg).unwrap();
+
+        // and the old one can not be enabled again while the duration is taken
+        let _msg = ExecuteMsg::EnableLockSetting { index: 0 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg);
+        assert_generic_err(_res, "duplicate lock duration");
+        let _msg = ExecuteMsg::DisableLockSetting { index: 4 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        let _msg = ExecuteMsg::EnableLockSetting { index: 0 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        assert_eq!(
+            get_boost_config(deps.as_ref())
+                .unwrap()
+                .disabled_lock_settings,
+            vec![4u32]
+        );
+    }
+
+    #[test]
+    fn test_delegate_boost() {
+        let mut deps = mock_dependencies();
+
+        let _msg = InstantiateMsg {
+            gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
+            ve_seilor_lock_settings: ve_seilor_lock_settings(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        mock_ve_seilor_balance(&mut deps, Uint128::from(1000u128));
+
+        let _msg = ExecuteMsg::Lock {
+            index: 3,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+
+        let now = mock_env().block.time.seconds();
+        let boost_of =
+            |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, user: &str, at: u64| {
+                let mut env = mock_env();
+                env.block.time = env.block.time.plus_seconds(at - now);
+                get_user_boost(
+                    deps.as_ref(),
+                    env,
+                    Addr::unchecked(user),
+                    Uint128::from(at),
+                    Uint128::from(now + 100000),
+                    None,
+                )
+                .unwrap()
+                .user_boost
+            };
+        let delegate = |receiver: &str, boost: u128, expire_time: u64, mint_receipt: bool| {
+            ExecuteMsg::DelegateBoost {
+                receiver: Addr::unchecked(receiver),
+                boost: Uint128::from(boost),
+                expire_time: Uint128::from(expire_time),
+                mint_receipt,
+            }
+        };
+
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lucky", &[]),
+            delegate("lucky", 40000000u128, now + 1000, false),
+        );
+        assert_generic_err(_res, "cannot delegate to yourself");
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("jerry", &[]),
+            delegate("tom", 40000000u128, now + 1000, false),
+        );
+        assert_generic_err(_res, "no active lock");
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lucky", &[]),
+            delegate("tom", 40000000u128, now + 31536001, false),
+        );
+        assert_generic_err(_res, "expire time must be within the lock period");
+
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lucky", &[]),
+            delegate("tom", 40000000u128, now + 1000, false),
+        )
+        .unwrap();
+        assert_eq!(boost_of(&deps, "lucky", now), Uint128::from(60000000u128));
+        assert_eq!(boost_of(&deps, "tom", now), Uint128::from(40000000u128));
+
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lucky", &[]),
+            delegate("jerry", 70000000u128, now + 1000, false),
+        );
+        assert_generic_err(_res, "insufficient boost to delegate");
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lucky", &[]),
+            delegate("jerry", 20000000u128, now + 2000, true),
+        );
+        assert_generic_err(_res, "delegation nft not set");
+
+        // delegate with a receipt
+        let _msg = ExecuteMsg::SetDelegationNft {
+            nft_addr: Some(Addr::unchecked("boost_nft")),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lucky", &[]),
+            delegate("jerry", 20000000u128, now + 2000, true),
+        )
+        .unwrap();
+        assert_eq!(
+            _res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "boost_nft".to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::Mint {
+                    token_id: "2".to_string(),
+                    owner: "jerry".to_string(),
+                    token_uri: None,
+                    extension: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(boost_of(&deps, "lucky", now), Uint128::from(40000000u128));
+
+        // the receipt was traded to alice
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "boost_nft" => {
+                SystemResult::Ok(ContractResult::Ok(Binary::from(
+                    br#"{"owner":"alice","approvals":[]}"#.as_slice(),
+                )))
+            }
+            _ => panic!("unexpected query"),
+        });
+        let _msg = ExecuteMsg::SyncDelegation { id: 1 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), _msg);
+        assert_generic_err(_res, "delegation has no receipt");
+        let _msg = ExecuteMsg::SyncDelegation { id: 2 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), _msg).unwrap();
+        assert_eq!(boost_of(&deps, "jerry", now), Uint128::zero());
+        assert_eq!(boost_of(&deps, "alice", now), Uint128::from(20000000u128));
+        let delegations = get_user_delegations(deps.as_ref(), Addr::unchecked("lucky")).unwrap();
+        assert_eq!(delegations.delegated.len(), 2);
+        assert_eq!(delegations.delegated[1].receiver, Addr::unchecked("alice"));
+
+        // expired delegations go back to the delegator
+        assert_eq!(
+            boost_of(&deps, "lucky", now + 1000),
+            Uint128::from(80000000u128)
+        );
+        assert_eq!(boost_of(&deps, "tom", now + 1000), Uint128::zero());
+        assert_eq!(
+            boost_of(&deps, "lucky", now + 2000),
+            Uint128::from(100000000u128)
+        );
+
+        // extending into a tier gov lowered below the active delegations is rejected
+        mock_ve_seilor_balance(&mut deps, Uint128::from(1000u128));
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3000);
+        let _res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("lucky", &[]),
+            delegate("tom", 60000000u128, now + 5000, false),
+        )
+        .unwrap();
+        let _msg = ExecuteMsg::UpdateLockSetting {
+            index: 3,
+            duration: Uint128::from(31536000u128),
+            mining_boost: Uint128::from(50000000u128),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("gov", &[]), _msg).unwrap();
+        let _msg = ExecuteMsg::ExtendLock { index: 3 };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(_res, "delegated boost exceeds the mining boost of the lock");
+        env.block.time = env.block.time.plus_seconds(2000);
+        let _msg = ExecuteMsg::ExtendLock { index: 3 };
+        let _res = execute(deps.as_mut(), env, mock_info("lucky", &[]), _msg).unwrap();
+        assert_eq!(
+            boost_of(&deps, "lucky", now + 5000),
+            Uint128::from(50000000u128)
+        );
+    }
+
+    #[test]
+    fn test_delegation_in_pool_and_cleanup() {
+        let mut deps = mock_dependencies();
+
+        let _msg = InstantiateMsg {
+            gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
+            ve_seilor_lock_settings: ve_seilor_lock_settings(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        mock_ve_seilor_balance(&mut deps, Uint128::from(1000u128));
+
+        // 365 days with a 100% boost by default, 20% in pool_a
+        let _msg = ExecuteMsg::Lock {
+            index: 3,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+        let _msg = ExecuteMsg::SetPoolLockSettings {
+            pool: Addr::unchecked("pool_a"),
+            lock_settings: vec![VeSeilorLockSetting {
+                duration: Uint128::from(31536000u128),
+                mining_boost: Uint128::from(20000000u128),
+            }],
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        let _msg = ExecuteMsg::SetDelegationNft {
+            nft_addr: Some(Addr::unchecked("boost_nft")),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+
+        let now = mock_env().block.time.seconds();
+        let boost_in = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                        user: &str,
+                        pool: Option<&str>| {
+            get_user_boost(
+                deps.as_ref(),
+                mock_env(),
+                Addr::unchecked(user),
+                Uint128::from(now),
+                Uint128::from(now + 100000),
+                pool.map(Addr::unchecked),
+            )
+            .unwrap()
+            .user_boost
+        };
+
+        // 40% of the default boost is 40% of the boost in pool_a
+        let _msg = ExecuteMsg::DelegateBoost {
+            receiver: Addr::unchecked("tom"),
+            boost: Uint128::from(40000000u128),
+            expire_time: Uint128::from(now + 1000),
+            mint_receipt: false,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+        let _msg = ExecuteMsg::DelegateBoost {
+            receiver: Addr::unchecked("jerry"),
+            boost: Uint128::from(10000000u128),
+            expire_time: Uint128::from(now + 2000),
+            mint_receipt: true,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+        assert_eq!(boost_in(&deps, "lucky", None), Uint128::from(50000000u128));
+        assert_eq!(boost_in(&deps, "tom", None), Uint128::from(40000000u128));
+        assert_eq!(
+            boost_in(&deps, "lucky", Some("pool_a")),
+            Uint128::from(10000000u128)
+        );
+        assert_eq!(
+            boost_in(&deps, "tom", Some("pool_a")),
+            Uint128::from(8000000u128)
+        );
+        assert_eq!(
+            boost_in(&deps, "jerry", Some("pool_a")),
+            Uint128::from(2000000u128)
+        );
+
+        // expired delegations are dropped by anyone
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(1000);
+        let _msg = ExecuteMsg::ClearExpiredDelegations {
+            user: Addr::unchecked("lucky"),
+        };
+        let _res = execute(deps.as_mut(), env, mock_info("anyone", &[]), _msg).unwrap();
+        assert_eq!(_res.attributes[2], ("cleared", "1"));
+        let delegations = get_user_delegations(deps.as_ref(), Addr::unchecked("lucky")).unwrap();
+        assert_eq!(delegations.delegated.len(), 1);
+        assert_eq!(
+            get_user_delegations(deps.as_ref(), Addr::unchecked("tom"))
+                .unwrap()
+                .received
+                .len(),
+            0
+        );
+
+        // the receipt of an expired delegation is sent back and burned
+        let receipt = |token_id: &str| {
+            ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: "jerry".to_string(),
+                token_id: token_id.to_string(),
+                msg: Binary::default(),
+            })
+        };
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("boost_nft", &[]),
+            receipt("2"),
+        );
+        assert_generic_err(_res, "delegation not expired");
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(2000);
+        let _res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("other_nft", &[]),
+            receipt("2"),
+        );
+        assert_generic_err(_res, "unauthorized");
+        let _res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("boost_nft", &[]),
+            receipt("2"),
+        )
+        .unwrap();
+        assert_eq!(
+            _res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "boost_nft".to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::Burn {
+                    token_id: "2".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        let delegations = get_user_delegations(deps.as_ref(), Addr::unchecked("lucky")).unwrap();
+        assert_eq!(delegations.delegated.len(), 0);
+    }
+
+    #[test]
+    fn test_pool_lock_settings_and_history() {
+        let mut deps = mock_dependencies();
+
+        let _msg = InstantiateMsg {
+            gov: Some(Addr::unchecked("gov")),
+            ve_seilor_addr: Addr::unchecked("ve_seilor"),
+            ve_seilor_lock_settings: ve_seilor_lock_settings(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        mock_ve_seilor_balance(&mut deps, Uint128::from(1000u128));
+
+        // index 1, 90 days with a 30% boost
+        let _msg = ExecuteMsg::Lock {
+            index: 1,
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+
+        let pool_settings = vec![
+            VeSeilorLockSetting {
+                duration: Uint128::from(2592000u128),
+                mining_boost: Uint128::from(50000000u128),
+            },
+            VeSeilorLockSetting {
+                duration: Uint128::from(15552000u128),
+                mining_boost: Uint128::from(200000000u128),
+            },
+        ];
+        let _msg = ExecuteMsg::SetPoolLockSettings {
+            pool: Addr::unchecked("pool_a"),
+            lock_settings: pool_settings.clone(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg);
+        assert_generic_err(_res, "unauthorized");
+        let _msg = ExecuteMsg::SetPoolLockSettings {
+            pool: Addr::unchecked("pool_a"),
+            lock_settings: vec![pool_settings[0].clone(), pool_settings[0].clone()],
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg);
+        assert_generic_err(_res, "duplicate lock duration");
+        let _msg = ExecuteMsg::SetPoolLockSettings {
+            pool: Addr::unchecked("pool_a"),
+            lock_settings: pool_settings.clone(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        assert_eq!(
+            get_pool_lock_settings(deps.as_ref(), Addr::unchecked("pool_a"))
+                .unwrap()
+                .lock_settings,
+            pool_settings
+        );
+
+        let now = mock_env().block.time.seconds();
+        let boost_in = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, pool: &str| {
+            get_user_boost(
+                deps.as_ref(),
+                mock_env(),
+                Addr::unchecked("lucky"),
+                Uint128::from(now),
+                Uint128::from(now + 100000),
+                Some(Addr::unchecked(pool)),
+            )
+            .unwrap()
+            .user_boost
+        };
+        // the 90 days lock is covered by the 30 days setting of pool_a only
+        assert_eq!(boost_in(&deps, "pool_a"), Uint128::from(50000000u128));
+        assert_eq!(boost_in(&deps, "pool_b"), Uint128::from(30000000u128));
+
+        let _msg = ExecuteMsg::ExtendLock { index: 2 };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+        assert_eq!(boost_in(&deps, "pool_a"), Uint128::from(200000000u128));
+
+        let _msg = ExecuteMsg::SetPoolLockSettings {
+            pool: Addr::unchecked("pool_a"),
+            lock_settings: vec![],
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), _msg).unwrap();
+        assert_eq!(boost_in(&deps, "pool_a"), Uint128::from(50000000u128));
+
+        // history
+        let _msg = ExecuteMsg::IncreaseLockAmount {
+            amount: Uint128::from(50u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("lucky", &[]), _msg).unwrap();
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(15552000);
+        let _res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("lucky", &[]),
+            ExecuteMsg::Unlock {},
+        )
+        .unwrap();
+
+        let history = get_user_lock_history(deps.as_ref(), Addr::unchecked("lucky"), None, None)
+            .unwrap()
+            .history;
+        let actions: Vec<&str> = history.iter().map(|item| item.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            vec!["lock", "extend_lock", "increase_lock_amount", "unlock"]
+        );
+        assert_eq!(history[0].mining_boost, Uint128::from(30000000u128));
+        assert_eq!(history[1].mining_boost, Uint128::from(50000000u128));
+        assert_eq!(history[2].locked_amount, Uint128::from(150u128));
+        assert_eq!(history[3].locked_amount, Uint128::zero());
+        assert_eq!(history[3].timestamp, Uint128::from(now + 15552000));
+
+        let page = get_user_lock_history(deps.as_ref(), Addr::unchecked("lucky"), Some(2), Some(1))
+            .unwrap()
+            .history;
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, 3);
     }
 }
@@ -0,0 +1,40 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Empty};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Subset of the cw721-base messages used to mint, burn and look up delegation receipts
+#[cw_serde]
+pub enum Cw721ExecuteMsg {
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: Option<Empty>,
+    },
+    Burn {
+        token_id: String,
+    },
+}
+
+// Hook called by a cw721 contract when a token is sent to boost with SendNft
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+#[cw_serde]
+pub enum Cw721QueryMsg {
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+}
+
+// Only the owner is read, the other fields of the cw721 response are ignored
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnerOfResponse {
+    pub owner: String,
+}
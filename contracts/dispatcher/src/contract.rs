@@ -1,5 +1,5 @@
 use crate::error::ContractError;
-use crate::handler::{accept_gov, add_users, set_gov, update_config, user_claim};
+use crate::handler::{accept_gov, add_users, rescue_tokens, set_gov, update_config, user_claim};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::querier::{query_global_infos, query_user_info, query_user_infos};
 use crate::state::{store_global_config, store_global_state, GlobalConfig, GlobalState};
@@ -74,6 +74,11 @@ pub fn execute(
         ExecuteMsg::UserClaim {} => user_claim(deps, env, info),
         ExecuteMsg::SetGov { gov } => set_gov(deps, info, gov),
         ExecuteMsg::AcceptGov {} => accept_gov(deps, info),
+        ExecuteMsg::RescueTokens {
+            asset,
+            amount,
+            recipient,
+        } => rescue_tokens(deps, env, info, asset, amount, recipient),
     }
 }
 
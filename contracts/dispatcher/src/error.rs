@@ -1,3 +1,4 @@
+use common::rescue::RescueError;
 use cosmwasm_std::{Addr, ConversionOverflowError, OverflowError, StdError};
 use thiserror::Error;
 
@@ -67,4 +68,6 @@ pub enum ContractError {
 
     #[error("Global Claim Lock Amount Too Large")]
     GlobalClaimLockAmountTooLarge {},
+    #[error("{0}")]
+    Rescue(#[from] RescueError),
 }
@@ -1,6 +1,6 @@
 use crate::error::ContractError;
 use crate::helper::is_empty_str;
-use crate::msg::{AddUserMsg, UpdateGlobalConfigMsg};
+use crate::msg::{AddUserMsg, AssetInfo, UpdateGlobalConfigMsg};
 use crate::state::{
     read_global_config, read_global_state, read_user_state, store_global_config,
     store_global_state, store_user_by_page, store_user_state, UserState,
@@ -251,3 +251,36 @@ fn _transfer_token(
     });
     Ok(cosmos_msg)
 }
+
+// Rescues tokens sent to the contract by mistake. The claim tokens locked for users and not
+// claimed yet are kept back.
+pub fn rescue_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    let config = read_global_config(deps.storage)?;
+    if config.gov != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut reserved = Uint128::zero();
+    if asset.is_token(&config.claim_token) {
+        let global_state = read_global_state(deps.storage)?;
+        reserved = Uint128::try_from(
+            global_state.total_user_lock_amount - global_state.total_user_claimed_lock_amount,
+        )?;
+    }
+    Ok(common::rescue::rescue_tokens(
+        deps.as_ref(),
+        &env,
+        info.sender,
+        asset,
+        amount,
+        recipient,
+        reserved,
+    )?)
+}
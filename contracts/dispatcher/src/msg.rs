@@ -1,6 +1,7 @@
 use crate::state::{GlobalConfig, GlobalState, UserState};
+pub use common::asset::AssetInfo;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint256};
+use cosmwasm_std::{Addr, Uint128, Uint256};
 
 #[cw_serde]
 pub struct UpdateGlobalConfigMsg {
@@ -31,8 +32,17 @@ pub enum ExecuteMsg {
     UpdateConfig(UpdateGlobalConfigMsg),
     AddUser(Vec<AddUserMsg>),
     UserClaim {},
-    SetGov { gov: Addr },
+    SetGov {
+        gov: Addr,
+    },
     AcceptGov {},
+    /// Sends tokens that ended up in the contract by mistake to `recipient`,
+    /// the claim tokens users can still claim can't be rescued.
+    RescueTokens {
+        asset: AssetInfo,
+        amount: Uint128,
+        recipient: Addr,
+    },
 }
 
 #[cw_serde]
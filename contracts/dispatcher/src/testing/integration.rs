@@ -166,6 +166,89 @@ fn test_integration() {
     assert_eq!(users.len(), 5);
 }
 
+#[test]
+fn test_rescue_tokens() {
+    let block_time = 1688128676u64;
+    let creator = Addr::unchecked(CREATOR);
+    let recipient = Addr::unchecked("recipient");
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time));
+
+    let cw20_contract_id = store_cw20_contract(&mut app);
+    let cw20_token = app
+        .instantiate_contract(
+            cw20_contract_id,
+            creator.clone(),
+            &mock_cw20_instantiate_msg(),
+            &[], // no funds
+            String::from("cw20_token"),
+            None,
+        )
+        .unwrap();
+    let dispatcher_contract_id = store_dispatcher_contract(&mut app);
+    let dispatcher_contact = app
+        .instantiate_contract(
+            dispatcher_contract_id,
+            creator.clone(),
+            &crate::testing::mock_fn::mock_instantiate_msg(cw20_token.clone()),
+            &[], // no funds
+            String::from("dispatcher_contact"),
+            None,
+        )
+        .unwrap();
+
+    let transfer_amount = Uint128::from(10_000_000_000_000u128);
+    let res = transfer_token(
+        &creator,
+        &dispatcher_contact,
+        &mut app,
+        &cw20_token,
+        transfer_amount,
+    );
+    assert!(res.is_ok());
+    let res = add_users(
+        &creator,
+        &mut app,
+        &mock_add_users_msg(),
+        &dispatcher_contact,
+    );
+    assert!(res.is_ok());
+
+    // only what is not assigned to users can be rescued
+    let state = query_global_infos(&mut app, &dispatcher_contact).state;
+    let surplus = transfer_amount
+        - Uint128::try_from(state.total_user_lock_amount - state.total_user_claimed_lock_amount)
+            .unwrap();
+    let res = rescue_tokens(
+        &Addr::unchecked("tom"),
+        &mut app,
+        &dispatcher_contact,
+        &cw20_token,
+        surplus,
+        &recipient,
+    );
+    assert!(res.is_err());
+    let res = rescue_tokens(
+        &creator,
+        &mut app,
+        &dispatcher_contact,
+        &cw20_token,
+        surplus + Uint128::one(),
+        &recipient,
+    );
+    assert!(res.is_err());
+    let res = rescue_tokens(
+        &creator,
+        &mut app,
+        &dispatcher_contact,
+        &cw20_token,
+        surplus,
+        &recipient,
+    );
+    assert!(res.is_ok());
+    let res = get_token_balance(&mut app, &cw20_token, &recipient);
+    assert_eq!(res.balance, surplus);
+}
+
 fn add_users(
     user: &Addr,
     app: &mut App,
@@ -201,6 +284,34 @@ fn user_claim(user: &Addr, app: &mut App, dispatcher_contract: &Addr) -> StdResu
         Ok(Response::default())
     }
 }
+fn rescue_tokens(
+    gov: &Addr,
+    app: &mut App,
+    dispatcher_contract: &Addr,
+    token: &Addr,
+    amount: Uint128,
+    recipient: &Addr,
+) -> StdResult<Response> {
+    let send_msg = crate::msg::ExecuteMsg::RescueTokens {
+        asset: crate::msg::AssetInfo::Token {
+            contract_addr: token.clone(),
+        },
+        amount,
+        recipient: recipient.clone(),
+    };
+    let res = app.execute_contract(
+        gov.clone(),
+        dispatcher_contract.clone(),
+        &send_msg,
+        &[], // no funds
+    );
+    if res.is_err() {
+        println!("rescue_tokens error: {:?}", res);
+        Err(StdError::generic_err("rescue_tokens error"))
+    } else {
+        Ok(Response::default())
+    }
+}
 fn query_global_infos(app: &mut App, dispatcher_contract: &Addr) -> GlobalInfosResponse {
     let query_msg = crate::msg::QueryMsg::QueryGlobalConfig {};
     let res: GlobalInfosResponse = app
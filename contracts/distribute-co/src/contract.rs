@@ -1,10 +1,11 @@
 use crate::handler::{
-    add_period_configs, add_user_period_configs, update_user_status, user_claim_periods,
+    add_period_configs, add_user_period_configs, merkle_claim_periods, register_merkle_root,
+    update_user_status, user_claim_periods,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::querier::{
-    query_all_period_configs, query_config, query_period_config, query_user_period_config,
-    query_user_status,
+    query_all_period_configs, query_config, query_merkle_cohort, query_period_config,
+    query_user_period_config, query_user_status,
 };
 use crate::state::{read_config, store_config, Config};
 #[cfg(not(feature = "library"))]
@@ -64,6 +65,27 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             user_address,
             status,
         } => update_user_status(deps, env, info, user_address, status),
+        ExecuteMsg::RegisterMerkleRoot {
+            cohort_id,
+            merkle_root,
+            cohort_total_amount,
+        } => register_merkle_root(deps, env, info, cohort_id, merkle_root, cohort_total_amount),
+        ExecuteMsg::MerkleClaimPeriods {
+            cohort_id,
+            user_per_period_amount,
+            user_total_amount,
+            proof,
+            period_ids,
+        } => merkle_claim_periods(
+            deps,
+            env,
+            info,
+            cohort_id,
+            user_per_period_amount,
+            user_total_amount,
+            proof,
+            period_ids,
+        ),
     }
 }
 
@@ -82,6 +104,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::QueryUserStatus { user_address } => {
             to_binary(&query_user_status(deps, user_address)?)
         }
+        QueryMsg::QueryMerkleCohort { cohort_id } => {
+            to_binary(&query_merkle_cohort(deps, cohort_id)?)
+        }
     }
 }
 
@@ -1,14 +1,17 @@
 use crate::msg::UserPeriodConfigMsg;
 use crate::state::{
-    has_period_config, has_user_period_config, read_config, read_period_config,
-    read_user_period_config, read_user_status, store_config, store_period_config,
-    store_user_period_config, store_user_status, PeriodConfig, UserPeriodClaimedDetails,
-    UserPeriodConfig,
+    has_merkle_cohort, has_merkle_user, has_period_config, has_user_period_config, has_user_status,
+    read_config, read_merkle_cohort, read_period_config, read_user_period_config, read_user_status,
+    store_config, store_merkle_cohort, store_merkle_user, store_period_config,
+    store_user_period_config, store_user_status, MerkleCohort, PeriodConfig,
+    UserPeriodClaimedDetails, UserPeriodConfig,
 };
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
-    WasmMsg,
+    attr, to_binary, Addr, Attribute, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
 };
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 
 pub fn add_period_configs(
     deps: DepsMut,
@@ -155,6 +158,7 @@ pub fn user_claim_periods(
             let claim_from_distribute_msg = distribute::msg::ExecuteMsg::Claim {
                 rule_type: "co".to_string(),
                 msg: None,
+                mode: None,
             };
             let claim_distribute_msg = CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: config.token_distribute_address.clone().to_string(),
@@ -206,3 +210,158 @@ pub fn user_claim_periods(
         ("user", user_address.as_str()),
     ]))
 }
+
+pub fn register_merkle_root(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    cohort_id: u64,
+    merkle_root: String,
+    cohort_total_amount: Uint128,
+) -> StdResult<Response> {
+    let own_res = cw_ownable::assert_owner(deps.storage, &info.sender);
+    if own_res.is_err() {
+        return Err(StdError::generic_err(own_res.err().unwrap().to_string()));
+    }
+    if has_merkle_cohort(deps.storage, &cohort_id) {
+        return Err(StdError::generic_err(format!(
+            "Cohort {} already exists",
+            cohort_id
+        )));
+    }
+    let mut root_buf: [u8; 32] = [0; 32];
+    if hex::decode_to_slice(&merkle_root, &mut root_buf).is_err() {
+        return Err(StdError::generic_err("Invalid merkle root"));
+    }
+
+    let mut config = read_config(deps.storage)?;
+    config.user_register_amount += cohort_total_amount;
+    if config.user_register_amount > config.total_distribute_amount {
+        return Err(StdError::generic_err(format!(
+            "Cohort {} total amount exceeds total distribute amount",
+            cohort_id
+        )));
+    }
+    store_config(deps.storage, &config)?;
+
+    let merkle_cohort = MerkleCohort {
+        merkle_root: merkle_root.clone(),
+        cohort_total_amount,
+        cohort_register_amount: Uint128::zero(),
+    };
+    store_merkle_cohort(deps.storage, &cohort_id, &merkle_cohort)?;
+    Ok(Response::default().add_attributes(vec![
+        ("action", "register_merkle_root"),
+        ("owner", info.sender.as_str()),
+        ("cohort_id", cohort_id.to_string().as_str()),
+        ("merkle_root", merkle_root.as_str()),
+        (
+            "cohort_total_amount",
+            cohort_total_amount.to_string().as_str(),
+        ),
+    ]))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn merkle_claim_periods(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cohort_id: u64,
+    user_per_period_amount: Uint128,
+    user_total_amount: Uint128,
+    proof: Vec<String>,
+    period_ids: Vec<u64>,
+) -> StdResult<Response> {
+    let attrs = _register_merkle_user(
+        deps.branch(),
+        &info,
+        cohort_id,
+        user_per_period_amount,
+        user_total_amount,
+        proof,
+    )?;
+    Ok(user_claim_periods(deps, env, info, period_ids)?.add_attributes(attrs))
+}
+
+/// Creates the user period config of a cohort member from the proven leaf, does nothing once the
+/// user registered from this cohort. The leaf amount is taken from what the cohort reserved.
+fn _register_merkle_user(
+    deps: DepsMut,
+    info: &MessageInfo,
+    cohort_id: u64,
+    user_per_period_amount: Uint128,
+    user_total_amount: Uint128,
+    proof: Vec<String>,
+) -> StdResult<Vec<Attribute>> {
+    let user_address = info.sender.clone();
+    if !has_merkle_cohort(deps.storage, &cohort_id) {
+        return Err(StdError::generic_err(format!(
+            "Cohort {} does not exist",
+            cohort_id
+        )));
+    }
+    if has_merkle_user(deps.storage, cohort_id, &user_address) {
+        return Ok(vec![]);
+    }
+    if has_user_period_config(deps.storage, &user_address) {
+        return Err(StdError::generic_err(format!(
+            "User {} already exists",
+            user_address
+        )));
+    }
+
+    let mut merkle_cohort = read_merkle_cohort(deps.storage, &cohort_id)?;
+    let leaf = format!(
+        "{},{},{}",
+        user_address, user_per_period_amount, user_total_amount
+    );
+    _verify_merkle_proof(&merkle_cohort.merkle_root, &leaf, &proof)?;
+
+    merkle_cohort.cohort_register_amount += user_total_amount;
+    if merkle_cohort.cohort_register_amount > merkle_cohort.cohort_total_amount {
+        return Err(StdError::generic_err(format!(
+            "User {} total amount exceeds cohort {} total amount",
+            user_address, cohort_id
+        )));
+    }
+    store_merkle_cohort(deps.storage, &cohort_id, &merkle_cohort)?;
+    store_merkle_user(deps.storage, cohort_id, &user_address)?;
+
+    let user_period_config = UserPeriodConfig {
+        user_per_period_amount,
+        user_total_claimed_amount: Uint128::zero(),
+        user_total_amount,
+        user_claimed_periods: HashMap::new(),
+    };
+    store_user_period_config(deps.storage, &user_address, &user_period_config)?;
+    // a user the owner already disabled stays disabled
+    if !has_user_status(deps.storage, &user_address) {
+        store_user_status(deps.storage, &user_address, true)?;
+    }
+
+    Ok(vec![
+        attr("cohort_id", cohort_id.to_string()),
+        attr("user_total_amount", user_total_amount.to_string()),
+    ])
+}
+
+// sorted pair hashing, so proofs don't need the position of each sibling
+fn _verify_merkle_proof(merkle_root: &str, leaf: &str, proof: &[String]) -> StdResult<()> {
+    let mut hash: [u8; 32] = Sha256::digest(leaf.as_bytes()).into();
+    for p in proof {
+        let mut proof_buf: [u8; 32] = [0; 32];
+        if hex::decode_to_slice(p, &mut proof_buf).is_err() {
+            return Err(StdError::generic_err("Invalid merkle proof"));
+        }
+        let mut hashes = [hash, proof_buf];
+        hashes.sort_unstable();
+        hash = Sha256::digest(hashes.concat()).into();
+    }
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    if hex::decode_to_slice(merkle_root, &mut root_buf).is_err() || root_buf != hash {
+        return Err(StdError::generic_err("Merkle verification failed"));
+    }
+    Ok(())
+}
@@ -34,6 +34,24 @@ pub enum ExecuteMsg {
         user_address: Addr,
         status: bool,
     },
+    /// Owner posts the hex encoded sha256 merkle root of a cohort, each leaf is
+    /// `sha256("{user_address},{user_per_period_amount},{user_total_amount}")`.
+    /// `cohort_total_amount` is reserved against the total distribute amount.
+    RegisterMerkleRoot {
+        cohort_id: u64,
+        merkle_root: String,
+        cohort_total_amount: Uint128,
+    },
+    /// Same as `UserClaimPeriods`, the user period config of a cohort member is created from the
+    /// proven leaf on the first claim. A user that already has a period config can't register a
+    /// second leaf.
+    MerkleClaimPeriods {
+        cohort_id: u64,
+        user_per_period_amount: Uint128,
+        user_total_amount: Uint128,
+        proof: Vec<String>,
+        period_ids: Vec<u64>,
+    },
 }
 
 #[cw_serde]
@@ -51,6 +69,8 @@ pub enum QueryMsg {
     QueryAllPeriodConfigs {},
     #[returns(bool)]
     QueryUserStatus { user_address: Addr },
+    #[returns(crate::state::MerkleCohort)]
+    QueryMerkleCohort { cohort_id: u64 },
 }
 
 #[cw_serde]
@@ -1,6 +1,7 @@
 use crate::state::{
-    read_all_period_config, read_config, read_period_config, read_user_period_config,
-    read_user_status, Config, PeriodConfig, UserPeriodConfig,
+    read_all_period_config, read_config, read_merkle_cohort, read_period_config,
+    read_user_period_config, read_user_status, Config, MerkleCohort, PeriodConfig,
+    UserPeriodConfig,
 };
 use cosmwasm_std::{Addr, Deps, StdResult};
 
@@ -28,3 +29,8 @@ pub fn query_user_status(deps: Deps, user_address: Addr) -> StdResult<bool> {
     let user_status = read_user_status(deps.storage, &user_address)?;
     Ok(user_status)
 }
+
+pub fn query_merkle_cohort(deps: Deps, cohort_id: u64) -> StdResult<MerkleCohort> {
+    let merkle_cohort = read_merkle_cohort(deps.storage, &cohort_id)?;
+    Ok(merkle_cohort)
+}
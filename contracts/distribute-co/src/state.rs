@@ -27,6 +27,14 @@ pub struct UserPeriodConfig {
     pub user_claimed_periods: HashMap<u64, UserPeriodClaimedDetails>,
 }
 
+#[cw_serde]
+pub struct MerkleCohort {
+    pub merkle_root: String,
+    // reserved against the total distribute amount when the root is registered
+    pub cohort_total_amount: Uint128,
+    pub cohort_register_amount: Uint128,
+}
+
 #[cw_serde]
 pub struct UserPeriodClaimedDetails {
     pub claimed_amount: Uint128,
@@ -40,6 +48,11 @@ const USER_PERIOD_CONFIGS: Map<&Addr, UserPeriodConfig> = Map::new("user_period_
 
 const USER_STATUS: Map<&Addr, bool> = Map::new("user_status");
 
+const MERKLE_COHORTS: Map<&u64, MerkleCohort> = Map::new("merkle_cohorts");
+
+// (cohort id, user address) => registered from the cohort leaf
+const MERKLE_USERS: Map<(u64, &Addr), bool> = Map::new("merkle_users");
+
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
     CONFIG.save(storage, config)
 }
@@ -109,3 +122,31 @@ pub fn read_user_status(storage: &dyn Storage, user_address: &Addr) -> StdResult
 pub fn has_user_status(storage: &dyn Storage, user_address: &Addr) -> bool {
     USER_STATUS.has(storage, user_address)
 }
+
+pub fn store_merkle_cohort(
+    storage: &mut dyn Storage,
+    cohort_id: &u64,
+    merkle_cohort: &MerkleCohort,
+) -> StdResult<()> {
+    MERKLE_COHORTS.save(storage, cohort_id, merkle_cohort)
+}
+
+pub fn read_merkle_cohort(storage: &dyn Storage, cohort_id: &u64) -> StdResult<MerkleCohort> {
+    MERKLE_COHORTS.load(storage, cohort_id)
+}
+
+pub fn has_merkle_cohort(storage: &dyn Storage, cohort_id: &u64) -> bool {
+    MERKLE_COHORTS.has(storage, cohort_id)
+}
+
+pub fn store_merkle_user(
+    storage: &mut dyn Storage,
+    cohort_id: u64,
+    user_address: &Addr,
+) -> StdResult<()> {
+    MERKLE_USERS.save(storage, (cohort_id, user_address), &true)
+}
+
+pub fn has_merkle_user(storage: &dyn Storage, cohort_id: u64, user_address: &Addr) -> bool {
+    MERKLE_USERS.has(storage, (cohort_id, user_address))
+}
@@ -1,7 +1,264 @@
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, UserPeriodConfigMsg};
+use crate::state::{Config, MerkleCohort, PeriodConfig, UserPeriodConfig};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_binary, Addr, StdError, Uint128};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn init() {
         println!("Hello, world!")
     }
-}
\ No newline at end of file
+}
+fn leaf_hash(user: &str, per_period_amount: u128, total_amount: u128) -> [u8; 32] {
+    let leaf = format!("{},{},{}", user, per_period_amount, total_amount);
+    Sha256::digest(leaf.as_bytes()).into()
+}
+
+fn merkle_claim_msg(per_period_amount: u128, total_amount: u128, proof: [u8; 32]) -> ExecuteMsg {
+    ExecuteMsg::MerkleClaimPeriods {
+        cohort_id: 1,
+        user_per_period_amount: Uint128::from(per_period_amount),
+        user_total_amount: Uint128::from(total_amount),
+        proof: vec![hex::encode(proof)],
+        period_ids: vec![1],
+    }
+}
+
+#[test]
+fn test_merkle_claim_periods() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = mock_info("owner", &[]);
+    let instantiate_msg = InstantiateMsg {
+        gov: None,
+        token_address: Addr::unchecked("seilor"),
+        token_distribute_address: Addr::unchecked("distribute"),
+        total_distribute_amount: Uint128::zero(),
+    };
+    instantiate(deps.as_mut(), env.clone(), owner.clone(), instantiate_msg).unwrap();
+    let add_period_msg = ExecuteMsg::AddPeriodConfigs {
+        period_configs: vec![PeriodConfig {
+            period_id: 1,
+            period_claimed_time: 0,
+            period_claimed_amount: Uint128::zero(),
+            period_total_amount: Uint128::from(1000u128),
+            claimed_from_distribute: false,
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), owner.clone(), add_period_msg).unwrap();
+
+    // two leaf tree, each leaf is the proof of the other
+    let alice_leaf = leaf_hash("alice", 100, 300);
+    let bob_leaf = leaf_hash("bob", 200, 600);
+    let mut leaves = [alice_leaf, bob_leaf];
+    leaves.sort_unstable();
+    let merkle_root = hex::encode(Sha256::digest(leaves.concat()));
+
+    let register_msg = ExecuteMsg::RegisterMerkleRoot {
+        cohort_id: 1,
+        merkle_root: merkle_root.clone(),
+        cohort_total_amount: Uint128::from(900u128),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        register_msg.clone(),
+    );
+    assert!(res.is_err());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::RegisterMerkleRoot {
+            cohort_id: 2,
+            merkle_root: "root".to_string(),
+            cohort_total_amount: Uint128::from(900u128),
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("Invalid merkle root")
+    );
+    // the cohort total is reserved against the total distribute amount
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::RegisterMerkleRoot {
+            cohort_id: 2,
+            merkle_root: merkle_root.clone(),
+            cohort_total_amount: Uint128::from(1001u128),
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("Cohort 2 total amount exceeds total distribute amount")
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        register_msg.clone(),
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), env.clone(), owner.clone(), register_msg);
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("Cohort 1 already exists")
+    );
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::QueryMerkleCohort { cohort_id: 1 },
+    )
+    .unwrap();
+    let merkle_cohort: MerkleCohort = from_binary(&res).unwrap();
+    assert_eq!(merkle_cohort.merkle_root, merkle_root);
+    assert_eq!(merkle_cohort.cohort_total_amount, Uint128::from(900u128));
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfig {}).unwrap();
+    let config: Config = from_binary(&res).unwrap();
+    assert_eq!(config.user_register_amount, Uint128::from(900u128));
+
+    // the owner can't register users past what the cohort reserved
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::AddUserPeriodConfigs {
+            user_period_configs: vec![UserPeriodConfigMsg {
+                user_address: Addr::unchecked("carol"),
+                user_per_period_amount: Uint128::from(100u128),
+                user_total_amount: Uint128::from(200u128),
+                user_claimed_periods: HashMap::new(),
+            }],
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("User carol total amount exceeds total distribute amount")
+    );
+
+    // only registered cohorts can be claimed from
+    let mut unknown_cohort_msg = merkle_claim_msg(100, 300, bob_leaf);
+    if let ExecuteMsg::MerkleClaimPeriods { cohort_id, .. } = &mut unknown_cohort_msg {
+        *cohort_id = 2;
+    }
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        unknown_cohort_msg,
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("Cohort 2 does not exist")
+    );
+
+    // wrong amounts don't prove
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        merkle_claim_msg(100, 400, bob_leaf),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("Merkle verification failed")
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        merkle_claim_msg(100, 300, bob_leaf),
+    )
+    .unwrap();
+    // claim from distribute and transfer to alice
+    assert_eq!(res.messages.len(), 2);
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::QueryUserPeriodConfig {
+            user_address: Addr::unchecked("alice"),
+        },
+    )
+    .unwrap();
+    let user_period_config: UserPeriodConfig = from_binary(&res).unwrap();
+    assert_eq!(user_period_config.user_total_amount, Uint128::from(300u128));
+    assert_eq!(
+        user_period_config.user_total_claimed_amount,
+        Uint128::from(100u128)
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        merkle_claim_msg(100, 300, bob_leaf),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("User alice already claimed period 1")
+    );
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::QueryMerkleCohort { cohort_id: 1 },
+    )
+    .unwrap();
+    let merkle_cohort: MerkleCohort = from_binary(&res).unwrap();
+    assert_eq!(merkle_cohort.cohort_register_amount, Uint128::from(300u128));
+
+    // a leaf of a second cohort is rejected for a user that already has a period config
+    let mut alice_leaves = [leaf_hash("alice", 50, 50), alice_leaf];
+    alice_leaves.sort_unstable();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        owner.clone(),
+        ExecuteMsg::RegisterMerkleRoot {
+            cohort_id: 3,
+            merkle_root: hex::encode(Sha256::digest(alice_leaves.concat())),
+            cohort_total_amount: Uint128::from(50u128),
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        ExecuteMsg::MerkleClaimPeriods {
+            cohort_id: 3,
+            user_per_period_amount: Uint128::from(50u128),
+            user_total_amount: Uint128::from(50u128),
+            proof: vec![hex::encode(alice_leaf)],
+            period_ids: vec![2],
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("User alice already exists")
+    );
+
+    // a user disabled by the owner can't claim through the cohort
+    let disable_msg = ExecuteMsg::UpdateUserStatus {
+        user_address: Addr::unchecked("bob"),
+        status: false,
+    };
+    execute(deps.as_mut(), env.clone(), owner, disable_msg).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("bob", &[]),
+        merkle_claim_msg(200, 600, alice_leaf),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("User bob is not allowed to claim")
+    );
+}
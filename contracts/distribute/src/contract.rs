@@ -1,20 +1,25 @@
 use crate::error::ContractError;
 use crate::handler::{
-    accept_gov, add_rule_config, claim, set_gov, update_config, update_rule_config,
+    accept_gov, add_rule_config, claim, patch_rule, revoke_rule, set_gov, set_rule_claim_config,
+    update_config, update_rule_config,
 };
-use crate::helper::BASE_RATE_12;
+use crate::helper::{linear_release_schedule, validate_release_curve};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::querier::{query_claimable_info, query_config, query_rule_info, query_token_minter_cap};
+use crate::querier::{
+    query_all_rules, query_claimable_info, query_config, query_rule_info, query_rules_by_owner,
+    query_token_minter_cap, query_vesting_summary,
+};
 use crate::state::{
-    read_rule_config, store_distribute_config, store_rule_config, store_rule_config_state,
-    DistributeConfig, RuleConfig, RuleConfigState,
+    store_distribute_config, store_rule_config, store_rule_config_state, DistributeConfig,
+    RuleConfig, RuleConfigState,
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    attr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "kryptonite.finance:seilor-distribute";
@@ -33,10 +38,15 @@ pub fn instantiate(
     let mut rule_total_amount = 0u128;
     for (rule_type, rule_msg) in msg.rule_configs_map {
         rule_total_amount += rule_msg.rule_total_amount.clone();
-        let end_linear_release_time =
-            rule_msg.start_linear_release_time + rule_msg.unlock_linear_release_time;
-        let linear_release_per_second = rule_msg.unlock_linear_release_amount * BASE_RATE_12
-            / u128::from(rule_msg.unlock_linear_release_time);
+        if let Some(release_curve) = &rule_msg.release_curve {
+            validate_release_curve(release_curve, rule_msg.rule_total_amount)?;
+        }
+        let (end_linear_release_time, linear_release_per_second) = linear_release_schedule(
+            rule_msg.start_linear_release_time,
+            rule_msg.unlock_linear_release_amount,
+            rule_msg.unlock_linear_release_time,
+            &rule_msg.release_curve,
+        )?;
         let rule_config = RuleConfig {
             rule_name: rule_msg.rule_name,
             rule_owner: rule_msg.rule_owner,
@@ -49,6 +59,11 @@ pub fn instantiate(
             unlock_linear_release_amount: rule_msg.unlock_linear_release_amount,
             unlock_linear_release_time: rule_msg.unlock_linear_release_time,
             linear_release_per_second,
+            release_curve: rule_msg.release_curve,
+            revoke_time: None,
+            revoked_amount: 0u128,
+            operator: None,
+            beneficiaries: vec![],
         };
         store_rule_config(deps.storage, &rule_type, &rule_config)?;
 
@@ -70,6 +85,7 @@ pub fn instantiate(
         new_gov: None,
         distribute_ve_token: msg.distribute_ve_token,
         token_cap,
+        fund: msg.fund,
     };
 
     if distribute_config.total_amount < rule_total_amount {
@@ -105,10 +121,15 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Claim { rule_type, msg } => claim(deps, env, info, rule_type, msg),
-        ExecuteMsg::UpdateConfig { distribute_token } => {
-            update_config(deps, info, distribute_token)
-        }
+        ExecuteMsg::Claim {
+            rule_type,
+            msg,
+            mode,
+        } => claim(deps, env, info, rule_type, msg, mode),
+        ExecuteMsg::UpdateConfig {
+            distribute_token,
+            fund,
+        } => update_config(deps, info, distribute_token, fund),
         ExecuteMsg::UpdateRuleConfig { update_rule_msg } => {
             update_rule_config(deps, info, update_rule_msg)
         }
@@ -116,6 +137,15 @@ pub fn execute(
             rule_type,
             rule_msg,
         } => add_rule_config(deps, info, rule_type, rule_msg),
+        ExecuteMsg::RevokeRule {
+            rule_type,
+            effective_time,
+        } => revoke_rule(deps, env, info, rule_type, effective_time),
+        ExecuteMsg::SetRuleClaimConfig {
+            rule_type,
+            operator,
+            beneficiaries,
+        } => set_rule_claim_config(deps, info, rule_type, operator, beneficiaries),
         ExecuteMsg::SetGov { gov } => set_gov(deps, info, gov),
         ExecuteMsg::AcceptGov {} => accept_gov(deps, info),
     }
@@ -129,51 +159,48 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::QueryRuleInfo { rule_type } => to_binary(&query_rule_info(deps, rule_type)?),
         QueryMsg::QueryConfig {} => to_binary(&query_config(deps)?),
+        QueryMsg::AllRules { start_after, limit } => {
+            to_binary(&query_all_rules(deps, start_after, limit)?)
+        }
+        QueryMsg::RulesByOwner {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_rules_by_owner(deps, owner, start_after, limit)?),
+        QueryMsg::VestingSummary {} => to_binary(&query_vesting_summary(deps, env)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    // update co rule config
-    let mut co_config = read_rule_config(_deps.storage, "co".as_ref())?;
-    if co_config.linear_release_per_second < 2661192214111922141u128 {
-        co_config.linear_release_per_second = 2661192214111922141u128;
-        co_config.unlock_linear_release_time = 47347200;
-        co_config.end_linear_release_time =
-            co_config.start_linear_release_time + co_config.unlock_linear_release_time;
-        store_rule_config(_deps.storage, "co".as_ref(), &co_config)?;
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = get_contract_version(deps.storage)?;
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from contract {}",
+            contract_version.contract
+        )));
     }
-
-    // update mm rule config
-    let mut mm_config = read_rule_config(_deps.storage, "mm".as_ref())?;
-    if mm_config.linear_release_per_second < 4357501102292768959u128 {
-        mm_config.linear_release_per_second = 4357501102292768959u128;
-        mm_config.unlock_linear_release_time = 29030400;
-        mm_config.end_linear_release_time =
-            mm_config.start_linear_release_time + mm_config.unlock_linear_release_time;
-        store_rule_config(_deps.storage, "mm".as_ref(), &mm_config)?;
+    let stored_version = parse_version(&contract_version.version)?;
+    if stored_version > parse_version(CONTRACT_VERSION)? {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from version {} to older version {}",
+            contract_version.version, CONTRACT_VERSION
+        )));
     }
 
-    // update team rule config
-    let mut team_config = read_rule_config(_deps.storage, "team".as_ref())?;
-    if team_config.linear_release_per_second < 7614522417153996101u128 {
-        team_config.linear_release_per_second = 7614522417153996101u128;
-        team_config.unlock_linear_release_time = 26265600;
-        team_config.end_linear_release_time =
-            team_config.start_linear_release_time + team_config.unlock_linear_release_time;
-        store_rule_config(_deps.storage, "team".as_ref(), &team_config)?;
+    let mut attrs = vec![
+        attr("action", "migrate"),
+        attr("from_version", contract_version.version),
+        attr("to_version", CONTRACT_VERSION),
+    ];
+    for patch in msg.rule_patches {
+        attrs.extend(patch_rule(deps.branch(), &env, patch)?);
     }
 
-    // update pa rule config
-
-    let mut pa_config = read_rule_config(_deps.storage, "pa".as_ref())?;
-    if pa_config.linear_release_per_second < 1713267543859649122u128 {
-        pa_config.linear_release_per_second = 1713267543859649122u128;
-        pa_config.unlock_linear_release_time = 26265600;
-        pa_config.end_linear_release_time =
-            pa_config.start_linear_release_time + pa_config.unlock_linear_release_time;
-        store_rule_config(_deps.storage, "pa".as_ref(), &pa_config)?;
-    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attributes(attrs))
+}
 
-    Ok(Response::default())
+fn parse_version(version: &str) -> StdResult<Version> {
+    Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()))
 }
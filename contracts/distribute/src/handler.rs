@@ -1,15 +1,17 @@
 use crate::error::ContractError;
-use crate::helper::BASE_RATE_12;
-use crate::msg::{RuleConfigMsg, UpdateRuleConfigMsg};
-use crate::querier::{check_total_supply, query_claimable_info};
+use crate::helper::{
+    linear_release_schedule, validate_beneficiaries, validate_release_curve, BPS_DENOMINATOR,
+};
+use crate::msg::{ClaimMode, RuleConfigMsg, RulePatch, UpdateRuleConfigMsg};
+use crate::querier::{calc_released_amount, check_total_supply, query_claimable_info};
 use crate::state::{
     check_rule_config_exist, read_distribute_config, read_rule_config, read_rule_config_state,
-    store_distribute_config, store_rule_config, store_rule_config_state, RuleConfig,
-    RuleConfigState,
+    store_distribute_config, store_rule_config, store_rule_config_state, Beneficiary,
+    DistributeConfig, RuleConfig, RuleConfigState,
 };
 use cosmwasm_std::{
-    attr, to_binary, Addr, Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError,
-    Uint128, WasmMsg,
+    attr, to_binary, Addr, Attribute, Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
 };
 
 pub fn claim(
@@ -18,18 +20,39 @@ pub fn claim(
     info: MessageInfo,
     rule_type: String,
     msg: Option<Binary>,
+    mode: Option<ClaimMode>,
 ) -> Result<Response, ContractError> {
     let claim_user = info.sender;
-    // check rule type owner
+    let mode = mode.unwrap_or(ClaimMode::Liquid {});
+    // check rule type owner or operator
     let rule_config = read_rule_config(deps.storage, &rule_type)?;
-    if rule_config.rule_owner.ne(&claim_user) {
+    let is_owner = rule_config.rule_owner.eq(&claim_user);
+    if !is_owner && rule_config.operator.as_ref() != Some(&claim_user) {
         return Err(ContractError::Unauthorized {});
     }
+    if !is_owner && msg.is_some() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "only the rule owner can attach a msg",
+        )));
+    }
+    // locking the owner's vesting as veSEILOR is the owner's decision
+    if !is_owner && mode != (ClaimMode::Liquid {}) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "only the rule owner can choose the claim mode",
+        )));
+    }
+    if msg.is_some() && !rule_config.beneficiaries.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "a msg can't be attached to a claim split between beneficiaries",
+        )));
+    }
     let block_time = env.block.time.seconds();
     let distribute_config = read_distribute_config(deps.storage)?;
     let mut rule_config_state = read_rule_config_state(deps.storage, &rule_type)?;
 
-    let total_can_claimed_amount = rule_config.rule_total_amount - rule_config_state.claimed_amount;
+    let total_can_claimed_amount = rule_config.rule_total_amount
+        - rule_config.revoked_amount
+        - rule_config_state.claimed_amount;
     // check if can claim
     if total_can_claimed_amount == 0u128 {
         return Err(ContractError::NoMoreAmountClaim {});
@@ -60,36 +83,135 @@ pub fn claim(
     rule_config_state.last_claim_linear_release_time = block_time;
     store_rule_config_state(deps.storage, &rule_type, &rule_config_state)?;
 
+    //check token total supply, ve mints count against the same cap
+    check_total_supply(
+        deps.as_ref(),
+        distribute_config.distribute_token.clone(),
+        distribute_config.distribute_ve_token.clone(),
+        distribute_config.token_cap,
+        Uint128::from(claim_amount),
+    )?;
+
     let mut cosmos_msgs = vec![];
-    if claim_amount > 0u128 {
+    let mut attrs = vec![
+        attr("action", "claim"),
+        attr("claim_user", claim_user.as_str()),
+        attr("rule_owner", rule_config.rule_owner.as_str()),
+        attr("claim_amount", claim_amount.to_string()),
+    ];
+    for (beneficiary, amount) in _claim_payouts(&rule_config, claim_amount) {
+        let (liquid_amount, ve_amount) = _split_claim_amount(amount, &mode)?;
+        // the msg goes with the liquid SEILOR, there must be some to forward it with
+        if msg.is_some() && liquid_amount == 0u128 {
+            return Err(ContractError::Std(StdError::generic_err(
+                "a msg can't be attached to a claim without liquid amount",
+            )));
+        }
+        cosmos_msgs.extend(_claim_msgs(
+            &distribute_config,
+            &beneficiary,
+            liquid_amount,
+            ve_amount,
+            msg.clone(),
+        )?);
+        attrs.extend(vec![
+            attr("beneficiary", beneficiary.as_str()),
+            attr("beneficiary_amount", amount.to_string()),
+            attr("liquid_amount", liquid_amount.to_string()),
+            attr("ve_amount", ve_amount.to_string()),
+        ]);
+    }
+
+    Ok(Response::new()
+        .add_attributes(attrs)
+        .add_messages(cosmos_msgs))
+}
+
+/// Splits a claim by beneficiary bps, the last beneficiary gets the rounding remainder.
+fn _claim_payouts(rule_config: &RuleConfig, claim_amount: u128) -> Vec<(Addr, u128)> {
+    if rule_config.beneficiaries.is_empty() {
+        return vec![(rule_config.rule_owner.clone(), claim_amount)];
+    }
+    let mut payouts = vec![];
+    let mut remaining_amount = claim_amount;
+    let last_index = rule_config.beneficiaries.len() - 1;
+    for (index, beneficiary) in rule_config.beneficiaries.iter().enumerate() {
+        let amount = if index == last_index {
+            remaining_amount
+        } else {
+            claim_amount * u128::from(beneficiary.bps) / u128::from(BPS_DENOMINATOR)
+        };
+        remaining_amount -= amount;
+        payouts.push((beneficiary.address.clone(), amount));
+    }
+    payouts
+}
+
+fn _split_claim_amount(
+    claim_amount: u128,
+    mode: &ClaimMode,
+) -> Result<(u128, u128), ContractError> {
+    let ve_amount = match mode {
+        ClaimMode::Liquid {} => 0u128,
+        ClaimMode::Ve {} => claim_amount,
+        ClaimMode::Split { ve_bps } => {
+            if *ve_bps > BPS_DENOMINATOR {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "ve bps over 10000",
+                )));
+            }
+            claim_amount * u128::from(*ve_bps) / u128::from(BPS_DENOMINATOR)
+        }
+    };
+    Ok((claim_amount - ve_amount, ve_amount))
+}
+
+fn _claim_msgs(
+    distribute_config: &DistributeConfig,
+    recipient: &Addr,
+    liquid_amount: u128,
+    ve_amount: u128,
+    msg: Option<Binary>,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut cosmos_msgs = vec![];
+    if liquid_amount > 0u128 {
         // send the claim amount to user
         let seilor_mint_msg = seilor::msg::ExecuteMsg::Mint {
-            recipient: claim_user.clone().to_string(),
-            amount: Uint128::from(claim_amount.clone()),
-            contract: Option::from(claim_user.clone().to_string()),
+            recipient: recipient.to_string(),
+            amount: Uint128::from(liquid_amount),
+            contract: Option::from(recipient.to_string()),
             msg,
         };
-        let mint_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: distribute_config.distribute_token.to_string(),
             msg: to_binary(&seilor_mint_msg)?,
             funds: vec![],
-        });
-        cosmos_msgs.push(mint_msg);
+        }));
     }
-
-    Ok(Response::new()
-        .add_attributes(vec![
-            ("action", "claim"),
-            ("claim_user", claim_user.as_str()),
-            ("claim_amount", claim_amount.to_string().as_str()),
-        ])
-        .add_messages(cosmos_msgs))
+    if ve_amount > 0u128 {
+        // the fund mints veSEILOR for the user, this contract must be a ve fund minter
+        let fund = distribute_config
+            .fund
+            .clone()
+            .ok_or_else(|| StdError::generic_err("fund not set, can't claim as ve"))?;
+        let ve_fund_mint_msg = fund::msg::ExecuteMsg::VeFundMint {
+            user: recipient.clone(),
+            amount: Uint128::from(ve_amount),
+        };
+        cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: fund.to_string(),
+            msg: to_binary(&ve_fund_mint_msg)?,
+            funds: vec![],
+        }));
+    }
+    Ok(cosmos_msgs)
 }
 
 pub fn update_config(
     deps: DepsMut,
     info: MessageInfo,
     distribute_token: Option<Addr>,
+    fund: Option<Addr>,
 ) -> Result<Response, ContractError> {
     let mut distribute_config = read_distribute_config(deps.storage)?;
     if info.sender != distribute_config.gov {
@@ -104,6 +226,11 @@ pub fn update_config(
         distribute_config.distribute_token = distribute_token.clone();
         attrs.push(attr("distribute_token", distribute_token.to_string()));
     }
+    if let Some(fund) = fund {
+        deps.api.addr_validate(fund.clone().as_str())?;
+        distribute_config.fund = Some(fund.clone());
+        attrs.push(attr("fund", fund.to_string()));
+    }
     store_distribute_config(deps.storage, &distribute_config)?;
     Ok(Response::new().add_attributes(attrs))
 }
@@ -174,6 +301,10 @@ pub fn add_rule_config(
         )));
     }
 
+    if let Some(release_curve) = &rule_msg.release_curve {
+        validate_release_curve(release_curve, rule_msg.rule_total_amount)?;
+    }
+
     let rule_total_amount = rule_msg.rule_total_amount + distribute_config.rules_total_amount;
 
     if rule_total_amount > distribute_config.total_amount {
@@ -182,10 +313,12 @@ pub fn add_rule_config(
         )));
     }
 
-    let end_linear_release_time =
-        rule_msg.start_linear_release_time + rule_msg.unlock_linear_release_time;
-    let linear_release_per_second = rule_msg.unlock_linear_release_amount * BASE_RATE_12
-        / u128::from(rule_msg.unlock_linear_release_time);
+    let (end_linear_release_time, linear_release_per_second) = linear_release_schedule(
+        rule_msg.start_linear_release_time,
+        rule_msg.unlock_linear_release_amount,
+        rule_msg.unlock_linear_release_time,
+        &rule_msg.release_curve,
+    )?;
 
     distribute_config.rules_total_amount = rule_total_amount;
 
@@ -203,6 +336,11 @@ pub fn add_rule_config(
         unlock_linear_release_amount: rule_msg.unlock_linear_release_amount,
         unlock_linear_release_time: rule_msg.unlock_linear_release_time,
         linear_release_per_second,
+        release_curve: rule_msg.release_curve,
+        revoke_time: None,
+        revoked_amount: 0u128,
+        operator: None,
+        beneficiaries: vec![],
     };
     store_rule_config(deps.storage, &rule_type, &rule_config)?;
 
@@ -220,6 +358,90 @@ pub fn add_rule_config(
     ]))
 }
 
+pub fn revoke_rule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rule_type: String,
+    effective_time: u64,
+) -> Result<Response, ContractError> {
+    let mut distribute_config = read_distribute_config(deps.storage)?;
+    if distribute_config.gov != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut rule_config = read_rule_config(deps.storage, &rule_type)?;
+    if rule_config.revoke_time.is_some() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "rule already revoked",
+        )));
+    }
+    if effective_time < env.block.time.seconds() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "effective time must not be in the past",
+        )));
+    }
+
+    // everything released up to the effective time stays claimable by the owner
+    let (release_amount, linear_released_amount) =
+        calc_released_amount(&rule_config, effective_time);
+    let vested_amount =
+        (release_amount + linear_released_amount).min(rule_config.rule_total_amount);
+    let revoked_amount = rule_config.rule_total_amount - vested_amount;
+
+    rule_config.revoke_time = Some(effective_time);
+    rule_config.revoked_amount = revoked_amount;
+    store_rule_config(deps.storage, &rule_type, &rule_config)?;
+
+    // the unvested remainder goes back to the unallocated part of total_amount
+    distribute_config.rules_total_amount -= revoked_amount;
+    store_distribute_config(deps.storage, &distribute_config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke_rule"),
+        attr("rule_type", rule_type),
+        attr("effective_time", effective_time.to_string()),
+        attr("vested_amount", vested_amount.to_string()),
+        attr("revoked_amount", revoked_amount.to_string()),
+    ]))
+}
+
+pub fn set_rule_claim_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    rule_type: String,
+    operator: Option<Addr>,
+    beneficiaries: Vec<Beneficiary>,
+) -> Result<Response, ContractError> {
+    let distribute_config = read_distribute_config(deps.storage)?;
+    let mut rule_config = read_rule_config(deps.storage, &rule_type)?;
+    if info.sender != rule_config.rule_owner && info.sender != distribute_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Some(operator) = &operator {
+        deps.api.addr_validate(operator.as_str())?;
+    }
+    validate_beneficiaries(deps.api, &beneficiaries)?;
+
+    let mut attrs = vec![
+        attr("action", "set_rule_claim_config"),
+        attr("sender", info.sender.to_string()),
+        attr("rule_type", rule_type.clone()),
+    ];
+    if let Some(operator) = &operator {
+        attrs.push(attr("operator", operator.to_string()));
+    }
+    for beneficiary in &beneficiaries {
+        attrs.push(attr("beneficiary", beneficiary.address.to_string()));
+        attrs.push(attr("bps", beneficiary.bps.to_string()));
+    }
+
+    rule_config.operator = operator;
+    rule_config.beneficiaries = beneficiaries;
+    store_rule_config(deps.storage, &rule_type, &rule_config)?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
 pub fn set_gov(deps: DepsMut, info: MessageInfo, gov: Addr) -> Result<Response, ContractError> {
     let mut distribute_config = read_distribute_config(deps.storage)?;
     if distribute_config.gov != info.sender {
@@ -252,3 +474,103 @@ pub fn accept_gov(deps: DepsMut, info: MessageInfo) -> Result<Response, Contract
         attr("gov", distribute_config.gov.to_string()),
     ]))
 }
+
+/// Applies a migrate schedule correction. The patched schedule must still cover what the rule
+/// already released to its owner, so nothing claimed is taken back.
+pub fn patch_rule(deps: DepsMut, env: &Env, patch: RulePatch) -> StdResult<Vec<Attribute>> {
+    let rule_type = patch.rule_type;
+    let mut distribute_config = read_distribute_config(deps.storage)?;
+    let mut rule_config = read_rule_config(deps.storage, &rule_type)?;
+    let rule_config_state = read_rule_config_state(deps.storage, &rule_type)?;
+    if rule_config.revoke_time.is_some() {
+        return Err(StdError::generic_err(format!(
+            "rule {} is revoked and can't be patched",
+            rule_type
+        )));
+    }
+
+    if let Some(rule_total_amount) = patch.rule_total_amount {
+        let rules_total_amount = distribute_config.rules_total_amount
+            - rule_config.rule_total_amount
+            + rule_total_amount;
+        if rules_total_amount > distribute_config.total_amount {
+            return Err(StdError::generic_err(
+                "rule total amount over distribute token amount",
+            ));
+        }
+        if rule_total_amount > rule_config.rule_total_amount {
+            check_total_supply(
+                deps.as_ref(),
+                distribute_config.distribute_token.clone(),
+                distribute_config.distribute_ve_token.clone(),
+                distribute_config.token_cap,
+                Uint128::from(rule_total_amount - rule_config.rule_total_amount),
+            )?;
+        }
+        distribute_config.rules_total_amount = rules_total_amount;
+        rule_config.rule_total_amount = rule_total_amount;
+    }
+    if let Some(start_release_amount) = patch.start_release_amount {
+        rule_config.start_release_amount = start_release_amount;
+    }
+    if let Some(lock_start_time) = patch.lock_start_time {
+        rule_config.lock_start_time = lock_start_time;
+    }
+    if let Some(start_linear_release_time) = patch.start_linear_release_time {
+        rule_config.start_linear_release_time = start_linear_release_time;
+    }
+    if let Some(unlock_linear_release_amount) = patch.unlock_linear_release_amount {
+        rule_config.unlock_linear_release_amount = unlock_linear_release_amount;
+    }
+    if let Some(unlock_linear_release_time) = patch.unlock_linear_release_time {
+        rule_config.unlock_linear_release_time = unlock_linear_release_time;
+    }
+    if let Some(release_curve) = patch.release_curve {
+        rule_config.release_curve = Some(release_curve);
+    }
+    if let Some(release_curve) = &rule_config.release_curve {
+        validate_release_curve(release_curve, rule_config.rule_total_amount)?;
+    }
+    // the linear release is only derived again from the fields the patch sets, a rate set by
+    // hand on an older deployment is kept otherwise
+    let rate_patched =
+        patch.unlock_linear_release_amount.is_some() || patch.unlock_linear_release_time.is_some();
+    if rate_patched || patch.start_linear_release_time.is_some() {
+        let (end_linear_release_time, linear_release_per_second) = linear_release_schedule(
+            rule_config.start_linear_release_time,
+            rule_config.unlock_linear_release_amount,
+            rule_config.unlock_linear_release_time,
+            &rule_config.release_curve,
+        )?;
+        rule_config.end_linear_release_time = end_linear_release_time;
+        if rate_patched {
+            rule_config.linear_release_per_second = linear_release_per_second;
+        }
+    }
+
+    if rule_config.rule_total_amount < rule_config_state.claimed_amount {
+        return Err(StdError::generic_err(format!(
+            "rule {} total amount below claimed amount {}",
+            rule_type, rule_config_state.claimed_amount
+        )));
+    }
+    let (release_amount, linear_released_amount) =
+        calc_released_amount(&rule_config, env.block.time.seconds());
+    if release_amount + linear_released_amount < rule_config_state.claimed_amount {
+        return Err(StdError::generic_err(format!(
+            "rule {} would release less than claimed amount {}",
+            rule_type, rule_config_state.claimed_amount
+        )));
+    }
+
+    store_rule_config(deps.storage, &rule_type, &rule_config)?;
+    store_distribute_config(deps.storage, &distribute_config)?;
+
+    Ok(vec![
+        attr("patched_rule", rule_type),
+        attr(
+            "linear_release_per_second",
+            rule_config.linear_release_per_second.to_string(),
+        ),
+    ])
+}
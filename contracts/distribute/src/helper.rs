@@ -1,2 +1,150 @@
+use crate::state::{Beneficiary, ReleaseCurve};
+use cosmwasm_std::{Api, StdError, StdResult};
+
 // pub const BASE_RATE_6: u128 = 1000000u128;
 pub const BASE_RATE_12: u128 = 1000000000000u128;
+pub const SECONDS_PER_MONTH: u64 = 2592000u64;
+pub const BPS_DENOMINATOR: u64 = 10000u64;
+
+// End time and rate of the plain linear release, a rule on a release curve doesn't use the rate
+pub fn linear_release_schedule(
+    start_linear_release_time: u64,
+    unlock_linear_release_amount: u128,
+    unlock_linear_release_time: u64,
+    release_curve: &Option<ReleaseCurve>,
+) -> StdResult<(u64, u128)> {
+    let end_linear_release_time = start_linear_release_time + unlock_linear_release_time;
+    if release_curve.is_some() {
+        return Ok((end_linear_release_time, 0u128));
+    }
+    if unlock_linear_release_time == 0 {
+        return Err(StdError::generic_err(
+            "unlock linear release time must be greater than zero",
+        ));
+    }
+    let linear_release_per_second =
+        unlock_linear_release_amount * BASE_RATE_12 / u128::from(unlock_linear_release_time);
+    Ok((end_linear_release_time, linear_release_per_second))
+}
+
+pub fn validate_release_curve(curve: &ReleaseCurve, rule_total_amount: u128) -> StdResult<()> {
+    match curve {
+        ReleaseCurve::CliffLinear {
+            cliff_time,
+            cliff_amount,
+            end_time,
+        } => {
+            if *cliff_amount > rule_total_amount {
+                return Err(StdError::generic_err("cliff amount over rule total amount"));
+            }
+            if end_time <= cliff_time {
+                return Err(StdError::generic_err(
+                    "end time must be greater than cliff time",
+                ));
+            }
+        }
+        ReleaseCurve::MonthlyStep { months, .. } => {
+            if *months == 0 {
+                return Err(StdError::generic_err("months must be greater than zero"));
+            }
+        }
+        ReleaseCurve::PiecewiseLinear { points } => {
+            if points.is_empty() {
+                return Err(StdError::generic_err("curve points must not be empty"));
+            }
+            for pair in points.windows(2) {
+                if pair[1].time <= pair[0].time {
+                    return Err(StdError::generic_err(
+                        "curve point times must be strictly increasing",
+                    ));
+                }
+                if pair[1].amount < pair[0].amount {
+                    return Err(StdError::generic_err(
+                        "curve point amounts must not decrease",
+                    ));
+                }
+            }
+            if points.last().unwrap().amount != rule_total_amount {
+                return Err(StdError::generic_err(
+                    "curve must total exactly rule total amount",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Cumulative amount the curve has released at `time`.
+pub fn curve_released_amount(curve: &ReleaseCurve, rule_total_amount: u128, time: u64) -> u128 {
+    match curve {
+        ReleaseCurve::CliffLinear {
+            cliff_time,
+            cliff_amount,
+            end_time,
+        } => {
+            if time < *cliff_time {
+                0
+            } else if time >= *end_time {
+                rule_total_amount
+            } else {
+                cliff_amount
+                    + (rule_total_amount - cliff_amount) * u128::from(time - cliff_time)
+                        / u128::from(end_time - cliff_time)
+            }
+        }
+        ReleaseCurve::MonthlyStep { start_time, months } => {
+            if time < *start_time {
+                return 0;
+            }
+            let elapsed_months = (time - start_time) / SECONDS_PER_MONTH;
+            if elapsed_months >= *months {
+                rule_total_amount
+            } else {
+                rule_total_amount * u128::from(elapsed_months) / u128::from(*months)
+            }
+        }
+        ReleaseCurve::PiecewiseLinear { points } => {
+            let passed = points.iter().take_while(|point| point.time <= time).count();
+            if passed == 0 {
+                return 0;
+            }
+            let from = &points[passed - 1];
+            match points.get(passed) {
+                None => from.amount,
+                Some(to) => {
+                    from.amount
+                        + (to.amount - from.amount) * u128::from(time - from.time)
+                            / u128::from(to.time - from.time)
+                }
+            }
+        }
+    }
+}
+
+pub fn validate_beneficiaries(api: &dyn Api, beneficiaries: &[Beneficiary]) -> StdResult<()> {
+    if beneficiaries.is_empty() {
+        return Ok(());
+    }
+    let mut total_bps = 0u64;
+    for (index, beneficiary) in beneficiaries.iter().enumerate() {
+        api.addr_validate(beneficiary.address.as_str())?;
+        if beneficiary.bps == 0 {
+            return Err(StdError::generic_err(
+                "beneficiary bps must be greater than zero",
+            ));
+        }
+        if beneficiaries[..index]
+            .iter()
+            .any(|other| other.address == beneficiary.address)
+        {
+            return Err(StdError::generic_err("duplicate beneficiary"));
+        }
+        total_bps += beneficiary.bps;
+    }
+    if total_bps != BPS_DENOMINATOR {
+        return Err(StdError::generic_err(
+            "beneficiary bps must add up to 10000",
+        ));
+    }
+    Ok(())
+}
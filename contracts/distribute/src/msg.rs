@@ -1,4 +1,4 @@
-use crate::state::{RuleConfig, RuleConfigState};
+use crate::state::{Beneficiary, ReleaseCurve, RuleConfig, RuleConfigState};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, Uint128};
 use std::collections::HashMap;
@@ -16,6 +16,29 @@ pub struct QueryRuleInfoResponse {
     pub rule_config_state: RuleConfigState,
 }
 
+#[cw_serde]
+pub struct RuleResponse {
+    pub rule_type: String,
+    pub rule_config: RuleConfig,
+    pub rule_config_state: RuleConfigState,
+}
+
+#[cw_serde]
+pub struct RulesResponse {
+    pub rules: Vec<RuleResponse>,
+}
+
+#[cw_serde]
+pub struct VestingSummaryResponse {
+    /// Allocated to rules, revoked amounts excluded.
+    pub allocated_amount: u128,
+    /// Released by all rules at the current time, claimed or not.
+    pub released_amount: u128,
+    pub claimed_amount: u128,
+    /// Released but not claimed yet.
+    pub claimable_amount: u128,
+}
+
 #[cw_serde]
 pub struct QueryConfigResponse {
     pub gov: Addr,
@@ -25,6 +48,7 @@ pub struct QueryConfigResponse {
     pub rules_total_amount: u128,
     pub token_cap: Option<Uint128>,
     pub new_gov: Option<Addr>,
+    pub fund: Option<Addr>,
 }
 
 #[cw_serde]
@@ -45,6 +69,7 @@ pub struct RuleConfigMsg {
     pub start_linear_release_time: u64,
     pub unlock_linear_release_amount: u128,
     pub unlock_linear_release_time: u64,
+    pub release_curve: Option<ReleaseCurve>,
 }
 
 #[cw_serde]
@@ -54,6 +79,18 @@ pub struct InstantiateMsg {
     pub distribute_token: Addr,
     pub distribute_ve_token: Addr,
     pub rule_configs_map: HashMap<String, RuleConfigMsg>,
+    pub fund: Option<Addr>,
+}
+
+/// How a claim is paid out, liquid SEILOR minted by the token itself or veSEILOR minted through the fund.
+#[cw_serde]
+pub enum ClaimMode {
+    Liquid {},
+    Ve {},
+    /// `ve_bps` basis points of the claim as veSEILOR, the rest as SEILOR.
+    Split {
+        ve_bps: u64,
+    },
 }
 
 #[cw_serde]
@@ -61,9 +98,11 @@ pub enum ExecuteMsg {
     Claim {
         rule_type: String,
         msg: Option<Binary>,
+        mode: Option<ClaimMode>,
     },
     UpdateConfig {
         distribute_token: Option<Addr>,
+        fund: Option<Addr>,
     },
     UpdateRuleConfig {
         update_rule_msg: UpdateRuleConfigMsg,
@@ -72,6 +111,19 @@ pub enum ExecuteMsg {
         rule_type: String,
         rule_msg: RuleConfigMsg,
     },
+    /// Stops the rule releasing at `effective_time`, the owner keeps claiming what released until
+    /// then and the unvested remainder returns to the unallocated amount.
+    RevokeRule {
+        rule_type: String,
+        effective_time: u64,
+    },
+    /// Rule owner or gov. Replaces who may claim besides the owner and how claims are split,
+    /// beneficiary bps must add up to 10000, an empty list pays the owner.
+    SetRuleClaimConfig {
+        rule_type: String,
+        operator: Option<Addr>,
+        beneficiaries: Vec<Beneficiary>,
+    },
     SetGov {
         gov: Addr,
     },
@@ -87,7 +139,37 @@ pub enum QueryMsg {
     QueryRuleInfo { rule_type: String },
     #[returns(QueryConfigResponse)]
     QueryConfig {},
+    #[returns(RulesResponse)]
+    AllRules {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(RulesResponse)]
+    RulesByOwner {
+        owner: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(VestingSummaryResponse)]
+    VestingSummary {},
+}
+
+/// Schedule correction applied by `migrate`, unset fields are kept. The linear end time is derived
+/// again when the start or duration is patched, the rate only when the amount or duration is.
+#[cw_serde]
+pub struct RulePatch {
+    pub rule_type: String,
+    pub rule_total_amount: Option<u128>,
+    pub start_release_amount: Option<u128>,
+    pub lock_start_time: Option<u64>,
+    pub start_linear_release_time: Option<u64>,
+    pub unlock_linear_release_amount: Option<u128>,
+    pub unlock_linear_release_time: Option<u64>,
+    pub release_curve: Option<ReleaseCurve>,
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    #[serde(default)]
+    pub rule_patches: Vec<RulePatch>,
+}
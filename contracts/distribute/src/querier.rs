@@ -1,6 +1,12 @@
-use crate::helper::BASE_RATE_12;
-use crate::msg::{QueryClaimableInfoResponse, QueryConfigResponse, QueryRuleInfoResponse};
-use crate::state::{read_distribute_config, read_rule_config, read_rule_config_state};
+use crate::helper::{curve_released_amount, BASE_RATE_12};
+use crate::msg::{
+    QueryClaimableInfoResponse, QueryConfigResponse, QueryRuleInfoResponse, RuleResponse,
+    RulesResponse, VestingSummaryResponse,
+};
+use crate::state::{
+    read_all_rule_configs, read_distribute_config, read_rule_config, read_rule_config_state,
+    read_rule_configs, read_rule_configs_by_owner, RuleConfig,
+};
 use cosmwasm_std::{
     to_binary, Addr, Deps, Env, QueryRequest, StdError, StdResult, Uint128, WasmQuery,
 };
@@ -17,7 +23,9 @@ pub fn query_claimable_info(
     let rule_config = read_rule_config(deps.storage, &rule_type)?;
     let rule_config_state = read_rule_config_state(deps.storage, &rule_type)?;
 
-    let total_can_claimed_amount = rule_config.rule_total_amount - rule_config_state.claimed_amount;
+    let total_can_claimed_amount = rule_config.rule_total_amount
+        - rule_config.revoked_amount
+        - rule_config_state.claimed_amount;
     // check if can claim
     if total_can_claimed_amount == 0u128 {
         return Ok(QueryClaimableInfoResponse {
@@ -35,33 +43,54 @@ pub fn query_claimable_info(
         });
     }
 
+    // a revoked rule stops releasing at its revoke time
+    let release_time = match rule_config.revoke_time {
+        Some(revoke_time) => block_time.min(revoke_time),
+        None => block_time,
+    };
+    let (release_amount, linear_released_amount) = calc_released_amount(&rule_config, release_time);
+    let can_claim_amount =
+        release_amount + linear_released_amount - rule_config_state.claimed_amount;
+
+    Ok(QueryClaimableInfoResponse {
+        can_claim_amount,
+        release_amount,
+        linear_release_amount: linear_released_amount,
+    })
+}
+
+/// Start release and linear release amounts of the rule at `time`, a release curve reports
+/// everything it released as linear release amount.
+pub fn calc_released_amount(rule_config: &RuleConfig, time: u64) -> (u128, u128) {
+    if rule_config.lock_start_time != 0 && rule_config.lock_start_time > time {
+        return (0u128, 0u128);
+    }
+
+    if let Some(release_curve) = &rule_config.release_curve {
+        let released_amount =
+            curve_released_amount(release_curve, rule_config.rule_total_amount, time);
+        return (0u128, released_amount);
+    }
+
     let mut release_amount = 0u128;
     let mut linear_released_amount = 0u128;
 
     //Calculate the start release amount
     if rule_config.start_release_amount != 0 {
         release_amount = rule_config.start_release_amount;
-        //update the start release state
     }
 
     //Calculate the linear release amount
-    if block_time > rule_config.start_linear_release_time {
-        if block_time > rule_config.end_linear_release_time {
+    if time > rule_config.start_linear_release_time {
+        if time > rule_config.end_linear_release_time {
             linear_released_amount = rule_config.unlock_linear_release_amount;
         } else {
-            let diff_time = block_time - rule_config.start_linear_release_time;
+            let diff_time = time - rule_config.start_linear_release_time;
             linear_released_amount =
                 u128::from(diff_time) * rule_config.linear_release_per_second / BASE_RATE_12;
         }
     }
-    let can_claim_amount =
-        release_amount + linear_released_amount - rule_config_state.claimed_amount;
-
-    Ok(QueryClaimableInfoResponse {
-        can_claim_amount,
-        release_amount,
-        linear_release_amount: linear_released_amount,
-    })
+    (release_amount, linear_released_amount)
 }
 
 pub fn query_rule_info(deps: Deps, rule_type: String) -> StdResult<QueryRuleInfoResponse> {
@@ -73,6 +102,62 @@ pub fn query_rule_info(deps: Deps, rule_type: String) -> StdResult<QueryRuleInfo
     })
 }
 
+fn _rule_response(
+    deps: Deps,
+    rule_type: String,
+    rule_config: RuleConfig,
+) -> StdResult<RuleResponse> {
+    let rule_config_state = read_rule_config_state(deps.storage, &rule_type)?;
+    Ok(RuleResponse {
+        rule_type,
+        rule_config,
+        rule_config_state,
+    })
+}
+
+pub fn query_all_rules(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RulesResponse> {
+    let rules = read_rule_configs(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(rule_type, rule_config)| _rule_response(deps, rule_type, rule_config))
+        .collect::<StdResult<Vec<RuleResponse>>>()?;
+    Ok(RulesResponse { rules })
+}
+
+pub fn query_rules_by_owner(
+    deps: Deps,
+    owner: Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RulesResponse> {
+    let rules = read_rule_configs_by_owner(deps.storage, &owner, start_after, limit)?
+        .into_iter()
+        .map(|(rule_type, rule_config)| _rule_response(deps, rule_type, rule_config))
+        .collect::<StdResult<Vec<RuleResponse>>>()?;
+    Ok(RulesResponse { rules })
+}
+
+pub fn query_vesting_summary(deps: Deps, env: Env) -> StdResult<VestingSummaryResponse> {
+    let config = read_distribute_config(deps.storage)?;
+    let mut claimed_amount = 0u128;
+    let mut claimable_amount = 0u128;
+    for (rule_type, _) in read_all_rule_configs(deps.storage)? {
+        let rule_config_state = read_rule_config_state(deps.storage, &rule_type)?;
+        let claimable_info = query_claimable_info(deps, env.clone(), rule_type)?;
+        claimed_amount += rule_config_state.claimed_amount;
+        claimable_amount += claimable_info.can_claim_amount;
+    }
+    Ok(VestingSummaryResponse {
+        allocated_amount: config.rules_total_amount,
+        released_amount: claimed_amount + claimable_amount,
+        claimed_amount,
+        claimable_amount,
+    })
+}
+
 pub fn query_config(deps: Deps) -> StdResult<crate::msg::QueryConfigResponse> {
     let config = read_distribute_config(deps.storage)?;
     Ok(QueryConfigResponse {
@@ -83,6 +168,7 @@ pub fn query_config(deps: Deps) -> StdResult<crate::msg::QueryConfigResponse> {
         rules_total_amount: config.rules_total_amount,
         token_cap: config.token_cap,
         new_gov: config.new_gov,
+        fund: config.fund,
     })
 }
 
@@ -1,5 +1,5 @@
-use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +12,8 @@ pub struct DistributeConfig {
     pub new_gov: Option<Addr>,
     pub distribute_ve_token: Addr,
     pub token_cap: Option<Uint128>,
+    #[serde(default)]
+    pub fund: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -27,6 +29,50 @@ pub struct RuleConfig {
     pub unlock_linear_release_amount: u128,
     pub unlock_linear_release_time: u64,
     pub linear_release_per_second: u128,
+    #[serde(default)]
+    pub release_curve: Option<ReleaseCurve>,
+    /// Set by `RevokeRule`, nothing releases after it.
+    #[serde(default)]
+    pub revoke_time: Option<u64>,
+    /// Unvested part of `rule_total_amount` returned to the unallocated pool by `RevokeRule`.
+    #[serde(default)]
+    pub revoked_amount: u128,
+    /// May claim on behalf of the rule owner.
+    #[serde(default)]
+    pub operator: Option<Addr>,
+    /// Claims are split between the beneficiaries when set, otherwise paid to the rule owner.
+    #[serde(default)]
+    pub beneficiaries: Vec<Beneficiary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Beneficiary {
+    pub address: Addr,
+    pub bps: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurvePoint {
+    pub time: u64,
+    pub amount: u128,
+}
+
+/// Vesting schedule of a rule, replacing the start release and linear release fields when set.
+/// All amounts are cumulative and a curve always releases exactly `rule_total_amount`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseCurve {
+    /// Nothing before `cliff_time`, `cliff_amount` at `cliff_time`, then the rest linearly until `end_time`.
+    CliffLinear {
+        cliff_time: u64,
+        cliff_amount: u128,
+        end_time: u64,
+    },
+    /// `rule_total_amount / months` at the end of each 30 day month after `start_time`,
+    /// the last month releases the rounding remainder.
+    MonthlyStep { start_time: u64, months: u64 },
+    /// Linear between consecutive points, nothing before the first point.
+    PiecewiseLinear { points: Vec<CurvePoint> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -43,6 +89,10 @@ const RULE_CONFIG: Map<&str, RuleConfig> = Map::new("rule_config");
 
 const RULE_CONFIG_STATE: Map<&str, RuleConfigState> = Map::new("rule_config_state");
 
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
 pub fn store_distribute_config(
     storage: &mut dyn Storage,
     config: &DistributeConfig,
@@ -66,6 +116,43 @@ pub fn read_rule_config(storage: &dyn Storage, key: &str) -> StdResult<RuleConfi
     RULE_CONFIG.load(storage, key)
 }
 
+pub fn read_rule_configs(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, RuleConfig)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    RULE_CONFIG
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn read_rule_configs_by_owner(
+    storage: &dyn Storage,
+    owner: &Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, RuleConfig)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    RULE_CONFIG
+        .range(storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, rule_config)) => rule_config.rule_owner == *owner,
+            Err(_) => true,
+        })
+        .take(limit)
+        .collect()
+}
+
+pub fn read_all_rule_configs(storage: &dyn Storage) -> StdResult<Vec<(String, RuleConfig)>> {
+    RULE_CONFIG
+        .range(storage, None, None, Order::Ascending)
+        .collect()
+}
+
 pub fn store_rule_config_state(
     storage: &mut dyn Storage,
     key: &str,
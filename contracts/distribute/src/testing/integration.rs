@@ -1,15 +1,19 @@
 use crate::contract::{execute, instantiate, query};
 use crate::helper::BASE_RATE_12;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryClaimableInfoResponse, QueryMsg, QueryRuleInfoResponse,
+    ClaimMode, ExecuteMsg, InstantiateMsg, QueryClaimableInfoResponse, QueryMsg,
+    QueryRuleInfoResponse,
 };
+use crate::state::Beneficiary;
 use crate::testing::mock_fn::{
     mock_instantiate_msg, COMMUNITY_OFFERING_OWNER, CREATOR, DAO_OWNER, MINING_OWNER,
     RESERVE_OWNER, TEAM_OWNER,
 };
-use crate::testing::mock_third_fn::{mock_seilor_instantiate_msg, mock_ve_seilor_instantiate_msg};
+use crate::testing::mock_third_fn::{
+    mock_fund_instantiate_msg, mock_seilor_instantiate_msg, mock_ve_seilor_instantiate_msg,
+};
 use cosmwasm_std::testing::mock_env;
-use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use cosmwasm_std::{to_binary, Addr, Coin, Timestamp, Uint128};
 use cw20::{BalanceResponse, TokenInfoResponse};
 use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
 
@@ -129,6 +133,232 @@ fn test_integration_claim_all() {
     assert_eq!(res.total_supply, Uint128::from(1_000_000_000_000_000u128));
 }
 
+#[test]
+fn test_integration_claim_split() {
+    let creator = Addr::unchecked(CREATOR);
+    let mut app = mock_app(creator.clone(), vec![], None);
+    let seilor_token = seilor_contract_instance(&creator, &mut app);
+    let ve_seilor_token = ve_seilor_contract_instance(&creator, &mut app);
+    let fund = fund_contract_instance(&creator, &mut app, &ve_seilor_token, &seilor_token);
+    let seilor_distribute =
+        distribute_contract_instance(&creator, &seilor_token, &ve_seilor_token, &mut app);
+    update_distribute_contract_to_seilor(&creator, &mut app, &seilor_token, &seilor_distribute);
+
+    let rule_type = "community_offering".to_string();
+    let owner = Addr::unchecked(COMMUNITY_OFFERING_OWNER.clone().to_string());
+    let rule_config = query_rule_info(&mut app, &seilor_distribute, &rule_type).rule_config;
+    app.update_block(|block| {
+        block.time = Timestamp::from_seconds(rule_config.lock_start_time + 1000000u64);
+        block.height += 1000000u64;
+    });
+
+    // no fund configured yet
+    let success = claim_with_mode(
+        &owner,
+        &mut app,
+        &seilor_distribute,
+        &rule_type,
+        ClaimMode::Ve {},
+    );
+    assert!(!success);
+
+    // the fund mints veSEILOR for the distribute contract
+    let ve_seilor_config = ve_seilor::msg::ExecuteMsg::UpdateConfig {
+        max_minted: None,
+        fund: Some(fund.clone()),
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        ve_seilor_token.clone(),
+        &ve_seilor_config,
+        &[],
+    );
+    assert!(res.is_ok());
+    let ve_fund_minter_msg = fund::msg::ExecuteMsg::SetVeFundMinter {
+        minter: seilor_distribute.clone(),
+        is_ve_minter: true,
+    };
+    let res = app.execute_contract(creator.clone(), fund.clone(), &ve_fund_minter_msg, &[]);
+    assert!(res.is_ok());
+    let update_config_msg = ExecuteMsg::UpdateConfig {
+        distribute_token: None,
+        fund: Some(fund.clone()),
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        seilor_distribute.clone(),
+        &update_config_msg,
+        &[],
+    );
+    assert!(res.is_ok());
+
+    let success = claim_with_mode(
+        &owner,
+        &mut app,
+        &seilor_distribute,
+        &rule_type,
+        ClaimMode::Split { ve_bps: 10001 },
+    );
+    assert!(!success);
+
+    // a msg has nothing to go with when the whole claim is locked
+    let claim_msg = ExecuteMsg::Claim {
+        rule_type: rule_type.clone(),
+        msg: Some(to_binary(&"hook").unwrap()),
+        mode: Some(ClaimMode::Ve {}),
+    };
+    let res = app.execute_contract(owner.clone(), seilor_distribute.clone(), &claim_msg, &[]);
+    assert!(res.is_err());
+
+    let success = claim_with_mode(
+        &owner,
+        &mut app,
+        &seilor_distribute,
+        &rule_type,
+        ClaimMode::Split { ve_bps: 4000 },
+    );
+    assert!(success);
+
+    let ve_amount = rule_config.start_release_amount * 4000 / 10000;
+    let res = get_seilor_balance(&mut app, &seilor_token, &owner);
+    assert_eq!(
+        res.balance.u128(),
+        rule_config.start_release_amount - ve_amount
+    );
+    let res = get_seilor_balance(&mut app, &ve_seilor_token, &owner);
+    assert_eq!(res.balance.u128(), ve_amount);
+
+    let res = query_claimable_info(&mut app, &seilor_distribute, &rule_type);
+    assert_eq!(res.can_claim_amount, 0u128);
+}
+
+#[test]
+fn test_integration_claim_beneficiaries() {
+    let creator = Addr::unchecked(CREATOR);
+    let mut app = mock_app(creator.clone(), vec![], None);
+    let seilor_token = seilor_contract_instance(&creator, &mut app);
+    let ve_seilor_token = ve_seilor_contract_instance(&creator, &mut app);
+    let seilor_distribute =
+        distribute_contract_instance(&creator, &seilor_token, &ve_seilor_token, &mut app);
+    update_distribute_contract_to_seilor(&creator, &mut app, &seilor_token, &seilor_distribute);
+
+    let rule_type = "community_offering".to_string();
+    let owner = Addr::unchecked(COMMUNITY_OFFERING_OWNER.clone().to_string());
+    let operator = Addr::unchecked("operator");
+    let beneficiary = Addr::unchecked("beneficiary");
+    let rule_config = query_rule_info(&mut app, &seilor_distribute, &rule_type).rule_config;
+    app.update_block(|block| {
+        block.time = Timestamp::from_seconds(rule_config.lock_start_time + 1000000u64);
+        block.height += 1000000u64;
+    });
+
+    // not the owner or gov
+    let mut set_claim_config_msg = ExecuteMsg::SetRuleClaimConfig {
+        rule_type: rule_type.clone(),
+        operator: Some(operator.clone()),
+        beneficiaries: vec![
+            Beneficiary {
+                address: owner.clone(),
+                bps: 7000,
+            },
+            Beneficiary {
+                address: beneficiary.clone(),
+                bps: 2000,
+            },
+        ],
+    };
+    let res = app.execute_contract(
+        operator.clone(),
+        seilor_distribute.clone(),
+        &set_claim_config_msg,
+        &[],
+    );
+    assert!(res.is_err());
+
+    // bps don't add up to 10000
+    let res = app.execute_contract(
+        owner.clone(),
+        seilor_distribute.clone(),
+        &set_claim_config_msg,
+        &[],
+    );
+    assert!(res.is_err());
+
+    if let ExecuteMsg::SetRuleClaimConfig { beneficiaries, .. } = &mut set_claim_config_msg {
+        beneficiaries[1].bps = 3000;
+    }
+    let res = app.execute_contract(
+        owner.clone(),
+        seilor_distribute.clone(),
+        &set_claim_config_msg,
+        &[],
+    );
+    assert!(res.is_ok());
+
+    // an operator can't attach a msg for the owner
+    let claim_msg = ExecuteMsg::Claim {
+        rule_type: rule_type.clone(),
+        msg: Some(to_binary(&"hook").unwrap()),
+        mode: None,
+    };
+    let res = app.execute_contract(operator.clone(), seilor_distribute.clone(), &claim_msg, &[]);
+    assert!(res.is_err());
+    // nor lock the owner's claim as veSEILOR
+    assert!(!claim_with_mode(
+        &operator,
+        &mut app,
+        &seilor_distribute,
+        &rule_type,
+        ClaimMode::Ve {},
+    ));
+    // the owner's msg would not reach the beneficiaries
+    let res = app.execute_contract(owner.clone(), seilor_distribute.clone(), &claim_msg, &[]);
+    assert!(res.is_err());
+
+    let claim_msg = ExecuteMsg::Claim {
+        rule_type: rule_type.clone(),
+        msg: None,
+        mode: None,
+    };
+    let res = app.execute_contract(operator.clone(), seilor_distribute.clone(), &claim_msg, &[]);
+    assert!(res.is_ok());
+    let beneficiary_amounts: Vec<String> = res
+        .unwrap()
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attribute| attribute.key == "beneficiary_amount")
+        .map(|attribute| attribute.value.clone())
+        .collect();
+    assert_eq!(
+        beneficiary_amounts,
+        vec!["24500000000000".to_string(), "10500000000000".to_string()]
+    );
+
+    let res = get_seilor_balance(&mut app, &seilor_token, &owner);
+    assert_eq!(res.balance.u128(), 24_500_000_000_000u128);
+    let res = get_seilor_balance(&mut app, &seilor_token, &beneficiary);
+    assert_eq!(res.balance.u128(), 10_500_000_000_000u128);
+    let res = get_seilor_balance(&mut app, &seilor_token, &operator);
+    assert_eq!(res.balance.u128(), 0u128);
+}
+
+fn claim_with_mode(
+    sender: &Addr,
+    app: &mut App,
+    seilor_distribute: &Addr,
+    rule_type: &String,
+    mode: ClaimMode,
+) -> bool {
+    let claim_msg = ExecuteMsg::Claim {
+        rule_type: rule_type.clone(),
+        msg: None,
+        mode: Some(mode),
+    };
+    let res = app.execute_contract(sender.clone(), seilor_distribute.clone(), &claim_msg, &[]);
+    res.is_ok()
+}
+
 fn check_rule_type(
     mut app: &mut App,
     seilor_token: &Addr,
@@ -291,6 +521,7 @@ fn claim(sender: &Addr, app: &mut App, seilor_distribute: &Addr, rule_type: &Str
     let claim_msg = ExecuteMsg::Claim {
         rule_type: rule_type.clone(),
         msg: None,
+        mode: None,
     };
     let res = app.execute_contract(
         sender.clone(),
@@ -418,3 +649,27 @@ fn ve_seilor_contract_instance(creator: &Addr, mut app: &mut App) -> Addr {
         .unwrap();
     ve_seilor_token
 }
+
+fn fund_contract_instance(
+    creator: &Addr,
+    app: &mut App,
+    ve_seilor_token: &Addr,
+    seilor_token: &Addr,
+) -> Addr {
+    let fund_contract = Box::new(ContractWrapper::new_with_empty(
+        fund::contract::execute,
+        fund::contract::instantiate,
+        fund::contract::query,
+    ));
+    let fund_code_id = app.store_code(fund_contract);
+    let fund_instance_msg = mock_fund_instantiate_msg(ve_seilor_token, seilor_token);
+    app.instantiate_contract(
+        fund_code_id,
+        creator.clone(),
+        &fund_instance_msg,
+        &[], // no funds
+        String::from("FUND"),
+        None,
+    )
+    .unwrap()
+}
@@ -31,6 +31,7 @@ pub fn mock_instantiate_msg(distribute_token: Addr, distribute_ve_token: Addr) -
             start_linear_release_time: 1696315269u64,
             unlock_linear_release_amount: 140_000_000_000_000u128,
             unlock_linear_release_time: 31622399u64,
+            release_curve: None,
         },
     );
     rule_configs_map.insert(
@@ -45,6 +46,7 @@ pub fn mock_instantiate_msg(distribute_token: Addr, distribute_ve_token: Addr) -
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 200_000_000_000_000u128,
             unlock_linear_release_time: 157852799u64,
+            release_curve: None,
         },
     );
 
@@ -60,6 +62,7 @@ pub fn mock_instantiate_msg(distribute_token: Addr, distribute_ve_token: Addr) -
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 161_000_000_000_000u128,
             unlock_linear_release_time: 94694400u64,
+            release_curve: None,
         },
     );
     rule_configs_map.insert(
@@ -74,6 +77,7 @@ pub fn mock_instantiate_msg(distribute_token: Addr, distribute_ve_token: Addr) -
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 350_000_000_000_000u128,
             unlock_linear_release_time: 65836800u64,
+            release_curve: None,
         },
     );
 
@@ -89,6 +93,7 @@ pub fn mock_instantiate_msg(distribute_token: Addr, distribute_ve_token: Addr) -
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 45_000_000_000_000u128,
             unlock_linear_release_time: 94694400u64,
+            release_curve: None,
         },
     );
 
@@ -98,6 +103,7 @@ pub fn mock_instantiate_msg(distribute_token: Addr, distribute_ve_token: Addr) -
         distribute_token,
         distribute_ve_token,
         rule_configs_map,
+        fund: None,
     }
 }
 
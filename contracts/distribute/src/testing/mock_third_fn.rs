@@ -1,3 +1,4 @@
+use cosmwasm_std::{Addr, Uint64};
 use cw20_base::msg::InstantiateMarketingInfo;
 
 pub fn mock_seilor_instantiate_msg() -> seilor::msg::InstantiateMsg {
@@ -45,3 +46,16 @@ pub fn mock_ve_seilor_instantiate_msg() -> ve_seilor::msg::InstantiateMsg {
     };
     msg
 }
+
+pub fn mock_fund_instantiate_msg(ve_seilor: &Addr, seilor: &Addr) -> fund::msg::InstantiateMsg {
+    let msg = fund::msg::InstantiateMsg {
+        gov: None,
+        ve_seilor_addr: ve_seilor.clone(),
+        seilor_addr: seilor.clone(),
+        kusd_denom: "factory/kusd".to_string(),
+        kusd_reward_addr: Addr::unchecked("kusd_reward"),
+        exit_cycle: Uint64::from(2592000u64),
+        claim_able_time: Uint64::from(1687190400u64),
+    };
+    msg
+}
@@ -1,10 +1,20 @@
+use crate::contract::migrate;
 use crate::error::ContractError;
-use crate::handler::{add_rule_config, update_config, update_rule_config};
-use crate::msg::{RuleConfigMsg, UpdateRuleConfigMsg};
-use crate::querier::{query_config, query_rule_info};
-use crate::testing::mock_fn::{mock_instantiate, mock_instantiate_msg};
+use crate::handler::{add_rule_config, claim, revoke_rule, update_config, update_rule_config};
+use crate::helper::BASE_RATE_12;
+use crate::helper::SECONDS_PER_MONTH;
+use crate::msg::{MigrateMsg, RuleConfigMsg, RulePatch, UpdateRuleConfigMsg};
+use crate::querier::{
+    query_all_rules, query_claimable_info, query_config, query_rule_info, query_rules_by_owner,
+    query_vesting_summary,
+};
+use crate::state::{
+    read_rule_config, read_rule_config_state, store_rule_config, store_rule_config_state,
+    CurvePoint, ReleaseCurve,
+};
+use crate::testing::mock_fn::{mock_instantiate, mock_instantiate_msg, TEAM_OWNER};
 use cosmwasm_std::testing::mock_info;
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, Env, StdError, Timestamp};
 
 const SEILOR_TOKEN: &str = "seilor_token";
 const VE_SEILOR_TOKEN: &str = "ve_seilor_token";
@@ -38,7 +48,7 @@ fn test_update_config() {
 
     // let new_gov = Some(Addr::unchecked("new_gov"));
     let new_distribute_token = Some(Addr::unchecked("new_distribute_token"));
-    let res = update_config(deps.as_mut(), info, new_distribute_token.clone());
+    let res = update_config(deps.as_mut(), info, new_distribute_token.clone(), None);
     assert!(res.is_ok());
     let config = query_config(deps.as_ref()).unwrap();
     // assert_eq!(config.gov, Addr::unchecked("new_gov"));
@@ -48,7 +58,7 @@ fn test_update_config() {
     );
 
     let other_info = mock_info("other", &[]);
-    let res = update_config(deps.as_mut(), other_info, new_distribute_token);
+    let res = update_config(deps.as_mut(), other_info, new_distribute_token, None);
     assert!(res.is_err());
     assert_eq!(res.err().unwrap(), ContractError::Unauthorized {});
 }
@@ -101,6 +111,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            release_curve: None,
         },
     );
 
@@ -132,6 +143,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            release_curve: None,
         },
     );
     assert!(res.is_ok());
@@ -169,6 +181,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            release_curve: None,
         },
     );
     assert!(res.is_err());
@@ -191,6 +204,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            release_curve: None,
         },
     );
     assert!(res.is_err());
@@ -216,6 +230,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 1688366468u64,
             unlock_linear_release_amount: 10000000000000u128,
             unlock_linear_release_time: 1719988868,
+            release_curve: None,
         },
     );
     assert!(res.is_err());
@@ -226,3 +241,519 @@ fn test_add_rule_config() {
         ))
     );
 }
+
+fn curve_rule_msg(rule_total_amount: u128, release_curve: ReleaseCurve) -> RuleConfigMsg {
+    RuleConfigMsg {
+        rule_name: "curve".to_string(),
+        rule_owner: Addr::unchecked("rule_owner"),
+        rule_total_amount,
+        start_release_amount: 0,
+        lock_start_time: 0,
+        start_linear_release_time: 0,
+        unlock_linear_release_amount: 0,
+        unlock_linear_release_time: 0,
+        release_curve: Some(release_curve),
+    }
+}
+
+fn claimable_at(deps: cosmwasm_std::Deps, env: &Env, rule_type: &str, time: u64) -> u128 {
+    let mut env = env.clone();
+    env.block.time = Timestamp::from_seconds(time);
+    query_claimable_info(deps, env, rule_type.to_string())
+        .unwrap()
+        .can_claim_amount
+}
+
+#[test]
+fn test_release_curves() {
+    let mut msg = mock_instantiate_msg(
+        Addr::unchecked(SEILOR_TOKEN),
+        Addr::unchecked(VE_SEILOR_TOKEN),
+    );
+    // leave room for the curve rules
+    msg.total_amount += 10000u128;
+    let (mut deps, env, info, res) = mock_instantiate(msg.clone());
+    assert!(res.is_ok());
+
+    // cliff then linear
+    let release_curve = ReleaseCurve::CliffLinear {
+        cliff_time: 1000,
+        cliff_amount: 250,
+        end_time: 2000,
+    };
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "cliff".to_string(),
+        curve_rule_msg(1000, release_curve),
+    );
+    assert!(res.is_ok());
+    assert_eq!(claimable_at(deps.as_ref(), &env, "cliff", 999), 0);
+    assert_eq!(claimable_at(deps.as_ref(), &env, "cliff", 1000), 250);
+    assert_eq!(claimable_at(deps.as_ref(), &env, "cliff", 1500), 625);
+    assert_eq!(claimable_at(deps.as_ref(), &env, "cliff", 3000), 1000);
+
+    // monthly steps, the last month releases the remainder
+    let release_curve = ReleaseCurve::MonthlyStep {
+        start_time: 1000,
+        months: 3,
+    };
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "step".to_string(),
+        curve_rule_msg(1000, release_curve),
+    );
+    assert!(res.is_ok());
+    let month = SECONDS_PER_MONTH;
+    assert_eq!(
+        claimable_at(deps.as_ref(), &env, "step", 1000 + month - 1),
+        0
+    );
+    assert_eq!(claimable_at(deps.as_ref(), &env, "step", 1000 + month), 333);
+    assert_eq!(
+        claimable_at(deps.as_ref(), &env, "step", 1000 + 2 * month),
+        666
+    );
+    assert_eq!(
+        claimable_at(deps.as_ref(), &env, "step", 1000 + 3 * month),
+        1000
+    );
+
+    // piecewise linear
+    let release_curve = ReleaseCurve::PiecewiseLinear {
+        points: vec![
+            CurvePoint {
+                time: 1000,
+                amount: 100,
+            },
+            CurvePoint {
+                time: 2000,
+                amount: 300,
+            },
+            CurvePoint {
+                time: 4000,
+                amount: 1000,
+            },
+        ],
+    };
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "piecewise".to_string(),
+        curve_rule_msg(1000, release_curve),
+    );
+    assert!(res.is_ok());
+    assert_eq!(claimable_at(deps.as_ref(), &env, "piecewise", 999), 0);
+    assert_eq!(claimable_at(deps.as_ref(), &env, "piecewise", 1000), 100);
+    assert_eq!(claimable_at(deps.as_ref(), &env, "piecewise", 1500), 200);
+    assert_eq!(claimable_at(deps.as_ref(), &env, "piecewise", 3000), 650);
+    assert_eq!(claimable_at(deps.as_ref(), &env, "piecewise", 5000), 1000);
+
+    // without a curve the linear release needs a duration
+    let mut rule_msg = curve_rule_msg(
+        1000,
+        ReleaseCurve::MonthlyStep {
+            start_time: 1000,
+            months: 1,
+        },
+    );
+    rule_msg.release_curve = None;
+    let res = add_rule_config(deps.as_mut(), info.clone(), "linear".to_string(), rule_msg);
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "unlock linear release time must be greater than zero",
+        ))
+    );
+
+    // a curve must total the rule total amount
+    let release_curve = ReleaseCurve::PiecewiseLinear {
+        points: vec![CurvePoint {
+            time: 1000,
+            amount: 999,
+        }],
+    };
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "short".to_string(),
+        curve_rule_msg(1000, release_curve),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "curve must total exactly rule total amount",
+        ))
+    );
+
+    let release_curve = ReleaseCurve::CliffLinear {
+        cliff_time: 1000,
+        cliff_amount: 1001,
+        end_time: 2000,
+    };
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "over".to_string(),
+        curve_rule_msg(1000, release_curve),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err("cliff amount over rule total amount",))
+    );
+}
+
+#[test]
+fn test_revoke_rule() {
+    let mut msg = mock_instantiate_msg(
+        Addr::unchecked(SEILOR_TOKEN),
+        Addr::unchecked(VE_SEILOR_TOKEN),
+    );
+    msg.total_amount += 10000u128;
+    let (mut deps, env, info, res) = mock_instantiate(msg.clone());
+    assert!(res.is_ok());
+    let now = env.block.time.seconds();
+
+    let release_curve = ReleaseCurve::PiecewiseLinear {
+        points: vec![
+            CurvePoint {
+                time: now,
+                amount: 0,
+            },
+            CurvePoint {
+                time: now + 1000,
+                amount: 1000,
+            },
+        ],
+    };
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "contributor".to_string(),
+        curve_rule_msg(1000, release_curve),
+    );
+    assert!(res.is_ok());
+    let rules_total_amount = query_config(deps.as_ref()).unwrap().rules_total_amount;
+
+    let mut revoke_env = env.clone();
+    revoke_env.block.time = Timestamp::from_seconds(now + 100);
+
+    let res = revoke_rule(
+        deps.as_mut(),
+        revoke_env.clone(),
+        mock_info("other", &[]),
+        "contributor".to_string(),
+        now + 400,
+    );
+    assert_eq!(res.err().unwrap(), ContractError::Unauthorized {});
+
+    let res = revoke_rule(
+        deps.as_mut(),
+        revoke_env.clone(),
+        info.clone(),
+        "contributor".to_string(),
+        now + 99,
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "effective time must not be in the past",
+        ))
+    );
+
+    let res = revoke_rule(
+        deps.as_mut(),
+        revoke_env.clone(),
+        info.clone(),
+        "contributor".to_string(),
+        now + 400,
+    );
+    assert!(res.is_ok());
+
+    let rule_config = query_rule_info(deps.as_ref(), "contributor".to_string())
+        .unwrap()
+        .rule_config;
+    assert_eq!(rule_config.revoke_time, Some(now + 400));
+    assert_eq!(rule_config.revoked_amount, 600);
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(config.rules_total_amount, rules_total_amount - 600);
+
+    // releases until the effective time only
+    assert_eq!(
+        claimable_at(deps.as_ref(), &env, "contributor", now + 200),
+        200
+    );
+    assert_eq!(
+        claimable_at(deps.as_ref(), &env, "contributor", now + 2000),
+        400
+    );
+
+    let res = revoke_rule(
+        deps.as_mut(),
+        revoke_env.clone(),
+        info.clone(),
+        "contributor".to_string(),
+        now + 500,
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err("rule already revoked"))
+    );
+
+    let mut claim_env = env.clone();
+    claim_env.block.time = Timestamp::from_seconds(now + 2000);
+    let res = claim(
+        deps.as_mut(),
+        claim_env.clone(),
+        mock_info("rule_owner", &[]),
+        "contributor".to_string(),
+        None,
+        None,
+    );
+    assert!(res.is_ok());
+    let query_data = query_rule_info(deps.as_ref(), "contributor".to_string()).unwrap();
+    assert_eq!(query_data.rule_config_state.claimed_amount, 400);
+
+    let res = claim(
+        deps.as_mut(),
+        claim_env,
+        mock_info("rule_owner", &[]),
+        "contributor".to_string(),
+        None,
+        None,
+    );
+    assert_eq!(res.err().unwrap(), ContractError::NoMoreAmountClaim {});
+}
+
+#[test]
+fn test_rule_queries() {
+    let msg = mock_instantiate_msg(
+        Addr::unchecked(SEILOR_TOKEN),
+        Addr::unchecked(VE_SEILOR_TOKEN),
+    );
+    let (mut deps, env, _, res) = mock_instantiate(msg.clone());
+    assert!(res.is_ok());
+
+    let res = query_all_rules(deps.as_ref(), None, Some(2)).unwrap();
+    let rule_types: Vec<String> = res.rules.into_iter().map(|rule| rule.rule_type).collect();
+    assert_eq!(rule_types, vec!["community_offering", "dao"]);
+
+    let res = query_all_rules(deps.as_ref(), Some("dao".to_string()), None).unwrap();
+    let rule_types: Vec<String> = res.rules.into_iter().map(|rule| rule.rule_type).collect();
+    assert_eq!(rule_types, vec!["mining", "reserve", "team"]);
+
+    let res = query_rules_by_owner(deps.as_ref(), Addr::unchecked(TEAM_OWNER), None, None).unwrap();
+    assert_eq!(res.rules.len(), 1);
+    assert_eq!(res.rules[0].rule_type, "team");
+    assert_eq!(
+        res.rules[0].rule_config.rule_total_amount,
+        200_000_000_000_000u128
+    );
+
+    let res = query_rules_by_owner(deps.as_ref(), Addr::unchecked("other"), None, None).unwrap();
+    assert!(res.rules.is_empty());
+
+    // before any lock starts
+    let res = query_vesting_summary(deps.as_ref(), env.clone()).unwrap();
+    assert_eq!(res.allocated_amount, msg.total_amount);
+    assert_eq!(res.released_amount, 0u128);
+    assert_eq!(res.claimable_amount, 0u128);
+
+    // after every rule finished releasing
+    let mut end_env = env.clone();
+    end_env.block.time = Timestamp::from_seconds(1996315269u64);
+    let res = query_vesting_summary(deps.as_ref(), end_env).unwrap();
+    assert_eq!(res.released_amount, msg.total_amount);
+    assert_eq!(res.claimed_amount, 0u128);
+    assert_eq!(res.claimable_amount, msg.total_amount);
+
+    // rules by owner are paginated by rule type
+    let team_rule = read_rule_config(deps.as_ref().storage, "team").unwrap();
+    store_rule_config(deps.as_mut().storage, "aaa_team", &team_rule).unwrap();
+    let team_state = read_rule_config_state(deps.as_ref().storage, "team").unwrap();
+    store_rule_config_state(deps.as_mut().storage, "aaa_team", &team_state).unwrap();
+    let owner = Addr::unchecked(TEAM_OWNER);
+    let res = query_rules_by_owner(deps.as_ref(), owner.clone(), None, Some(1)).unwrap();
+    let rule_types: Vec<String> = res.rules.into_iter().map(|rule| rule.rule_type).collect();
+    assert_eq!(rule_types, vec!["aaa_team"]);
+    let res =
+        query_rules_by_owner(deps.as_ref(), owner, Some("aaa_team".to_string()), None).unwrap();
+    let rule_types: Vec<String> = res.rules.into_iter().map(|rule| rule.rule_type).collect();
+    assert_eq!(rule_types, vec!["team"]);
+}
+
+fn rule_patch(rule_type: &str) -> RulePatch {
+    RulePatch {
+        rule_type: rule_type.to_string(),
+        rule_total_amount: None,
+        start_release_amount: None,
+        lock_start_time: None,
+        start_linear_release_time: None,
+        unlock_linear_release_amount: None,
+        unlock_linear_release_time: None,
+        release_curve: None,
+    }
+}
+
+#[test]
+fn test_migrate() {
+    let msg = mock_instantiate_msg(
+        Addr::unchecked(SEILOR_TOKEN),
+        Addr::unchecked(VE_SEILOR_TOKEN),
+    );
+    let (mut deps, env, _, res) = mock_instantiate(msg.clone());
+    assert!(res.is_ok());
+
+    // no patches
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![],
+        },
+    );
+    assert!(res.is_ok());
+
+    let mut patch = rule_patch("team");
+    patch.unlock_linear_release_time = Some(26265600);
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![patch],
+        },
+    );
+    assert!(res.is_ok());
+    let rule_config = query_rule_info(deps.as_ref(), "team".to_string())
+        .unwrap()
+        .rule_config;
+    assert_eq!(rule_config.unlock_linear_release_time, 26265600);
+    assert_eq!(
+        rule_config.end_linear_release_time,
+        rule_config.start_linear_release_time + 26265600
+    );
+    assert_eq!(
+        rule_config.linear_release_per_second,
+        200_000_000_000_000u128 * BASE_RATE_12 / 26265600
+    );
+
+    // a rate set by hand is kept by patches that don't touch the linear amount or duration
+    let mut rule_config = read_rule_config(deps.as_ref().storage, "team").unwrap();
+    rule_config.linear_release_per_second = 7_000_000u128 * BASE_RATE_12;
+    store_rule_config(deps.as_mut().storage, "team", &rule_config).unwrap();
+    let mut patch = rule_patch("team");
+    patch.lock_start_time = Some(rule_config.lock_start_time + 1);
+    patch.start_linear_release_time = Some(rule_config.start_linear_release_time + 1);
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![patch],
+        },
+    );
+    assert!(res.is_ok());
+    let patched = read_rule_config(deps.as_ref().storage, "team").unwrap();
+    assert_eq!(
+        patched.linear_release_per_second,
+        7_000_000u128 * BASE_RATE_12
+    );
+    assert_eq!(
+        patched.end_linear_release_time,
+        rule_config.end_linear_release_time + 1
+    );
+
+    // a release curve is checked again against the patched total
+    let mut patch = rule_patch("team");
+    patch.release_curve = Some(ReleaseCurve::PiecewiseLinear {
+        points: vec![CurvePoint {
+            time: 1000,
+            amount: 200_000_000_000_000u128,
+        }],
+    });
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![patch],
+        },
+    );
+    assert!(res.is_ok());
+    let mut patch = rule_patch("team");
+    patch.rule_total_amount = Some(100_000_000_000_000u128);
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![patch],
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("curve must total exactly rule total amount")
+    );
+    let mut rule_config = read_rule_config(deps.as_ref().storage, "team").unwrap();
+    rule_config.release_curve = None;
+    store_rule_config(deps.as_mut().storage, "team", &rule_config).unwrap();
+
+    // the patched schedule must cover what was claimed
+    let mut rule_config_state = read_rule_config_state(deps.as_ref().storage, "team").unwrap();
+    rule_config_state.claimed_amount = 1;
+    store_rule_config_state(deps.as_mut().storage, "team", &rule_config_state).unwrap();
+    let mut patch = rule_patch("team");
+    patch.start_release_amount = Some(0);
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![patch],
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("rule team would release less than claimed amount 1")
+    );
+
+    // the allocation can't exceed total amount
+    let mut patch = rule_patch("dao");
+    patch.rule_total_amount = Some(230_000_000_000_001u128);
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![patch],
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("rule total amount over distribute token amount")
+    );
+
+    // version gating
+    cw2::set_contract_version(
+        deps.as_mut().storage,
+        "kryptonite.finance:seilor-distribute",
+        "9.0.0",
+    )
+    .unwrap();
+    let res = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            rule_patches: vec![],
+        },
+    );
+    assert!(res.is_err());
+    cw2::set_contract_version(deps.as_mut().storage, "other", "0.1.0").unwrap();
+    let res = migrate(
+        deps.as_mut(),
+        env,
+        MigrateMsg {
+            rule_patches: vec![],
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        StdError::generic_err("Can't migrate from contract other")
+    );
+}
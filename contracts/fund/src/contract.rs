@@ -52,6 +52,7 @@ pub fn instantiate(
         claim_able_time: msg.claim_able_time,
         new_gov: None,
         token_cap,
+        boost_addr: None,
     };
 
     store_fund_config(deps.storage, &config)?;
@@ -1,8 +1,8 @@
 use crate::helper::{BASE_RATE_12, BASE_RATE_6};
 use crate::msg::{Cw20HookMsg, UpdateConfigMsg};
 use crate::querier::{
-    check_total_supply, earned, get_claim_able_seilor, get_reserved_seilor_for_vesting,
-    is_ve_fund_minter, total_staked,
+    boost_locked_of, check_total_supply, earned, get_claim_able_seilor,
+    get_reserved_seilor_for_vesting, is_ve_fund_minter, staked_of, total_staked,
 };
 use crate::state::{
     read_fund_config, read_rewards, read_time2full_redemption, read_unstake_rate,
@@ -65,6 +65,11 @@ pub fn update_fund_config(
         config.claim_able_time = claim_able_time.clone();
         attrs.push(attr("claim_able_time", claim_able_time.to_string()));
     }
+    if let Some(boost_addr) = msg.boost_addr {
+        deps.api.addr_validate(boost_addr.clone().as_str())?;
+        config.boost_addr = Some(boost_addr.clone());
+        attrs.push(attr("boost_addr", boost_addr.to_string()));
+    }
     store_fund_config(deps.storage, &config)?;
     Ok(Response::new().add_attributes(attrs))
 }
@@ -145,6 +150,14 @@ pub fn unstake(
         return Err(StdError::generic_err("It is not yet time to claim."));
     }
 
+    let locked_amount = boost_locked_of(deps.as_ref(), &env, sender.clone())?;
+    if !locked_amount.is_zero() {
+        let staked = staked_of(deps.as_ref(), sender.clone())?;
+        if staked.checked_sub(amount)?.lt(&locked_amount) {
+            return Err(StdError::generic_err("ve_seilor is locked in boost"));
+        }
+    }
+
     let mut sub_msgs = vec![];
     let ve_seilor_burn_msg = ve_seilor::msg::ExecuteMsg::Burn {
         user: sender.clone().to_string(),
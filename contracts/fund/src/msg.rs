@@ -9,6 +9,7 @@ pub struct UpdateConfigMsg {
     pub kusd_denom: Option<String>,
     pub kusd_reward_addr: Option<Addr>,
     pub claim_able_time: Option<Uint64>,
+    pub boost_addr: Option<Addr>,
 }
 
 #[cw_serde]
@@ -144,6 +145,8 @@ pub struct FundConfigResponse {
     pub claim_able_time: Uint64,
     pub new_gov: Option<Addr>,
     pub token_cap: Option<Uint128>,
+    // ve_seilor locked in boost can not be unstaked until the lock ends
+    pub boost_addr: Option<Addr>,
 }
 
 #[cw_serde]
@@ -32,6 +32,7 @@ pub fn fund_config(deps: Deps) -> StdResult<FundConfigResponse> {
         claim_able_time: config.claim_able_time,
         new_gov: config.new_gov,
         token_cap: config.token_cap,
+        boost_addr: config.boost_addr,
     })
 }
 
@@ -58,6 +59,22 @@ pub fn staked_of(deps: Deps, staker: Addr) -> StdResult<Uint128> {
     Ok(res.balance)
 }
 
+// ve_seilor the user locked in boost, a lock past its unlock time no longer holds anything
+pub fn boost_locked_of(deps: Deps, env: &Env, user: Addr) -> StdResult<Uint128> {
+    let boost_addr = read_fund_config(deps.storage)?.boost_addr;
+    if let Some(boost_addr) = boost_addr {
+        let res: boost::msg::LockStatusResponse =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: boost_addr.to_string(),
+                msg: to_binary(&boost::msg::QueryMsg::GetUserLockStatus { user })?,
+            }))?;
+        if res.unlock_time.u128() > env.block.time.seconds() as u128 {
+            return Ok(res.locked_amount);
+        }
+    }
+    Ok(Uint128::zero())
+}
+
 pub fn get_claim_able_seilor(
     deps: Deps,
     env: Env,
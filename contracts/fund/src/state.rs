@@ -20,6 +20,8 @@ pub struct FundConfig {
     pub claim_able_time: Uint64,
     pub new_gov: Option<Addr>,
     pub token_cap: Option<Uint128>,
+    // ve_seilor locked in boost can not be unstaked until the lock ends
+    pub boost_addr: Option<Addr>,
 }
 
 const FUND_CONFIG: Item<FundConfig> = Item::new("fund_config");
@@ -1,6 +1,8 @@
 use crate::contract::{execute, instantiate, query};
 use crate::msg::Cw20HookMsg::Stake;
-use crate::msg::{ExecuteMsg, GetClaimAbleKusdResponse, GetClaimAbleSeilorResponse, QueryMsg};
+use crate::msg::{
+    ExecuteMsg, GetClaimAbleKusdResponse, GetClaimAbleSeilorResponse, QueryMsg, UpdateConfigMsg,
+};
 use crate::testing::mock_fn::{mock_instantiate_msg, CREATOR, KUSD_DENOM, KUSD_REWARD_ADDR};
 use crate::testing::mock_third_fn::{mock_seilor_instantiate_msg, mock_ve_seilor_instantiate_msg};
 use cosmwasm_std::testing::mock_env;
@@ -36,6 +38,15 @@ fn store_ve_seilor_contract(app: &mut App) -> u64 {
     app.store_code(ve_seilor_contract)
 }
 
+fn store_boost_contract(app: &mut App) -> u64 {
+    let boost_contract = Box::new(ContractWrapper::new_with_empty(
+        boost::contract::execute,
+        boost::contract::instantiate,
+        boost::contract::query,
+    ));
+    app.store_code(boost_contract)
+}
+
 fn store_seilor_fun_contract(app: &mut App) -> u64 {
     let seilor_fun_contract =
         Box::new(ContractWrapper::new_with_empty(execute, instantiate, query));
@@ -243,6 +254,106 @@ fn test_integration() {
     );
 }
 
+#[test]
+fn test_unstake_locked_in_boost() {
+    let block_time = 1688105053u64;
+    let creator = Addr::unchecked(CREATOR);
+    let mut app = mock_app(creator.clone(), vec![], Option::Some(block_time));
+
+    let seilor_token = seilor_contract_instance(&creator, &mut app);
+    let ve_seilor_token = ve_seilor_contract_instance(&creator, &mut app);
+    let test_contract_addr =
+        fund_contract_instance(&creator, &mut app, &seilor_token, &ve_seilor_token);
+    add_seilor_and_ve_seilor_role_to_fund(
+        &creator,
+        &mut app,
+        &seilor_token,
+        &ve_seilor_token,
+        &test_contract_addr,
+    );
+    let boost = boost_contract_instance(&creator, &mut app, &ve_seilor_token);
+
+    let update_config_msg = ExecuteMsg::UpdateFundConfig {
+        update_config_msg: UpdateConfigMsg {
+            ve_seilor_addr: None,
+            seilor_addr: None,
+            kusd_denom: None,
+            kusd_reward_addr: None,
+            claim_able_time: None,
+            boost_addr: Some(boost.clone()),
+        },
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        test_contract_addr.clone(),
+        &update_config_msg,
+        &[],
+    );
+    assert!(res.is_ok());
+
+    let stake_amount = 100000000u128;
+    stake(
+        &creator,
+        &mut app,
+        &seilor_token,
+        &test_contract_addr,
+        &stake_amount,
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(300000000u64);
+        block.height += 300000000u64;
+    });
+
+    // lock 60% of the ve_seilor in boost
+    let lock_msg = boost::msg::ExecuteMsg::Lock {
+        index: 0,
+        amount: Uint128::from(60000000u128),
+    };
+    let res = app.execute_contract(creator.clone(), boost.clone(), &lock_msg, &[]);
+    assert!(res.is_ok());
+
+    // only the unlocked part can be unstaked
+    let unstake_msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(40000001u128),
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        test_contract_addr.clone(),
+        &unstake_msg,
+        &[],
+    );
+    assert!(res.is_err());
+    unstake(
+        &creator,
+        &mut app,
+        &test_contract_addr,
+        &Uint128::from(40000000u128),
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(2592000u64);
+        block.height += 2592000u64;
+    });
+
+    let res = app.execute_contract(
+        creator.clone(),
+        boost.clone(),
+        &boost::msg::ExecuteMsg::Unlock {},
+        &[],
+    );
+    assert!(res.is_ok());
+    unstake(
+        &creator,
+        &mut app,
+        &test_contract_addr,
+        &Uint128::from(60000000u128),
+    );
+
+    let query_res = get_ve_seilor_balance(&creator, &mut app, &ve_seilor_token);
+    assert_eq!(query_res.balance, Uint128::zero());
+}
+
 fn test_ve_fund_mint(
     creator: Addr,
     mut app: &mut App,
@@ -455,6 +566,27 @@ fn fund_contract_instance(
     test_contract_addr
 }
 
+fn boost_contract_instance(creator: &Addr, mut app: &mut App, ve_seilor_token: &Addr) -> Addr {
+    let boost_code_id = store_boost_contract(&mut app);
+    let boost_instance_msg = boost::msg::InstantiateMsg {
+        gov: None,
+        ve_seilor_addr: ve_seilor_token.clone(),
+        ve_seilor_lock_settings: vec![boost::state::VeSeilorLockSetting {
+            duration: Uint128::from(2592000u128),
+            mining_boost: Uint128::from(20000000u128),
+        }],
+    };
+    app.instantiate_contract(
+        boost_code_id,
+        creator.clone(),
+        &boost_instance_msg,
+        &[], // no funds
+        String::from("BOOST"),
+        None,
+    )
+    .unwrap()
+}
+
 fn ve_seilor_contract_instance(creator: &Addr, mut app: &mut App) -> Addr {
     let ve_seilor_code_id = store_ve_seilor_contract(&mut app);
     let ve_seilor_instance_msg: ve_seilor::msg::InstantiateMsg = mock_ve_seilor_instantiate_msg();
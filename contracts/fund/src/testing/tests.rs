@@ -51,6 +51,7 @@ fn test_update_fund_config() {
         kusd_denom: Option::from("new_kusd".to_string()),
         kusd_reward_addr: Option::from(Addr::unchecked("new_kusd_reward")),
         claim_able_time: Option::from(Uint64::from(20u64)),
+        boost_addr: Option::from(Addr::unchecked("boost")),
     };
     let info = mock_info("owner2", &[]);
     let res = update_fund_config(deps.as_mut(), env.clone(), info.clone(), update_msg.clone());
@@ -75,6 +76,7 @@ fn test_update_fund_config() {
             claim_able_time: Option::from(update_msg.claim_able_time.unwrap()).unwrap(),
             new_gov: None,
             token_cap: None,
+            boost_addr: Option::from(update_msg.boost_addr.unwrap()),
         }
     );
 }
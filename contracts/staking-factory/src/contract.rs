@@ -0,0 +1,95 @@
+use crate::error::ContractError;
+use crate::handler::{
+    accept_gov, create_pool, create_pool_reply, set_gov, set_pool_status, update_config,
+    CREATE_POOL_REPLY_ID,
+};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::querier::{query_all_pools, query_config, query_pool};
+use crate::state::{store_factory_config, FactoryConfig};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+};
+use cw2::set_contract_version;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "kryptonite.finance:staking-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let gov = msg.gov.unwrap_or_else(|| info.sender.clone());
+    deps.api.addr_validate(msg.rewards_token.as_str())?;
+    deps.api.addr_validate(msg.boost.as_str())?;
+    deps.api.addr_validate(msg.fund.as_str())?;
+    deps.api
+        .addr_validate(msg.reward_controller_addr.as_str())?;
+
+    let config = FactoryConfig {
+        gov,
+        staking_code_id: msg.staking_code_id,
+        staking_only_code_id: msg.staking_only_code_id,
+        rewards_token: msg.rewards_token,
+        boost: msg.boost,
+        fund: msg.fund,
+        reward_controller_addr: msg.reward_controller_addr,
+        new_gov: None,
+    };
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    store_factory_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "instantiate"),
+        ("owner", info.sender.as_str()),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig(msg) => update_config(deps, info, msg),
+        ExecuteMsg::CreatePool(msg) => create_pool(deps, env, info, msg),
+        ExecuteMsg::SetPoolStatus {
+            staking_token,
+            status,
+        } => set_pool_status(deps, info, staking_token, status),
+        ExecuteMsg::SetGov { gov } => set_gov(deps, info, gov),
+        ExecuteMsg::AcceptGov {} => accept_gov(deps, info),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        CREATE_POOL_REPLY_ID => create_pool_reply(deps, env, msg),
+        id => Err(ContractError::UnknownReplyId(id)),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Pool { staking_token } => to_binary(&query_pool(deps, staking_token)?),
+        QueryMsg::AllPools { start_after, limit } => {
+            to_binary(&query_all_pools(deps, start_after, limit)?)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
@@ -0,0 +1,24 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+    #[error("No New Gov")]
+    NoNewGov {},
+
+    #[error("Pool already exists for staking token {0}")]
+    PoolAlreadyExists(String),
+    #[error("Pool not found for staking token {0}")]
+    PoolNotFound(String),
+    #[error("Pool is deprecated")]
+    PoolDeprecated {},
+    #[error("Staking pools only support CW20 tokens")]
+    NativeTokenNotSupported {},
+    #[error("Unknown reply id {0}")]
+    UnknownReplyId(u64),
+}
@@ -0,0 +1,274 @@
+use crate::error::ContractError;
+use crate::msg::{CreatePoolMsg, PoolStatus, PoolType, UpdateConfigMsg};
+use crate::state::{
+    read_current_pool, read_factory_config, read_pending_pool, remove_pending_pool,
+    store_current_pool, store_factory_config, store_pending_pool, store_pool, PendingPool,
+    PoolInfo,
+};
+use common::asset::AssetInfo;
+use cosmwasm_std::{
+    attr, to_binary, Addr, DepsMut, Env, MessageInfo, Reply, Response, StdError, SubMsg, WasmMsg,
+};
+use cw_utils::parse_reply_instantiate_data;
+
+pub const CREATE_POOL_REPLY_ID: u64 = 1;
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    let mut config = read_factory_config(deps.storage)?;
+    if config.gov != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut attrs = vec![];
+    attrs.push(attr("action", "update_config"));
+
+    if let Some(staking_code_id) = msg.staking_code_id {
+        config.staking_code_id = staking_code_id;
+        attrs.push(attr("staking_code_id", staking_code_id.to_string()));
+    }
+    if let Some(staking_only_code_id) = msg.staking_only_code_id {
+        config.staking_only_code_id = staking_only_code_id;
+        attrs.push(attr(
+            "staking_only_code_id",
+            staking_only_code_id.to_string(),
+        ));
+    }
+    if let Some(rewards_token) = msg.rewards_token {
+        deps.api.addr_validate(rewards_token.as_str())?;
+        config.rewards_token = rewards_token.clone();
+        attrs.push(attr("rewards_token", rewards_token.to_string()));
+    }
+    if let Some(boost) = msg.boost {
+        deps.api.addr_validate(boost.as_str())?;
+        config.boost = boost.clone();
+        attrs.push(attr("boost", boost.to_string()));
+    }
+    if let Some(fund) = msg.fund {
+        deps.api.addr_validate(fund.as_str())?;
+        config.fund = fund.clone();
+        attrs.push(attr("fund", fund.to_string()));
+    }
+    if let Some(reward_controller_addr) = msg.reward_controller_addr {
+        deps.api.addr_validate(reward_controller_addr.as_str())?;
+        config.reward_controller_addr = reward_controller_addr.clone();
+        attrs.push(attr(
+            "reward_controller_addr",
+            reward_controller_addr.to_string(),
+        ));
+    }
+
+    store_factory_config(deps.storage, &config)?;
+
+    Ok(Response::default().add_attributes(attrs))
+}
+
+pub fn create_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CreatePoolMsg,
+) -> Result<Response, ContractError> {
+    let config = read_factory_config(deps.storage)?;
+    if config.gov != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let staking_token = msg.staking_token;
+    let rewards_token = msg.rewards_token.unwrap_or(AssetInfo::Token {
+        contract_addr: config.rewards_token.clone(),
+    });
+    for asset in [&staking_token, &rewards_token] {
+        if let AssetInfo::Token { contract_addr } = asset {
+            deps.api.addr_validate(contract_addr.as_str())?;
+        }
+    }
+    // a deprecated pool stays registered, the new one becomes the staking token's current pool
+    if let Some(pool) = read_current_pool(deps.storage, &staking_token)? {
+        if pool.status != PoolStatus::Deprecated {
+            return Err(ContractError::PoolAlreadyExists(staking_token.to_string()));
+        }
+    }
+
+    let (code_id, instantiate_msg, label) = match msg.pool_type {
+        PoolType::Staking => {
+            let (staking_addr, rewards_addr) = match (&staking_token, &rewards_token) {
+                (
+                    AssetInfo::Token {
+                        contract_addr: staking_addr,
+                    },
+                    AssetInfo::Token {
+                        contract_addr: rewards_addr,
+                    },
+                ) => (staking_addr.clone(), rewards_addr.clone()),
+                _ => return Err(ContractError::NativeTokenNotSupported {}),
+            };
+            (
+                config.staking_code_id,
+                to_binary(&staking::msg::InstantiateMsg {
+                    gov: Some(config.gov.clone()),
+                    staking_token: staking_addr,
+                    rewards_token: rewards_addr,
+                    boost: config.boost.clone(),
+                    fund: config.fund.clone(),
+                    reward_controller_addr: config.reward_controller_addr.clone(),
+                    duration: msg.duration,
+                    boost_mode: msg.boost_mode,
+                    unbonding_period: msg.unbonding_period,
+                    receipt_token: msg.receipt_token,
+                    factory: Some(env.contract.address.clone()),
+                })?,
+                format!("staking pool {}", staking_token),
+            )
+        }
+        PoolType::StakingOnly => (
+            config.staking_only_code_id,
+            to_binary(&staking_only::msg::InstantiateMsg {
+                gov: Some(config.gov.clone()),
+                staking_token: staking_token.clone(),
+                rewards_token: rewards_token.clone(),
+                reward_controller_addr: config.reward_controller_addr.clone(),
+                duration: msg.duration,
+                receipt_token: msg.receipt_token.map(|receipt_token| {
+                    staking_only::msg::ReceiptTokenMsg {
+                        code_id: receipt_token.code_id,
+                        name: receipt_token.name,
+                        symbol: receipt_token.symbol,
+                        decimals: receipt_token.decimals,
+                    }
+                }),
+                factory: Some(env.contract.address.clone()),
+            })?,
+            format!("staking-only pool {}", staking_token),
+        ),
+    };
+
+    store_pending_pool(
+        deps.storage,
+        &PendingPool {
+            pool_type: msg.pool_type,
+            staking_token: staking_token.clone(),
+            rewards_token: rewards_token.clone(),
+        },
+    )?;
+
+    let instantiate_sub_msg = SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: Some(config.gov.to_string()),
+            code_id,
+            msg: instantiate_msg,
+            funds: vec![],
+            label,
+        },
+        CREATE_POOL_REPLY_ID,
+    );
+    Ok(Response::new()
+        .add_submessage(instantiate_sub_msg)
+        .add_attributes(vec![
+            attr("action", "create_pool"),
+            attr("staking_token", staking_token.to_string()),
+            attr("rewards_token", rewards_token.to_string()),
+            attr("code_id", code_id.to_string()),
+            attr("created_at", env.block.time.seconds().to_string()),
+        ]))
+}
+
+// Registers the pool instantiated by create_pool as its staking token's current pool
+pub fn create_pool_reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|e| StdError::generic_err(e.to_string()))?;
+    let pool_addr = deps.api.addr_validate(&res.contract_address)?;
+    let pending_pool = read_pending_pool(deps.storage)?;
+    remove_pending_pool(deps.storage);
+
+    let pool = PoolInfo {
+        pool_addr: pool_addr.clone(),
+        pool_type: pending_pool.pool_type,
+        staking_token: pending_pool.staking_token,
+        rewards_token: pending_pool.rewards_token,
+        status: PoolStatus::Active,
+        created_at: env.block.time.seconds(),
+    };
+    store_pool(deps.storage, &pool)?;
+    store_current_pool(deps.storage, &pool.staking_token, &pool.pool_addr)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_pool"),
+        attr("staking_token", pool.staking_token.to_string()),
+        attr("pool_addr", pool_addr.to_string()),
+    ]))
+}
+
+// Shuts the pool down when it's deprecated, a deprecated pool can't be made active again
+pub fn set_pool_status(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking_token: AssetInfo,
+    status: PoolStatus,
+) -> Result<Response, ContractError> {
+    let config = read_factory_config(deps.storage)?;
+    if config.gov != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut pool = read_current_pool(deps.storage, &staking_token)?
+        .ok_or_else(|| ContractError::PoolNotFound(staking_token.to_string()))?;
+    if pool.status == PoolStatus::Deprecated {
+        return Err(ContractError::PoolDeprecated {});
+    }
+
+    let mut messages = vec![];
+    if status == PoolStatus::Deprecated {
+        let shutdown_msg = match pool.pool_type {
+            PoolType::Staking => to_binary(&staking::msg::ExecuteMsg::Shutdown {})?,
+            PoolType::StakingOnly => to_binary(&staking_only::msg::ExecuteMsg::Shutdown {})?,
+        };
+        messages.push(WasmMsg::Execute {
+            contract_addr: pool.pool_addr.to_string(),
+            msg: shutdown_msg,
+            funds: vec![],
+        });
+    }
+    pool.status = status;
+    store_pool(deps.storage, &pool)?;
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            attr("action", "set_pool_status"),
+            attr("staking_token", staking_token.to_string()),
+            attr("status", format!("{:?}", pool.status)),
+        ]))
+}
+
+pub fn set_gov(deps: DepsMut, info: MessageInfo, gov: Addr) -> Result<Response, ContractError> {
+    let mut config = read_factory_config(deps.storage)?;
+    if config.gov != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    deps.api.addr_validate(gov.clone().as_str())?;
+
+    config.new_gov = Some(gov.clone());
+    store_factory_config(deps.storage, &config)?;
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "set_gov"),
+        attr("gov", gov.to_string()),
+    ]))
+}
+
+pub fn accept_gov(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut config = read_factory_config(deps.storage)?;
+    if config.new_gov.is_none() {
+        return Err(ContractError::NoNewGov {});
+    }
+    if info.sender != config.new_gov.unwrap() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.gov = info.sender.clone();
+    config.new_gov = None;
+    store_factory_config(deps.storage, &config)?;
+    Ok(Response::default().add_attributes(vec![
+        attr("action", "accept_gov"),
+        attr("gov", config.gov.to_string()),
+    ]))
+}
@@ -0,0 +1,9 @@
+pub mod contract;
+pub mod error;
+mod handler;
+pub mod msg;
+mod querier;
+pub mod state;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,119 @@
+use common::asset::AssetInfo;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Uint128};
+use staking::msg::{BoostMode, ReceiptTokenMsg};
+
+/// Which contract a pool is instantiated from.
+#[cw_serde]
+pub enum PoolType {
+    /// `staking`, rewards are minted as veSEILOR through the fund and weighted by boost.
+    Staking,
+    /// `staking-only`, rewards are paid out in the rewards token.
+    StakingOnly,
+}
+
+#[cw_serde]
+pub enum PoolStatus {
+    Active,
+    /// Shut down, a new pool can be created for the staking token and this one stays listed.
+    Deprecated,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub gov: Option<Addr>,
+    pub staking_code_id: u64,
+    pub staking_only_code_id: u64,
+    pub rewards_token: Addr,
+    pub boost: Addr,
+    pub fund: Addr,
+    pub reward_controller_addr: Addr,
+}
+
+#[cw_serde]
+pub struct UpdateConfigMsg {
+    pub staking_code_id: Option<u64>,
+    pub staking_only_code_id: Option<u64>,
+    pub rewards_token: Option<Addr>,
+    pub boost: Option<Addr>,
+    pub fund: Option<Addr>,
+    pub reward_controller_addr: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct CreatePoolMsg {
+    pub pool_type: PoolType,
+    /// `staking` pools only take CW20 tokens.
+    pub staking_token: AssetInfo,
+    /// Defaults to the factory's rewards token.
+    pub rewards_token: Option<AssetInfo>,
+    pub duration: Uint128,
+    /// Only used by `staking` pools.
+    pub boost_mode: Option<BoostMode>,
+    /// Only used by `staking` pools.
+    pub unbonding_period: Option<Uint128>,
+    /// Receipt CW20 the pool instantiates and mints for stakes, none by default.
+    pub receipt_token: Option<ReceiptTokenMsg>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig(UpdateConfigMsg),
+    /// Instantiates a pool for a staking token that has no pool yet or a deprecated one, the factory's gov becomes its
+    /// gov and admin. A deprecated pool stays listed next to the new one.
+    CreatePool(CreatePoolMsg),
+    SetPoolStatus {
+        staking_token: AssetInfo,
+        status: PoolStatus,
+    },
+    SetGov {
+        gov: Addr,
+    },
+    AcceptGov {},
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// The latest pool created for the staking token.
+    #[returns(PoolResponse)]
+    Pool { staking_token: AssetInfo },
+    /// Every pool including deprecated ones, ordered by pool address.
+    #[returns(AllPoolsResponse)]
+    AllPools {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub gov: Addr,
+    pub staking_code_id: u64,
+    pub staking_only_code_id: u64,
+    pub rewards_token: Addr,
+    pub boost: Addr,
+    pub fund: Addr,
+    pub reward_controller_addr: Addr,
+    pub new_gov: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct PoolResponse {
+    pub pool_addr: Addr,
+    pub pool_type: PoolType,
+    pub staking_token: AssetInfo,
+    pub rewards_token: AssetInfo,
+    pub status: PoolStatus,
+    pub created_at: u64,
+}
+
+#[cw_serde]
+pub struct AllPoolsResponse {
+    pub pools: Vec<PoolResponse>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,47 @@
+use crate::msg::{AllPoolsResponse, ConfigResponse, PoolResponse};
+use crate::state::{read_current_pool, read_factory_config, read_pools, PoolInfo};
+use common::asset::AssetInfo;
+use cosmwasm_std::{Addr, Deps, StdError, StdResult};
+
+fn pool_response(pool: PoolInfo) -> PoolResponse {
+    PoolResponse {
+        pool_addr: pool.pool_addr,
+        pool_type: pool.pool_type,
+        staking_token: pool.staking_token,
+        rewards_token: pool.rewards_token,
+        status: pool.status,
+        created_at: pool.created_at,
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = read_factory_config(deps.storage)?;
+    Ok(ConfigResponse {
+        gov: config.gov,
+        staking_code_id: config.staking_code_id,
+        staking_only_code_id: config.staking_only_code_id,
+        rewards_token: config.rewards_token,
+        boost: config.boost,
+        fund: config.fund,
+        reward_controller_addr: config.reward_controller_addr,
+        new_gov: config.new_gov,
+    })
+}
+
+pub fn query_pool(deps: Deps, staking_token: AssetInfo) -> StdResult<PoolResponse> {
+    let pool = read_current_pool(deps.storage, &staking_token)?
+        .ok_or_else(|| StdError::generic_err("pool not found"))?;
+    Ok(pool_response(pool))
+}
+
+pub fn query_all_pools(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<AllPoolsResponse> {
+    let pools = read_pools(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(pool_response)
+        .collect();
+    Ok(AllPoolsResponse { pools })
+}
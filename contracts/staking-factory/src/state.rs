@@ -0,0 +1,116 @@
+use crate::msg::{PoolStatus, PoolType};
+use common::asset::AssetInfo;
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FactoryConfig {
+    pub gov: Addr,
+    pub staking_code_id: u64,
+    pub staking_only_code_id: u64,
+    // Defaults every new pool is instantiated with
+    pub rewards_token: Addr,
+    pub boost: Addr,
+    pub fund: Addr,
+    pub reward_controller_addr: Addr,
+    pub new_gov: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    pub pool_addr: Addr,
+    pub pool_type: PoolType,
+    pub staking_token: AssetInfo,
+    pub rewards_token: AssetInfo,
+    pub status: PoolStatus,
+    pub created_at: u64,
+}
+
+// A pool being instantiated, registered once the instantiate reply returns its address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingPool {
+    pub pool_type: PoolType,
+    pub staking_token: AssetInfo,
+    pub rewards_token: AssetInfo,
+}
+
+const FACTORY_CONFIG: Item<FactoryConfig> = Item::new("factory_config");
+const PENDING_POOL: Item<PendingPool> = Item::new("pending_pool");
+// Pool address => pool, deprecated pools stay listed
+const POOLS: Map<&Addr, PoolInfo> = Map::new("pools");
+// (asset kind, staking token address or denom) => the latest pool of the staking token
+const CURRENT_POOLS: Map<(&str, &str), Addr> = Map::new("current_pools");
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn store_factory_config(storage: &mut dyn Storage, config: &FactoryConfig) -> StdResult<()> {
+    FACTORY_CONFIG.save(storage, config)
+}
+
+pub fn read_factory_config(storage: &dyn Storage) -> StdResult<FactoryConfig> {
+    FACTORY_CONFIG.load(storage)
+}
+
+pub fn store_pending_pool(storage: &mut dyn Storage, pending_pool: &PendingPool) -> StdResult<()> {
+    PENDING_POOL.save(storage, pending_pool)
+}
+
+pub fn read_pending_pool(storage: &dyn Storage) -> StdResult<PendingPool> {
+    PENDING_POOL.load(storage)
+}
+
+pub fn remove_pending_pool(storage: &mut dyn Storage) {
+    PENDING_POOL.remove(storage)
+}
+
+// Keeps a CW20 address and a native denom with the same string apart
+fn asset_key(asset: &AssetInfo) -> (&str, &str) {
+    match asset {
+        AssetInfo::Token { contract_addr } => ("token", contract_addr.as_str()),
+        AssetInfo::NativeToken { denom } => ("native_token", denom.as_str()),
+    }
+}
+
+pub fn store_pool(storage: &mut dyn Storage, pool: &PoolInfo) -> StdResult<()> {
+    POOLS.save(storage, &pool.pool_addr, pool)
+}
+
+pub fn read_pool(storage: &dyn Storage, pool_addr: &Addr) -> StdResult<Option<PoolInfo>> {
+    POOLS.may_load(storage, pool_addr)
+}
+
+pub fn store_current_pool(
+    storage: &mut dyn Storage,
+    staking_token: &AssetInfo,
+    pool_addr: &Addr,
+) -> StdResult<()> {
+    CURRENT_POOLS.save(storage, asset_key(staking_token), pool_addr)
+}
+
+pub fn read_current_pool(
+    storage: &dyn Storage,
+    staking_token: &AssetInfo,
+) -> StdResult<Option<PoolInfo>> {
+    match CURRENT_POOLS.may_load(storage, asset_key(staking_token))? {
+        Some(pool_addr) => read_pool(storage, &pool_addr),
+        None => Ok(None),
+    }
+}
+
+pub fn read_pools(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PoolInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    POOLS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
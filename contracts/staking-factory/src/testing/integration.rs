@@ -0,0 +1,222 @@
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{
+    AllPoolsResponse, CreatePoolMsg, ExecuteMsg, PoolResponse, PoolStatus, PoolType, QueryMsg,
+};
+use crate::testing::mock_fn::{mock_instantiate_msg, CREATOR, REWARDS_TOKEN};
+use common::asset::AssetInfo;
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+fn store_staking_contract(app: &mut App) -> u64 {
+    let staking_contract = Box::new(ContractWrapper::new_with_empty(
+        staking::constract::execute,
+        staking::constract::instantiate,
+        staking::constract::query,
+    ));
+    app.store_code(staking_contract)
+}
+
+fn store_staking_only_contract(app: &mut App) -> u64 {
+    let staking_only_contract = Box::new(ContractWrapper::new_with_empty(
+        staking_only::constract::execute,
+        staking_only::constract::instantiate,
+        staking_only::constract::query,
+    ));
+    app.store_code(staking_only_contract)
+}
+
+fn factory_instance(creator: &Addr, app: &mut App) -> Addr {
+    let staking_code_id = store_staking_contract(app);
+    let staking_only_code_id = store_staking_only_contract(app);
+    let factory_contract =
+        Box::new(ContractWrapper::new_with_empty(execute, instantiate, query).with_reply(reply));
+    let factory_code_id = app.store_code(factory_contract);
+    app.instantiate_contract(
+        factory_code_id,
+        creator.clone(),
+        &mock_instantiate_msg(staking_code_id, staking_only_code_id),
+        &[],
+        String::from("Staking Factory"),
+        None,
+    )
+    .unwrap()
+}
+
+fn token_asset(contract_addr: &str) -> AssetInfo {
+    AssetInfo::Token {
+        contract_addr: Addr::unchecked(contract_addr),
+    }
+}
+
+fn create_pool(
+    app: &mut App,
+    sender: &Addr,
+    factory: &Addr,
+    pool_type: PoolType,
+    staking_token: &str,
+) -> bool {
+    app.execute_contract(
+        sender.clone(),
+        factory.clone(),
+        &ExecuteMsg::CreatePool(CreatePoolMsg {
+            pool_type,
+            staking_token: token_asset(staking_token),
+            rewards_token: None,
+            duration: Uint128::from(2592000u128),
+            boost_mode: None,
+            unbonding_period: Some(Uint128::from(604800u128)),
+            receipt_token: None,
+        }),
+        &[],
+    )
+    .is_ok()
+}
+
+fn all_pools(app: &mut App, factory: &Addr, start_after: Option<Addr>) -> Vec<PoolResponse> {
+    let res: AllPoolsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            factory.clone(),
+            &QueryMsg::AllPools {
+                start_after,
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    res.pools
+}
+
+#[test]
+fn test_create_pools_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let mut app = App::default();
+    let factory = factory_instance(&creator, &mut app);
+
+    let tom_address = Addr::unchecked("tom");
+    assert!(!create_pool(
+        &mut app,
+        &tom_address,
+        &factory,
+        PoolType::Staking,
+        "lp_token"
+    ));
+    assert!(create_pool(
+        &mut app,
+        &creator,
+        &factory,
+        PoolType::Staking,
+        "lp_token"
+    ));
+    assert!(create_pool(
+        &mut app,
+        &creator,
+        &factory,
+        PoolType::StakingOnly,
+        "seilor_token"
+    ));
+    // a staking token can only have one pool
+    assert!(!create_pool(
+        &mut app,
+        &creator,
+        &factory,
+        PoolType::StakingOnly,
+        "lp_token"
+    ));
+
+    // the pools are registered by staking token and governed by the factory's gov
+    let pool: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(
+            factory.clone(),
+            &QueryMsg::Pool {
+                staking_token: token_asset("lp_token"),
+            },
+        )
+        .unwrap();
+    assert_eq!(pool.pool_type, PoolType::Staking);
+    assert_eq!(pool.status, PoolStatus::Active);
+    assert_eq!(pool.rewards_token, token_asset(REWARDS_TOKEN));
+    let staking_config: staking::msg::StakingConfigResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pool.pool_addr.clone(),
+            &staking::msg::QueryMsg::QueryStakingConfig {},
+        )
+        .unwrap();
+    assert_eq!(staking_config.gov, creator);
+    assert_eq!(staking_config.staking_token, Addr::unchecked("lp_token"));
+    assert_eq!(staking_config.unbonding_period, Uint128::from(604800u128));
+    let contract_info = app
+        .wrap()
+        .query_wasm_contract_info(&pool.pool_addr)
+        .unwrap();
+    assert_eq!(contract_info.admin, Some(CREATOR.to_string()));
+
+    // pools are listed by pool address
+    let first_page = all_pools(&mut app, &factory, None);
+    assert_eq!(first_page.len(), 1);
+    assert_eq!(first_page[0].pool_addr, pool.pool_addr);
+    let second_page = all_pools(&mut app, &factory, Some(pool.pool_addr.clone()));
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].staking_token, token_asset("seilor_token"));
+    assert_eq!(second_page[0].pool_type, PoolType::StakingOnly);
+    assert!(all_pools(&mut app, &factory, Some(second_page[0].pool_addr.clone())).is_empty());
+
+    // deprecating a pool shuts it down through the factory, which instantiated it
+    for staking_token in ["lp_token", "seilor_token"] {
+        app.execute_contract(
+            creator.clone(),
+            factory.clone(),
+            &ExecuteMsg::SetPoolStatus {
+                staking_token: token_asset(staking_token),
+                status: PoolStatus::Deprecated,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    let staking_config: staking::msg::StakingConfigResponse = app
+        .wrap()
+        .query_wasm_smart(
+            pool.pool_addr.clone(),
+            &staking::msg::QueryMsg::QueryStakingConfig {},
+        )
+        .unwrap();
+    assert!(staking_config.shutdown);
+    let staking_only_config: staking_only::msg::StakingConfigResponse = app
+        .wrap()
+        .query_wasm_smart(
+            second_page[0].pool_addr.clone(),
+            &staking_only::msg::QueryMsg::QueryStakingConfig {},
+        )
+        .unwrap();
+    assert!(staking_only_config.shutdown);
+
+    // the staking token of a deprecated pool can get a new one
+    assert!(create_pool(
+        &mut app,
+        &creator,
+        &factory,
+        PoolType::Staking,
+        "lp_token"
+    ));
+    let new_pool: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(
+            factory.clone(),
+            &QueryMsg::Pool {
+                staking_token: token_asset("lp_token"),
+            },
+        )
+        .unwrap();
+    assert_ne!(new_pool.pool_addr, pool.pool_addr);
+    assert_eq!(new_pool.status, PoolStatus::Active);
+
+    // the deprecated pool is still listed for its stakers
+    let third_page = all_pools(&mut app, &factory, Some(second_page[0].pool_addr.clone()));
+    assert_eq!(third_page.len(), 1);
+    assert_eq!(third_page[0].pool_addr, new_pool.pool_addr);
+    let old_pool = all_pools(&mut app, &factory, None).remove(0);
+    assert_eq!(old_pool.pool_addr, pool.pool_addr);
+    assert_eq!(old_pool.status, PoolStatus::Deprecated);
+}
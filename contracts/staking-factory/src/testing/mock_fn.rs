@@ -0,0 +1,39 @@
+use crate::contract::instantiate;
+use crate::msg::InstantiateMsg;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{Addr, Env, MessageInfo, OwnedDeps, Response};
+
+pub const CREATOR: &str = "creator";
+pub const REWARDS_TOKEN: &str = "rewards_token";
+pub const BOOST: &str = "boost";
+pub const FUND: &str = "fund";
+pub const REWARD_CONTROLLER_ADDR: &str = "reward_controller_addr";
+
+pub fn mock_instantiate_msg(staking_code_id: u64, staking_only_code_id: u64) -> InstantiateMsg {
+    InstantiateMsg {
+        gov: None,
+        staking_code_id,
+        staking_only_code_id,
+        rewards_token: Addr::unchecked(REWARDS_TOKEN),
+        boost: Addr::unchecked(BOOST),
+        fund: Addr::unchecked(FUND),
+        reward_controller_addr: Addr::unchecked(REWARD_CONTROLLER_ADDR),
+    }
+}
+
+pub fn mock_instantiate(
+    msg: InstantiateMsg,
+) -> (
+    OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    Env,
+    MessageInfo,
+    Response,
+) {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let info = mock_info(CREATOR, &[]);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    (deps, env, info, res)
+}
@@ -0,0 +1,3 @@
+mod integration;
+mod mock_fn;
+mod tests;
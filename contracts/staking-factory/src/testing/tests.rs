@@ -0,0 +1,230 @@
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::handler::CREATE_POOL_REPLY_ID;
+use crate::msg::{CreatePoolMsg, ExecuteMsg, PoolStatus, PoolType};
+use crate::querier::{query_config, query_pool};
+use crate::state::{read_pending_pool, store_current_pool, store_pool, PoolInfo};
+use crate::testing::mock_fn::{mock_instantiate, mock_instantiate_msg, CREATOR, REWARDS_TOKEN};
+use common::asset::AssetInfo;
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{from_binary, to_binary, Addr, CosmosMsg, Uint128, WasmMsg};
+use staking::msg::ReceiptTokenMsg;
+
+fn token_asset(contract_addr: &str) -> AssetInfo {
+    AssetInfo::Token {
+        contract_addr: Addr::unchecked(contract_addr),
+    }
+}
+
+fn mock_create_pool_msg(pool_type: PoolType, staking_token: &str) -> CreatePoolMsg {
+    CreatePoolMsg {
+        pool_type,
+        staking_token: token_asset(staking_token),
+        rewards_token: None,
+        duration: Uint128::from(2592000u128),
+        boost_mode: None,
+        unbonding_period: None,
+        receipt_token: None,
+    }
+}
+
+#[test]
+fn test_instantiate() {
+    let (deps, _, _, _) = mock_instantiate(mock_instantiate_msg(1, 2));
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(config.gov, Addr::unchecked(CREATOR));
+    assert_eq!(config.staking_code_id, 1);
+    assert_eq!(config.staking_only_code_id, 2);
+    assert_eq!(config.new_gov, None);
+}
+
+#[test]
+fn test_create_pool() {
+    let (mut deps, env, info, _) = mock_instantiate(mock_instantiate_msg(1, 2));
+
+    let msg = ExecuteMsg::CreatePool(mock_create_pool_msg(PoolType::StakingOnly, "lp_token"));
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("tom", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, CREATE_POOL_REPLY_ID);
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate { admin, code_id, .. }) => {
+            assert_eq!(admin, &Some(CREATOR.to_string()));
+            assert_eq!(*code_id, 2);
+        }
+        _ => panic!("unexpected message"),
+    }
+    let pending_pool = read_pending_pool(&deps.storage).unwrap();
+    assert_eq!(pending_pool.staking_token, token_asset("lp_token"));
+    assert_eq!(pending_pool.rewards_token, token_asset(REWARDS_TOKEN));
+
+    // a staking token can only have one pool
+    store_pool(
+        &mut deps.storage,
+        &PoolInfo {
+            pool_addr: Addr::unchecked("pool"),
+            pool_type: PoolType::StakingOnly,
+            staking_token: token_asset("lp_token"),
+            rewards_token: token_asset(REWARDS_TOKEN),
+            status: PoolStatus::Active,
+            created_at: env.block.time.seconds(),
+        },
+    )
+    .unwrap();
+    store_current_pool(
+        &mut deps.storage,
+        &token_asset("lp_token"),
+        &Addr::unchecked("pool"),
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::PoolAlreadyExists("lp_token".to_string())
+    );
+
+    // staking-only pools take native denoms and a receipt token from the message, a denom doesn't
+    // clash with a CW20 address spelled the same
+    let receipt_token = ReceiptTokenMsg {
+        code_id: 3,
+        name: "Staked SEI".to_string(),
+        symbol: "SSEI".to_string(),
+        decimals: 6,
+    };
+    let mut create_pool_msg = mock_create_pool_msg(PoolType::StakingOnly, "");
+    create_pool_msg.staking_token = AssetInfo::NativeToken {
+        denom: "lp_token".to_string(),
+    };
+    create_pool_msg.receipt_token = Some(receipt_token.clone());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::CreatePool(create_pool_msg.clone()),
+    )
+    .unwrap();
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate { msg, .. }) => {
+            let instantiate_msg: staking_only::msg::InstantiateMsg = from_binary(msg).unwrap();
+            assert_eq!(instantiate_msg.staking_token, create_pool_msg.staking_token);
+            assert_eq!(instantiate_msg.rewards_token, token_asset(REWARDS_TOKEN));
+            assert_eq!(
+                instantiate_msg.receipt_token.unwrap().symbol,
+                receipt_token.symbol
+            );
+        }
+        _ => panic!("unexpected message"),
+    }
+
+    // staking pools only take CW20 tokens
+    create_pool_msg.pool_type = PoolType::Staking;
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::CreatePool(create_pool_msg),
+    );
+    assert_eq!(res.unwrap_err(), ContractError::NativeTokenNotSupported {});
+}
+
+#[test]
+fn test_set_pool_status() {
+    let (mut deps, env, info, _) = mock_instantiate(mock_instantiate_msg(1, 2));
+    let staking_token = token_asset("lp_token");
+    let set_status = |status: PoolStatus| ExecuteMsg::SetPoolStatus {
+        staking_token: staking_token.clone(),
+        status,
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        set_status(PoolStatus::Deprecated),
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::PoolNotFound("lp_token".to_string())
+    );
+
+    store_pool(
+        &mut deps.storage,
+        &PoolInfo {
+            pool_addr: Addr::unchecked("pool"),
+            pool_type: PoolType::Staking,
+            staking_token: staking_token.clone(),
+            rewards_token: token_asset(REWARDS_TOKEN),
+            status: PoolStatus::Active,
+            created_at: env.block.time.seconds(),
+        },
+    )
+    .unwrap();
+    store_current_pool(
+        &mut deps.storage,
+        &token_asset("lp_token"),
+        &Addr::unchecked("pool"),
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("tom", &[]),
+        set_status(PoolStatus::Deprecated),
+    );
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+    // staying active does nothing
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        set_status(PoolStatus::Active),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    // deprecating shuts the pool down
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        set_status(PoolStatus::Deprecated),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            assert_eq!(contract_addr, "pool");
+            assert_eq!(
+                msg,
+                &to_binary(&staking::msg::ExecuteMsg::Shutdown {}).unwrap()
+            );
+        }
+        _ => panic!("unexpected message"),
+    }
+    let pool = query_pool(deps.as_ref(), staking_token.clone()).unwrap();
+    assert_eq!(pool.status, PoolStatus::Deprecated);
+
+    // deprecated pools stay deprecated
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        set_status(PoolStatus::Active),
+    );
+    assert_eq!(res.unwrap_err(), ContractError::PoolDeprecated {});
+
+    // a new pool can be created for the staking token of a deprecated one
+    let msg = ExecuteMsg::CreatePool(mock_create_pool_msg(PoolType::Staking, "lp_token"));
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(res.messages[0].id, CREATE_POOL_REPLY_ID);
+}
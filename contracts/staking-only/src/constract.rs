@@ -1,20 +1,28 @@
 use crate::error::ContractError;
 use crate::handler::{
-    accept_gov, get_reward, receive_cw20, set_gov, update_staking_config, update_staking_duration,
-    withdraw,
+    accept_gov, get_reward, move_stake, notify_native_reward_amount, receipt_token_reply,
+    receive_cw20, recover_undistributed, rescue_tokens, seed_unclaimed, set_gov, set_incentivizer,
+    shutdown, stake_native, start_unclaimed_seed, update_staking_config, update_staking_duration,
+    withdraw, RECEIPT_TOKEN_REPLY_ID,
 };
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::msg::{AssetInfo, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::querier::{
     balance_of, earned, get_boost, get_user_reward_per_token_paid, get_user_updated_at,
-    last_time_reward_applicable, query_staking_config, query_staking_state, reward_per_token,
+    last_time_reward_applicable, query_incentivizers, query_pool_apr, query_projected_earnings,
+    query_reward_funder, query_staking_config, query_staking_state, reward_per_token,
+};
+use crate::state::{
+    migrate_staking_config, store_staking_config, store_staking_state, StakingConfig,
+    StakingState,
 };
-use crate::state::{store_staking_config, store_staking_state, StakingConfig, StakingState, read_staking_config};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, Uint256, QueryRequest, WasmQuery, CosmosMsg, WasmMsg, Addr};
-use cw20::{BalanceResponse, Cw20ExecuteMsg};
-use cw20::Cw20QueryMsg::Balance;
-use cw2::set_contract_version;
+use cosmwasm_std::{
+    attr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+    StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "kryptonite.finance:cw20-ve-seilor-staking-only";
@@ -34,12 +42,22 @@ pub fn instantiate(
     if msg.duration <= Uint128::zero() {
         return Err(ContractError::InvalidDuration {});
     }
+    for asset in [&msg.staking_token, &msg.rewards_token] {
+        if let AssetInfo::NativeToken { denom } = asset {
+            if denom.is_empty() {
+                return Err(ContractError::InvalidInput {});
+            }
+        }
+    }
     let staking_config = StakingConfig {
         gov,
         staking_token: msg.staking_token,
         rewards_token: msg.rewards_token,
         reward_controller_addr: msg.reward_controller_addr,
         new_gov: None,
+        receipt_token: None,
+        shutdown: false,
+        factory: msg.factory,
     };
 
     store_staking_config(deps.storage, &staking_config)?;
@@ -51,14 +69,35 @@ pub fn instantiate(
         reward_rate: Uint256::zero(),
         reward_per_token_stored: Uint128::zero(),
         total_supply: Uint128::zero(),
+        unclaimed: Uint128::zero(),
     };
 
     store_staking_state(deps.storage, &staking_state)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "instantiate"),
-        ("owner", info.sender.as_str()),
-    ]))
+    let mut sub_msgs = vec![];
+    if let Some(receipt_token) = msg.receipt_token {
+        sub_msgs.push(SubMsg::reply_on_success(
+            WasmMsg::Instantiate {
+                admin: Some(staking_config.gov.to_string()),
+                code_id: receipt_token.code_id,
+                msg: to_binary(&staking_receipt::msg::InstantiateMsg {
+                    name: receipt_token.name,
+                    symbol: receipt_token.symbol.clone(),
+                    decimals: receipt_token.decimals,
+                })?,
+                funds: vec![],
+                label: format!("staking receipt {}", receipt_token.symbol),
+            },
+            RECEIPT_TOKEN_REPLY_ID,
+        ));
+    }
+
+    Ok(Response::new()
+        .add_submessages(sub_msgs)
+        .add_attributes(vec![
+            ("action", "instantiate"),
+            ("owner", info.sender.as_str()),
+        ]))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -76,10 +115,33 @@ pub fn execute(
         ExecuteMsg::UpdateStakingState { duration } => {
             update_staking_duration(deps, env, info, duration)
         }
+        ExecuteMsg::Stake {} => stake_native(deps, env, info),
+        ExecuteMsg::NotifyRewardAmount {} => notify_native_reward_amount(deps, env, info),
+        ExecuteMsg::SetIncentivizer {
+            incentivizer,
+            allowed,
+        } => set_incentivizer(deps, info, incentivizer, allowed),
+        ExecuteMsg::RecoverUndistributed {} => recover_undistributed(deps, env, info),
+        ExecuteMsg::RescueTokens {
+            asset,
+            amount,
+            recipient,
+        } => rescue_tokens(deps, env, info, asset, amount, recipient),
+        ExecuteMsg::SeedUnclaimed { limit } => seed_unclaimed(deps, info, limit),
         ExecuteMsg::GetReward {} => get_reward(deps, env, info),
         ExecuteMsg::Withdraw { amount } => withdraw(deps, env, info, amount),
         ExecuteMsg::SetGov { gov } => set_gov(deps, info, gov),
         ExecuteMsg::AcceptGov {} => accept_gov(deps, info),
+        ExecuteMsg::Shutdown {} => shutdown(deps, env, info),
+        ExecuteMsg::MoveStake { from, to, amount } => move_stake(deps, env, info, from, to, amount),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        RECEIPT_TOKEN_REPLY_ID => receipt_token_reply(deps, msg),
+        id => Err(ContractError::UnknownReplyId(id)),
     }
 }
 
@@ -99,31 +161,46 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&get_user_reward_per_token_paid(deps, account)?)
         }
         QueryMsg::BalanceOf { account } => to_binary(&balance_of(deps, account)?),
+        QueryMsg::Incentivizers {} => to_binary(&query_incentivizers(deps)?),
+        QueryMsg::RewardFunder { funder } => to_binary(&query_reward_funder(deps, funder)?),
+        QueryMsg::PoolApr {} => to_binary(&query_pool_apr(deps, env)?),
+        QueryMsg::ProjectedEarnings { account, seconds } => {
+            to_binary(&query_projected_earnings(deps, env, account, seconds)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    let staking_config = read_staking_config(deps.storage)?;
-    let balanceRequest: BalanceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: staking_config.staking_token.to_string(),
-        msg: to_binary(&Balance {
-            address: env.contract.address.to_string(),
-        })?,
-    }))?;
-    if balanceRequest.balance.gt(&Uint128::zero()) {
-        let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: staking_config.staking_token.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: Addr::unchecked("sei1wl38643tpena47ec76zkjxyt55g3r2vsgwgaww").to_string(),
-                amount: balanceRequest.balance,
-            })?,
-            funds: vec![],
-        })];
-        return Ok(Response::new()
-            .add_messages(messages)
-            .add_attribute("action", "migrate"));
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = get_contract_version(deps.storage)?;
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Can't migrate from contract {}",
+            contract_version.contract
+        ))));
+    }
+    let stored_version = parse_version(&contract_version.version)?;
+    if stored_version > parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Can't migrate from version {} to older version {}",
+            contract_version.version, CONTRACT_VERSION
+        ))));
+    }
+
+    // before 0.2.0 the config held plain token addresses and the unclaimed rewards were not tracked
+    if stored_version < Version::new(0, 2, 0) {
+        migrate_staking_config(deps.storage)?;
+        start_unclaimed_seed(deps.branch(), &env)?;
     }
 
-    Ok(Response::default())
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "migrate"),
+        attr("from_version", contract_version.version),
+        attr("to_version", CONTRACT_VERSION),
+    ]))
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()))
 }
@@ -1,4 +1,6 @@
+use common::rescue::RescueError;
 use cosmwasm_std::{OverflowError, StdError};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,6 +11,9 @@ pub enum ContractError {
     #[error("{0}")]
     OverflowError(#[from] OverflowError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -18,4 +23,10 @@ pub enum ContractError {
     NoNewGov {},
     #[error("Invalid Duration")]
     InvalidDuration {},
+    #[error("Pool is shut down")]
+    PoolShutdown {},
+    #[error("Unknown reply id {0}")]
+    UnknownReplyId(u64),
+    #[error("{0}")]
+    Rescue(#[from] RescueError),
 }
@@ -1,19 +1,32 @@
 use crate::error::ContractError;
-use crate::helper::BASE_RATE_12;
-use crate::msg::{Cw20HookMsg, UpdateStakingConfigStruct};
-use crate::querier::{earned, is_empty_address, last_time_reward_applicable, reward_per_token};
+use crate::helper::{BASE_RATE_12, BASE_RATE_14, BASE_RATE_6};
+use crate::msg::{AssetInfo, Cw20HookMsg, UpdateStakingConfigStruct};
+use crate::querier::{
+    earned, get_boost, is_empty_address, last_time_reward_applicable, reserved_balance,
+    reward_per_token,
+};
 use crate::state::{
-    read_balance_of, read_rewards, read_staking_config, read_staking_state, store_balance_of,
-    store_rewards, store_staking_config, store_staking_state, store_user_reward_per_token_paid,
-    store_user_updated_at,
+    is_incentivizer, is_seeded_account, read_balance_of, read_incentivizers, read_reward_accounts,
+    read_reward_funder, read_reward_funders, read_rewards, read_staking_config, read_staking_state,
+    read_unclaimed_seed, read_user_reward_per_token_paid, remove_incentivizer,
+    remove_reward_funder, remove_unclaimed_seed, store_balance_of, store_incentivizer,
+    store_reward_funder, store_rewards, store_seeded_account, store_staking_config,
+    store_staking_state, store_unclaimed_seed, store_user_reward_per_token_paid,
+    store_user_updated_at, StakingConfig, StakingState, UnclaimedSeed, DEFAULT_LIMIT,
+    MAX_INCENTIVIZERS, MAX_LIMIT,
 };
 
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError,
-    SubMsg, Uint128, Uint256, WasmMsg,
+    attr, from_binary, to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
 };
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw20::Cw20ReceiveMsg;
+use cw_utils::{must_pay, parse_reply_instantiate_data};
+use staking_receipt::msg::ExecuteMsg as ReceiptExecuteMsg;
 use std::ops::Add;
+use std::str::FromStr;
+
+pub const RECEIPT_TOKEN_REPLY_ID: u64 = 1;
 
 pub fn update_staking_config(
     deps: DepsMut,
@@ -75,8 +88,115 @@ pub fn update_staking_duration(
     ]))
 }
 
+// Rewards emitted at `reward_rate` over `elapsed` seconds
+fn _emitted(reward_rate: Uint256, elapsed: Uint128) -> Result<Uint128, ContractError> {
+    let emitted = reward_rate.multiply_ratio(Uint256::from(elapsed), Uint256::from(BASE_RATE_12));
+    Ok(Uint128::from_str(&emitted.to_string())?)
+}
+
+// Credits the rewards emitted over `elapsed` seconds with nothing staked back to their funders
+fn _track_undistributed(
+    deps: DepsMut,
+    staking_state: &StakingState,
+    elapsed: Uint128,
+) -> Result<(), ContractError> {
+    let mut funded_rate = Uint256::zero();
+    for (funder, mut reward_funder) in read_reward_funders(deps.storage)? {
+        funded_rate += reward_funder.reward_rate;
+        reward_funder.undistributed += _emitted(reward_funder.reward_rate, elapsed)?;
+        store_reward_funder(deps.storage, &funder, &reward_funder)?;
+    }
+    // rewards notified before funders were tracked belong to the reward controller
+    let unfunded_rate = staking_state.reward_rate.saturating_sub(funded_rate);
+    if !unfunded_rate.is_zero() {
+        let reward_controller_addr = read_staking_config(deps.storage)?.reward_controller_addr;
+        let mut reward_funder = read_reward_funder(deps.storage, &reward_controller_addr);
+        reward_funder.undistributed += _emitted(unfunded_rate, elapsed)?;
+        store_reward_funder(deps.storage, &reward_controller_addr, &reward_funder)?;
+    }
+    Ok(())
+}
+
+// Starts tracking what stakers are owed on a pool upgraded from before it was tracked. What they
+// had earned by now joins unclaimed account by account, see _seed_account
+pub fn start_unclaimed_seed(mut deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+    // settles the pool up to now, the rewards of every staker are then earned as of now
+    _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.unclaimed = Uint128::zero();
+    store_staking_state(deps.storage, &staking_state)?;
+    store_unclaimed_seed(
+        deps.storage,
+        &UnclaimedSeed {
+            reward_per_token: staking_state.reward_per_token_stored,
+            last_account: None,
+        },
+    )?;
+    Ok(())
+}
+
+// Adds what the account had earned at the migration to unclaimed, once. Its rewards and paid
+// reward per token are still those of the migration until its first update, which seeds it
+fn _seed_account(deps: DepsMut, seed: &UnclaimedSeed, account: &Addr) -> StdResult<()> {
+    if is_seeded_account(deps.storage, account) {
+        return Ok(());
+    }
+    let balance_of = read_balance_of(deps.storage, account.clone());
+    let user_reward_per_token_paid = read_user_reward_per_token_paid(deps.storage, account.clone());
+    let boost = get_boost(deps.as_ref(), account.clone())?.boost;
+    let earned = balance_of
+        * boost
+        * seed
+            .reward_per_token
+            .saturating_sub(user_reward_per_token_paid)
+        / Uint128::new(BASE_RATE_14)
+        + read_rewards(deps.storage, account.clone());
+
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.unclaimed += earned;
+    store_staking_state(deps.storage, &staking_state)?;
+    store_seeded_account(deps.storage, account)
+}
+
+// Seeds unclaimed for the next `limit` accounts, the seed is done once they have all been walked
+pub fn seed_unclaimed(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut seed = read_unclaimed_seed(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("unclaimed rewards are already seeded"))?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let accounts = read_reward_accounts(deps.storage, seed.last_account.clone(), limit)?;
+    for account in accounts.iter() {
+        _seed_account(deps.branch(), &seed, account)?;
+    }
+
+    let done = accounts.len() < limit;
+    if done {
+        remove_unclaimed_seed(deps.storage);
+    } else {
+        seed.last_account = accounts.last().cloned();
+        store_unclaimed_seed(deps.storage, &seed)?;
+    }
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "seed_unclaimed"),
+        attr("seeded", accounts.len().to_string()),
+        attr("done", done.to_string()),
+    ]))
+}
+
 // Update user's claimable reward data and record the timestamp.
-fn _update_reward(deps: DepsMut, env: Env, account: Addr) -> Result<Response, ContractError> {
+fn _update_reward(mut deps: DepsMut, env: Env, account: Addr) -> Result<Response, ContractError> {
+    if !is_empty_address(account.as_str()) {
+        if let Some(seed) = read_unclaimed_seed(deps.storage)? {
+            _seed_account(deps.branch(), &seed, &account)?;
+        }
+    }
     let reward_per_token_response = reward_per_token(deps.as_ref(), env.clone())?;
     let reward_per_token_stored = reward_per_token_response.reward_per_token;
 
@@ -85,6 +205,15 @@ fn _update_reward(deps: DepsMut, env: Env, account: Addr) -> Result<Response, Co
     let updated_at = last_time_reward_applicable_response.last_time_reward_applicable;
 
     let mut staking_state = read_staking_state(deps.storage)?;
+    if staking_state.total_supply.is_zero() && updated_at > staking_state.updated_at {
+        let elapsed = updated_at - staking_state.updated_at;
+        _track_undistributed(deps.branch(), &staking_state, elapsed)?;
+    }
+    // rewards shared out since the last update, rounded down like each user's share of them
+    staking_state.unclaimed += staking_state.total_supply.multiply_ratio(
+        reward_per_token_stored - staking_state.reward_per_token_stored,
+        BASE_RATE_6,
+    );
     staking_state.reward_per_token_stored = reward_per_token_stored.clone();
     staking_state.updated_at = updated_at.clone();
     store_staking_state(deps.storage, &staking_state)?;
@@ -116,6 +245,9 @@ pub fn stake(
     user: Addr,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    if read_staking_config(deps.storage)?.shutdown {
+        return Err(ContractError::PoolShutdown {});
+    }
     _update_reward(deps.branch(), env.clone(), user.clone())?;
     if amount.is_zero() {
         return Err(ContractError::Std(StdError::generic_err("amount = 0")));
@@ -128,7 +260,15 @@ pub fn stake(
 
     store_balance_of(deps.storage, user.clone(), &balance_of.clone())?;
     store_staking_state(deps.storage, &staking_state)?;
-    Ok(Response::new().add_attributes(vec![
+
+    let messages = _receipt_msgs(
+        &read_staking_config(deps.storage)?,
+        ReceiptExecuteMsg::Mint {
+            recipient: user.to_string(),
+            amount,
+        },
+    )?;
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "stake"),
         attr("user", user.to_string()),
         attr("amount", amount.to_string()),
@@ -148,7 +288,7 @@ pub fn withdraw(
         return Err(ContractError::Std(StdError::generic_err("amount = 0")));
     }
 
-    let staking_token = read_staking_config(deps.storage)?.staking_token;
+    let staking_config = read_staking_config(deps.storage)?;
 
     let mut balance_of = read_balance_of(deps.storage, user.clone());
     let mut staking_state = read_staking_state(deps.storage)?;
@@ -158,14 +298,14 @@ pub fn withdraw(
     store_balance_of(deps.storage, user.clone(), &balance_of)?;
     store_staking_state(deps.storage, &staking_state)?;
 
-    let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: staking_token.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: user.clone().to_string(),
+    let mut messages: Vec<CosmosMsg> = _receipt_msgs(
+        &staking_config,
+        ReceiptExecuteMsg::Burn {
+            owner: user.to_string(),
             amount,
-        })?,
-        funds: vec![],
-    })];
+        },
+    )?;
+    messages.push(staking_config.staking_token.transfer_msg(&user, amount)?);
 
     Ok(Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "withdraw"),
@@ -197,7 +337,7 @@ pub fn receive_cw20(
     match from_binary(&cw20_msg.msg) {
         Ok(Cw20HookMsg::Stake {}) => {
             let staking_config = read_staking_config(deps.storage)?;
-            if contract_addr.ne(&staking_config.staking_token) {
+            if !staking_config.staking_token.is_token(&contract_addr) {
                 return Err(ContractError::Std(StdError::generic_err(
                     "not staking token",
                 )));
@@ -206,7 +346,7 @@ pub fn receive_cw20(
         }
         Ok(Cw20HookMsg::NotifyRewardAmount {}) => {
             let staking_config = read_staking_config(deps.storage)?;
-            if contract_addr.ne(&staking_config.rewards_token) {
+            if !staking_config.rewards_token.is_token(&contract_addr) {
                 return Err(ContractError::Std(StdError::generic_err(
                     "not rewards token",
                 )));
@@ -219,6 +359,34 @@ pub fn receive_cw20(
     }
 }
 
+// The amount of a native asset attached to the message, CW20 assets come in through receive_cw20
+fn _must_pay_native(info: &MessageInfo, asset: &AssetInfo) -> Result<Uint128, ContractError> {
+    match asset {
+        AssetInfo::NativeToken { denom } => Ok(must_pay(info, denom)?),
+        AssetInfo::Token { .. } => Err(ContractError::Std(StdError::generic_err(
+            "cw20 tokens must be sent with Receive",
+        ))),
+    }
+}
+
+// Stakes the native staking denom sent with the message
+pub fn stake_native(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let amount = _must_pay_native(&info, &staking_config.staking_token)?;
+    stake(deps, env, info.sender, amount)
+}
+
+// Notifies the native rewards denom sent with the message
+pub fn notify_native_reward_amount(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let amount = _must_pay_native(&info, &staking_config.rewards_token)?;
+    notify_reward_amount(deps, env, info.sender, amount)
+}
+
 // Allows users to claim their earned rewards
 pub fn get_reward(
     mut deps: DepsMut,
@@ -235,18 +403,12 @@ pub fn get_reward(
     let mut sub_msgs = vec![];
     if reward > Uint128::zero() {
         store_rewards(deps.storage, sender.clone(), &Uint128::zero())?;
+        let mut staking_state = read_staking_state(deps.storage)?;
+        staking_state.unclaimed = staking_state.unclaimed.saturating_sub(reward);
+        store_staking_state(deps.storage, &staking_state)?;
 
-        //Unified mint function for ve token, including reward updates
-        let transfer = cw20_base::msg::ExecuteMsg::Transfer {
-            recipient: sender.clone().to_string(),
-            amount: reward.clone(),
-        };
-
-        let transfer_reward_sub_msg = SubMsg::new(WasmMsg::Execute {
-            contract_addr: staking_config.rewards_token.clone().to_string(),
-            msg: to_binary(&transfer)?,
-            funds: vec![],
-        });
+        let transfer_reward_sub_msg =
+            SubMsg::new(staking_config.rewards_token.transfer_msg(&sender, reward)?);
         sub_msgs.push(transfer_reward_sub_msg);
     }
 
@@ -269,13 +431,25 @@ pub fn notify_reward_amount(
     _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
     if !amount.is_zero() {
         let staking_config = read_staking_config(deps.storage)?;
+        if staking_config.shutdown {
+            return Err(ContractError::PoolShutdown {});
+        }
 
-        if msg_sender != staking_config.reward_controller_addr {
+        if msg_sender != staking_config.reward_controller_addr
+            && !is_incentivizer(deps.storage, &msg_sender)
+        {
             return Err(ContractError::Unauthorized {});
         }
 
         let current_time = Uint128::from(env.block.time.seconds());
         let mut staking_state = read_staking_state(deps.storage)?;
+        _roll_reward_funders(
+            deps.branch(),
+            &staking_state,
+            current_time,
+            &msg_sender,
+            amount,
+        )?;
         if current_time >= staking_state.finish_at {
             // staking_state.reward_rate = amount / staking_state.duration;
             staking_state.reward_rate = Uint256::from(amount).multiply_ratio(
@@ -311,6 +485,241 @@ pub fn notify_reward_amount(
     ]))
 }
 
+// Rolls every funder's leftover into the new period like notify_reward_amount does with the total,
+// then adds the notified amount to the sender's share
+fn _roll_reward_funders(
+    deps: DepsMut,
+    staking_state: &StakingState,
+    current_time: Uint128,
+    msg_sender: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let remaining_time = staking_state.finish_at.saturating_sub(current_time);
+    for (funder, mut reward_funder) in read_reward_funders(deps.storage)? {
+        let remaining_rewards = _emitted(reward_funder.reward_rate, remaining_time)?;
+        reward_funder.reward_rate = Uint256::from(remaining_rewards).multiply_ratio(
+            Uint256::from(BASE_RATE_12),
+            Uint256::from(staking_state.duration),
+        );
+        if reward_funder == Default::default() {
+            remove_reward_funder(deps.storage, &funder);
+        } else {
+            store_reward_funder(deps.storage, &funder, &reward_funder)?;
+        }
+    }
+    let mut reward_funder = read_reward_funder(deps.storage, msg_sender);
+    reward_funder.reward_rate += Uint256::from(amount).multiply_ratio(
+        Uint256::from(BASE_RATE_12),
+        Uint256::from(staking_state.duration),
+    );
+    store_reward_funder(deps.storage, msg_sender, &reward_funder)?;
+    Ok(())
+}
+
+pub fn set_incentivizer(
+    deps: DepsMut,
+    info: MessageInfo,
+    incentivizer: Addr,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    deps.api.addr_validate(incentivizer.as_str())?;
+    if allowed {
+        if !is_incentivizer(deps.storage, &incentivizer)
+            && read_incentivizers(deps.storage)?.len() >= MAX_INCENTIVIZERS
+        {
+            return Err(ContractError::Std(StdError::generic_err(
+                "too many incentivizers",
+            )));
+        }
+        store_incentivizer(deps.storage, &incentivizer)?;
+    } else {
+        remove_incentivizer(deps.storage, &incentivizer);
+    }
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_incentivizer"),
+        attr("incentivizer", incentivizer.to_string()),
+        attr("allowed", allowed.to_string()),
+    ]))
+}
+
+// Pays the sender back its rewards emitted while nothing was staked
+pub fn recover_undistributed(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    _update_reward(deps.branch(), env, Addr::unchecked(""))?;
+    let funder = info.sender;
+    let mut reward_funder = read_reward_funder(deps.storage, &funder);
+    let amount = reward_funder.undistributed;
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "no undistributed rewards",
+        )));
+    }
+    reward_funder.undistributed = Uint128::zero();
+    if reward_funder == Default::default() {
+        remove_reward_funder(deps.storage, &funder);
+    } else {
+        store_reward_funder(deps.storage, &funder, &reward_funder)?;
+    }
+
+    let rewards_token = read_staking_config(deps.storage)?.rewards_token;
+    Ok(Response::new()
+        .add_message(rewards_token.transfer_msg(&funder, amount)?)
+        .add_attributes(vec![
+            attr("action", "recover_undistributed"),
+            attr("funder", funder.to_string()),
+            attr("amount", amount.to_string()),
+        ]))
+}
+
+// Rescues tokens sent to the pool by mistake, keeping back what is owed to stakers and funders.
+pub fn rescue_tokens(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    // unclaimed misses the rewards of the stakers not seeded yet
+    if read_unclaimed_seed(deps.storage)?.is_some() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "unclaimed rewards are still being seeded",
+        )));
+    }
+    // settles the rewards accrued so far into what is reserved for stakers and funders
+    _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
+    let reserved = reserved_balance(deps.as_ref(), &env, &asset)?;
+    Ok(common::rescue::rescue_tokens(
+        deps.as_ref(),
+        &env,
+        info.sender,
+        asset,
+        amount,
+        recipient,
+        reserved,
+    )?)
+}
+
+// Ends the reward stream now, each funder's share of the rest of the period becomes refundable
+// like rewards emitted with nothing staked. The pool can't be restarted
+pub fn shutdown(mut deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut staking_config = read_staking_config(deps.storage)?;
+    // the factory that instantiated the pool shuts it down when it's deprecated
+    if info.sender != staking_config.gov && Some(info.sender.clone()) != staking_config.factory {
+        return Err(ContractError::Unauthorized {});
+    }
+    if staking_config.shutdown {
+        return Err(ContractError::PoolShutdown {});
+    }
+    _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
+
+    let current_time = Uint128::from(env.block.time.seconds());
+    let mut staking_state = read_staking_state(deps.storage)?;
+    if staking_state.finish_at > current_time {
+        _track_undistributed(
+            deps.branch(),
+            &staking_state,
+            staking_state.finish_at - current_time,
+        )?;
+        staking_state.finish_at = current_time;
+    }
+    for (funder, mut reward_funder) in read_reward_funders(deps.storage)? {
+        reward_funder.reward_rate = Uint256::zero();
+        if reward_funder == Default::default() {
+            remove_reward_funder(deps.storage, &funder);
+        } else {
+            store_reward_funder(deps.storage, &funder, &reward_funder)?;
+        }
+    }
+    staking_state.reward_rate = Uint256::zero();
+    store_staking_state(deps.storage, &staking_state)?;
+
+    staking_config.shutdown = true;
+    store_staking_config(deps.storage, &staking_config)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "shutdown"),
+        attr("finish_at", staking_state.finish_at.to_string()),
+    ]))
+}
+
+// Mints or burns receipts, nothing to do when the pool has no receipt token
+fn _receipt_msgs(
+    staking_config: &StakingConfig,
+    msg: ReceiptExecuteMsg,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut messages = vec![];
+    if let Some(receipt_token) = &staking_config.receipt_token {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: receipt_token.to_string(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        }));
+    }
+    Ok(messages)
+}
+
+// Stores the receipt token instantiated along with the pool
+pub fn receipt_token_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|e| StdError::generic_err(e.to_string()))?;
+    let receipt_token = deps.api.addr_validate(&res.contract_address)?;
+    let mut staking_config = read_staking_config(deps.storage)?;
+    staking_config.receipt_token = Some(receipt_token.clone());
+    store_staking_config(deps.storage, &staking_config)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_receipt_token"),
+        attr("receipt_token", receipt_token.to_string()),
+    ]))
+}
+
+// Moves stake along with a receipt transfer, both sides are settled at their old balance
+pub fn move_stake(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: Addr,
+    to: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if staking_config.receipt_token != Some(info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if to == env.contract.address || to == info.sender {
+        return Err(ContractError::Std(StdError::generic_err(
+            "cannot move stake to the pool or its receipt token",
+        )));
+    }
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("amount = 0")));
+    }
+    _update_reward(deps.branch(), env.clone(), from.clone())?;
+    _update_reward(deps.branch(), env, to.clone())?;
+
+    let from_balance = read_balance_of(deps.storage, from.clone()).checked_sub(amount)?;
+    store_balance_of(deps.storage, from.clone(), &from_balance)?;
+    let to_balance = read_balance_of(deps.storage, to.clone()) + amount;
+    store_balance_of(deps.storage, to.clone(), &to_balance)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "move_stake"),
+        attr("from", from.to_string()),
+        attr("to", to.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
 pub fn set_gov(deps: DepsMut, info: MessageInfo, gov: Addr) -> Result<Response, ContractError> {
     let mut staking_config = read_staking_config(deps.storage)?;
     if staking_config.gov != info.sender {
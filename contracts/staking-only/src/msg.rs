@@ -1,3 +1,4 @@
+pub use common::asset::AssetInfo;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint256};
 use cw20::Cw20ReceiveMsg;
@@ -44,14 +45,37 @@ pub enum Cw20HookMsg {
     NotifyRewardAmount {},
 }
 
+#[cw_serde]
+pub struct IncentivizersResponse {
+    pub incentivizers: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RewardFunderResponse {
+    pub reward_rate: Uint256,
+    pub undistributed: Uint128,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub gov: Option<Addr>,
-    pub staking_token: Addr,
-    pub rewards_token: Addr,
+    pub staking_token: AssetInfo,
+    pub rewards_token: AssetInfo,
     pub reward_controller_addr: Addr,
 
     pub duration: Uint128,
+    /// Instantiates a receipt CW20 minted for stakes, transferring it moves the stake.
+    pub receipt_token: Option<ReceiptTokenMsg>,
+    /// The factory instantiating the pool, it can shut the pool down next to gov.
+    pub factory: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ReceiptTokenMsg {
+    pub code_id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
 }
 
 #[cw_serde]
@@ -64,6 +88,29 @@ pub enum ExecuteMsg {
     UpdateStakingState {
         duration: Uint128,
     },
+    /// Stakes the native staking denom attached to the message.
+    Stake {},
+    /// Notifies the native rewards denom attached to the message, reward controller or incentivizers only.
+    NotifyRewardAmount {},
+    /// Allows or disallows a third party to notify rewards, gov only.
+    SetIncentivizer {
+        incentivizer: Addr,
+        allowed: bool,
+    },
+    /// Pays the sender back the rewards it funded that were emitted while nothing was staked.
+    RecoverUndistributed {},
+    /// Sends tokens that ended up in the contract by mistake to `recipient`. Staked tokens, rewards
+    /// still owed to stakers and undistributed rewards can't be rescued.
+    RescueTokens {
+        asset: AssetInfo,
+        amount: Uint128,
+        recipient: Addr,
+    },
+    /// After a migration from before 0.2.0, adds what the next `limit` stakers had earned to the
+    /// unclaimed rewards, gov only.
+    SeedUnclaimed {
+        limit: Option<u32>,
+    },
     GetReward {},
     Withdraw {
         amount: Uint128,
@@ -72,6 +119,17 @@ pub enum ExecuteMsg {
         gov: Addr,
     },
     AcceptGov {},
+    /// Gov or the contract that instantiated the pool. Ends the reward stream now, the rest of the
+    /// period is refunded to its funders through `RecoverUndistributed`. Staking and
+    /// `NotifyRewardAmount` fail afterwards, withdrawing and claiming still work.
+    Shutdown {},
+    /// Only the receipt token. Moves stake along with a receipt transfer, settling the rewards of
+    /// both sides first.
+    MoveStake {
+        from: Addr,
+        to: Addr,
+        amount: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -95,15 +153,55 @@ pub enum QueryMsg {
     GetUserRewardPerTokenPaid { account: Addr },
     #[returns(BalanceOfResponse)]
     BalanceOf { account: Addr },
+    #[returns(IncentivizersResponse)]
+    Incentivizers {},
+    #[returns(RewardFunderResponse)]
+    RewardFunder { funder: Addr },
+    /// The rewards of the running period annualized per staked token.
+    #[returns(PoolAprResponse)]
+    PoolApr {},
+    /// The rewards the account earns over the next `seconds` at its current share, capped at
+    /// the end of the running period.
+    #[returns(ProjectedEarningsResponse)]
+    ProjectedEarnings { account: Addr, seconds: Uint128 },
+}
+
+#[cw_serde]
+pub struct PoolAprResponse {
+    /// Rewards emitted per second, without the 1e12 scale of `reward_rate` in the state.
+    pub reward_rate: Uint128,
+    /// Seconds until the running period finishes, 0 once it has.
+    pub remaining_seconds: Uint128,
+    /// Rewards left to emit in the running period.
+    pub remaining_rewards: Uint128,
+    pub total_supply: Uint128,
+    /// Yearly rewards per staked token, scaled by 1e18. 0 when nothing is staked or no period
+    /// is running.
+    pub apr: Uint128,
+}
+
+#[cw_serde]
+pub struct ProjectedEarningsResponse {
+    /// Rewards earned so far and not claimed yet.
+    pub earned: Uint128,
+    /// Rewards earned on top of `earned` over the projected seconds.
+    pub projected: Uint128,
+    /// Always 1x (1e8), staking-only has no boost.
+    pub boost: Uint128,
+    /// Yearly rewards per staked token of the account, scaled by 1e18.
+    pub apr: Uint128,
 }
 
 #[cw_serde]
 pub struct StakingConfigResponse {
     pub gov: Addr,
-    pub staking_token: Addr,
-    pub rewards_token: Addr,
+    pub staking_token: AssetInfo,
+    pub rewards_token: AssetInfo,
     pub reward_controller_addr: Addr,
     pub new_gov: Option<Addr>,
+    pub receipt_token: Option<Addr>,
+    pub shutdown: bool,
+    pub factory: Option<Addr>,
 }
 
 #[cw_serde]
@@ -1,12 +1,13 @@
-use crate::helper::{BASE_RATE_14, BASE_RATE_6, BASE_RATE_8};
+use crate::helper::{BASE_RATE_12, BASE_RATE_14, BASE_RATE_6, BASE_RATE_8, SECONDS_PER_YEAR};
 use crate::msg::{
-    BalanceOfResponse, EarnedResponse, GetBoostResponse, GetUserRewardPerTokenPaidResponse,
-    GetUserUpdatedAtResponse, LastTimeRewardApplicableResponse, RewardPerTokenResponse,
-    StakingConfigResponse, StakingStateResponse,
+    AssetInfo, BalanceOfResponse, EarnedResponse, GetBoostResponse,
+    GetUserRewardPerTokenPaidResponse, GetUserUpdatedAtResponse, IncentivizersResponse,
+    LastTimeRewardApplicableResponse, PoolAprResponse, ProjectedEarningsResponse,
+    RewardFunderResponse, RewardPerTokenResponse, StakingConfigResponse, StakingStateResponse,
 };
 use crate::state::{
-    read_balance_of, read_rewards, read_staking_config, read_staking_state,
-    read_user_reward_per_token_paid, read_user_updated_at,
+    read_balance_of, read_incentivizers, read_reward_funder, read_reward_funders, read_rewards,
+    read_staking_config, read_staking_state, read_user_reward_per_token_paid, read_user_updated_at,
 };
 use cosmwasm_std::{Addr, Deps, Env, StdResult, Uint128, Uint256};
 use std::str::FromStr;
@@ -69,6 +70,70 @@ pub fn earned(deps: Deps, env: Env, account: Addr) -> StdResult<EarnedResponse>
     Ok(EarnedResponse { earned })
 }
 
+pub fn query_pool_apr(deps: Deps, env: Env) -> StdResult<PoolAprResponse> {
+    let staking_state = read_staking_state(deps.storage)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let remaining_seconds = staking_state.finish_at.saturating_sub(current_time);
+    let remaining_rewards = staking_state.reward_rate.multiply_ratio(
+        Uint256::from(remaining_seconds),
+        Uint256::from(BASE_RATE_12),
+    );
+    // reward_rate carries 1e12, the apr 1e18
+    let apr = if remaining_seconds.is_zero() || staking_state.total_supply.is_zero() {
+        Uint256::zero()
+    } else {
+        staking_state.reward_rate.multiply_ratio(
+            Uint256::from(SECONDS_PER_YEAR * BASE_RATE_6),
+            Uint256::from(staking_state.total_supply),
+        )
+    };
+    Ok(PoolAprResponse {
+        reward_rate: Uint128::from_str(
+            &(staking_state.reward_rate / Uint256::from(BASE_RATE_12)).to_string(),
+        )?,
+        remaining_seconds,
+        remaining_rewards: Uint128::from_str(&remaining_rewards.to_string())?,
+        total_supply: staking_state.total_supply,
+        apr: Uint128::from_str(&apr.to_string())?,
+    })
+}
+
+pub fn query_projected_earnings(
+    deps: Deps,
+    env: Env,
+    account: Addr,
+    seconds: Uint128,
+) -> StdResult<ProjectedEarningsResponse> {
+    let staking_state = read_staking_state(deps.storage)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let remaining_seconds = staking_state.finish_at.saturating_sub(current_time);
+    let balance_of = read_balance_of(deps.storage, account.clone());
+
+    let projected = if staking_state.total_supply.is_zero() {
+        Uint256::zero()
+    } else {
+        staking_state
+            .reward_rate
+            .multiply_ratio(
+                Uint256::from(seconds.min(remaining_seconds)),
+                Uint256::from(BASE_RATE_12),
+            )
+            .multiply_ratio(balance_of, staking_state.total_supply)
+    };
+    // every staked token earns the same without boost
+    let apr = if balance_of.is_zero() {
+        Uint128::zero()
+    } else {
+        query_pool_apr(deps, env.clone())?.apr
+    };
+    Ok(ProjectedEarningsResponse {
+        earned: earned(deps, env, account.clone())?.earned,
+        projected: Uint128::from_str(&projected.to_string())?,
+        boost: get_boost(deps, account)?.boost,
+        apr,
+    })
+}
+
 pub fn is_empty_address(address: &str) -> bool {
     address.trim().is_empty()
 }
@@ -89,6 +154,9 @@ pub fn query_staking_config(deps: Deps) -> StdResult<StakingConfigResponse> {
         rewards_token: staking_config.rewards_token,
         reward_controller_addr: staking_config.reward_controller_addr,
         new_gov: staking_config.new_gov,
+        receipt_token: staking_config.receipt_token,
+        shutdown: staking_config.shutdown,
+        factory: staking_config.factory,
     })
 }
 
@@ -125,3 +193,45 @@ pub fn balance_of(deps: Deps, account: Addr) -> StdResult<BalanceOfResponse> {
     let balance_of = read_balance_of(deps.storage, account.clone());
     Ok(BalanceOfResponse { balance_of })
 }
+
+pub fn query_incentivizers(deps: Deps) -> StdResult<IncentivizersResponse> {
+    Ok(IncentivizersResponse {
+        incentivizers: read_incentivizers(deps.storage)?,
+    })
+}
+
+// The undistributed amount is as of the last update of the pool
+pub fn query_reward_funder(deps: Deps, funder: Addr) -> StdResult<RewardFunderResponse> {
+    let reward_funder = read_reward_funder(deps.storage, &funder);
+    Ok(RewardFunderResponse {
+        reward_rate: reward_funder.reward_rate,
+        undistributed: reward_funder.undistributed,
+    })
+}
+
+// The part of the contract's balance of the asset owed to stakers and funders: staked tokens,
+// rewards accrued and not claimed yet, the rest of the running period and the undistributed
+// rewards. Rewards must be settled up to now.
+pub fn reserved_balance(deps: Deps, env: &Env, asset: &AssetInfo) -> StdResult<Uint128> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let staking_state = read_staking_state(deps.storage)?;
+    let mut reserved = Uint128::zero();
+    if *asset == staking_config.staking_token {
+        reserved += staking_state.total_supply;
+    }
+    if *asset == staking_config.rewards_token {
+        reserved += staking_state.unclaimed;
+        let current_time = Uint128::from(env.block.time.seconds());
+        if staking_state.finish_at > current_time {
+            let remaining = staking_state.reward_rate.multiply_ratio(
+                Uint256::from(staking_state.finish_at - current_time),
+                Uint256::from(BASE_RATE_12),
+            );
+            reserved += Uint128::from_str(&remaining.to_string())?;
+        }
+        for (_, reward_funder) in read_reward_funders(deps.storage)? {
+            reserved += reward_funder.undistributed;
+        }
+    }
+    Ok(reserved)
+}
@@ -1,5 +1,6 @@
-use cosmwasm_std::{Addr, StdResult, Storage, Uint128, Uint256};
-use cw_storage_plus::{Item, Map};
+use crate::msg::AssetInfo;
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128, Uint256};
+use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -7,10 +8,29 @@ use serde::{Deserialize, Serialize};
 pub struct StakingConfig {
     pub gov: Addr,
     // Immutable variables for staking and rewards tokens
-    pub staking_token: Addr,
-    pub rewards_token: Addr,
+    pub staking_token: AssetInfo,
+    pub rewards_token: AssetInfo,
     pub reward_controller_addr: Addr,
     pub new_gov: Option<Addr>,
+    // Receipt CW20 minted for stakes, set once it has been instantiated
+    #[serde(default)]
+    pub receipt_token: Option<Addr>,
+    // Set by Shutdown, no stake or reward can be added afterwards
+    #[serde(default)]
+    pub shutdown: bool,
+    // Factory that instantiated the pool, it can shut the pool down next to gov
+    #[serde(default)]
+    pub factory: Option<Addr>,
+}
+
+// The config stored before native denoms were supported, both tokens were CW20s
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct LegacyStakingConfig {
+    gov: Addr,
+    staking_token: Addr,
+    rewards_token: Addr,
+    reward_controller_addr: Addr,
+    new_gov: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -27,16 +47,48 @@ pub struct StakingState {
     pub reward_per_token_stored: Uint128,
 
     pub total_supply: Uint128,
+    // Rewards accrued to stakers and not paid out yet, kept out of RescueTokens
+    #[serde(default)]
+    pub unclaimed: Uint128,
+}
+
+// A funder's part of the running reward period
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct RewardFunder {
+    // The funder's share of reward_rate
+    pub reward_rate: Uint256,
+    // Rewards of the funder emitted while nothing was staked, refundable
+    pub undistributed: Uint128,
 }
 
+// Seeding of the unclaimed rewards of a pool migrated from before 0.2.0, removed once every account is seeded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnclaimedSeed {
+    // reward_per_token_stored at the migration, what each account had earned by then joins unclaimed
+    pub reward_per_token: Uint128,
+    // Last account walked by SeedUnclaimed
+    pub last_account: Option<Addr>,
+}
+
+pub const MAX_INCENTIVIZERS: usize = 10;
+pub const MAX_LIMIT: u32 = 30;
+pub const DEFAULT_LIMIT: u32 = 10;
+
 const STAKING_CONFIG: Item<StakingConfig> = Item::new("staking_config");
+const LEGACY_STAKING_CONFIG: Item<LegacyStakingConfig> = Item::new("staking_config");
 const STAKING_STATE: Item<StakingState> = Item::new("staking_state");
 
 const USER_REWARD_PER_TOKEN_PAID: Map<Addr, Uint128> = Map::new("user_reward_per_token_paid");
 const REWARDS: Map<Addr, Uint128> = Map::new("rewards");
 const USER_UPDATED_AT: Map<Addr, Uint128> = Map::new("user_updated_at");
+// Third parties allowed to notify rewards besides the reward controller
+const INCENTIVIZERS: Map<&Addr, bool> = Map::new("incentivizers");
+const REWARD_FUNDERS: Map<&Addr, RewardFunder> = Map::new("reward_funders");
 // User address => staked amount
 const STAKING_USER_BALANCE_OF: Map<&Addr, Uint128> = Map::new("staking_user_balance_of");
+const UNCLAIMED_SEED: Item<UnclaimedSeed> = Item::new("unclaimed_seed");
+// Accounts already added to unclaimed while it is being seeded
+const SEEDED_ACCOUNTS: Map<&Addr, bool> = Map::new("seeded_accounts");
 
 pub fn store_user_reward_per_token_paid(
     storage: &mut dyn Storage,
@@ -60,6 +112,39 @@ pub fn read_rewards(storage: &dyn Storage, user: Addr) -> Uint128 {
     REWARDS.load(storage, user).unwrap_or(Uint128::zero())
 }
 
+// Accounts that ever had their rewards updated, walked by SeedUnclaimed
+pub fn read_reward_accounts(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: usize,
+) -> StdResult<Vec<Addr>> {
+    let start = start_after.map(Bound::exclusive);
+    REWARDS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn store_unclaimed_seed(storage: &mut dyn Storage, seed: &UnclaimedSeed) -> StdResult<()> {
+    UNCLAIMED_SEED.save(storage, seed)
+}
+
+pub fn read_unclaimed_seed(storage: &dyn Storage) -> StdResult<Option<UnclaimedSeed>> {
+    UNCLAIMED_SEED.may_load(storage)
+}
+
+pub fn remove_unclaimed_seed(storage: &mut dyn Storage) {
+    UNCLAIMED_SEED.remove(storage)
+}
+
+pub fn store_seeded_account(storage: &mut dyn Storage, account: &Addr) -> StdResult<()> {
+    SEEDED_ACCOUNTS.save(storage, account, &true)
+}
+
+pub fn is_seeded_account(storage: &dyn Storage, account: &Addr) -> bool {
+    SEEDED_ACCOUNTS.has(storage, account)
+}
+
 pub fn store_user_updated_at(
     storage: &mut dyn Storage,
     user: Addr,
@@ -93,6 +178,31 @@ pub fn read_staking_config(storage: &dyn Storage) -> StdResult<StakingConfig> {
     STAKING_CONFIG.load(storage)
 }
 
+// Rewrites a config stored with CW20 addresses as asset infos, does nothing if it already is
+pub fn migrate_staking_config(storage: &mut dyn Storage) -> StdResult<()> {
+    if STAKING_CONFIG.load(storage).is_ok() {
+        return Ok(());
+    }
+    let legacy = LEGACY_STAKING_CONFIG.load(storage)?;
+    STAKING_CONFIG.save(
+        storage,
+        &StakingConfig {
+            gov: legacy.gov,
+            staking_token: AssetInfo::Token {
+                contract_addr: legacy.staking_token,
+            },
+            rewards_token: AssetInfo::Token {
+                contract_addr: legacy.rewards_token,
+            },
+            reward_controller_addr: legacy.reward_controller_addr,
+            new_gov: legacy.new_gov,
+            receipt_token: None,
+            shutdown: false,
+            factory: None,
+        },
+    )
+}
+
 pub fn store_staking_state(storage: &mut dyn Storage, state: &StakingState) -> StdResult<()> {
     STAKING_STATE.save(storage, state)
 }
@@ -100,3 +210,43 @@ pub fn store_staking_state(storage: &mut dyn Storage, state: &StakingState) -> S
 pub fn read_staking_state(storage: &dyn Storage) -> StdResult<StakingState> {
     STAKING_STATE.load(storage)
 }
+
+pub fn store_incentivizer(storage: &mut dyn Storage, incentivizer: &Addr) -> StdResult<()> {
+    INCENTIVIZERS.save(storage, incentivizer, &true)
+}
+
+pub fn remove_incentivizer(storage: &mut dyn Storage, incentivizer: &Addr) {
+    INCENTIVIZERS.remove(storage, incentivizer)
+}
+
+pub fn is_incentivizer(storage: &dyn Storage, account: &Addr) -> bool {
+    INCENTIVIZERS.has(storage, account)
+}
+
+pub fn read_incentivizers(storage: &dyn Storage) -> StdResult<Vec<Addr>> {
+    INCENTIVIZERS
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn store_reward_funder(
+    storage: &mut dyn Storage,
+    funder: &Addr,
+    reward_funder: &RewardFunder,
+) -> StdResult<()> {
+    REWARD_FUNDERS.save(storage, funder, reward_funder)
+}
+
+pub fn remove_reward_funder(storage: &mut dyn Storage, funder: &Addr) {
+    REWARD_FUNDERS.remove(storage, funder)
+}
+
+pub fn read_reward_funder(storage: &dyn Storage, funder: &Addr) -> RewardFunder {
+    REWARD_FUNDERS.load(storage, funder).unwrap_or_default()
+}
+
+pub fn read_reward_funders(storage: &dyn Storage) -> StdResult<Vec<(Addr, RewardFunder)>> {
+    REWARD_FUNDERS
+        .range(storage, None, None, Order::Ascending)
+        .collect()
+}
@@ -1,16 +1,18 @@
-use crate::constract::{execute, instantiate, query};
+use crate::constract::{execute, instantiate, query, reply};
 use crate::msg::Cw20HookMsg::{NotifyRewardAmount, Stake};
 use crate::msg::{
-    BalanceOfResponse, EarnedResponse, ExecuteMsg, GetBoostResponse,
-    GetUserRewardPerTokenPaidResponse, GetUserUpdatedAtResponse, LastTimeRewardApplicableResponse,
-    QueryMsg, RewardPerTokenResponse, StakingConfigResponse, StakingStateResponse,
+    AssetInfo, BalanceOfResponse, EarnedResponse, ExecuteMsg, GetBoostResponse,
+    GetUserRewardPerTokenPaidResponse, GetUserUpdatedAtResponse, IncentivizersResponse,
+    InstantiateMsg, LastTimeRewardApplicableResponse, PoolAprResponse, ProjectedEarningsResponse,
+    QueryMsg, ReceiptTokenMsg, RewardFunderResponse, RewardPerTokenResponse, StakingConfigResponse,
+    StakingStateResponse,
 };
 use crate::testing::mock_fn::{mock_instantiate_msg, CREATOR, REWARD_CONTROLLER_ADDR};
 use crate::testing::mock_third_fn::{
     mock_reward_token_instance_msg, mock_staking_token_instance_msg,
 };
 use cosmwasm_std::testing::mock_env;
-use cosmwasm_std::{to_binary, Addr, Coin, Timestamp, Uint128};
+use cosmwasm_std::{coin, coins, to_binary, Addr, Coin, Timestamp, Uint128};
 use cw20::BalanceResponse;
 use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
 
@@ -327,6 +329,769 @@ fn test_integration() {
     println!("reward_controller_address balance: {:?}", balance);
 }
 
+fn native_staking_reward_instance(
+    creator: &Addr,
+    mut app: &mut App,
+    reward_controller_address: &Addr,
+) -> Addr {
+    let staking_reward_code_id = store_staking_reward_contract(&mut app);
+    app.instantiate_contract(
+        staking_reward_code_id,
+        creator.clone(),
+        &InstantiateMsg {
+            gov: None,
+            staking_token: AssetInfo::NativeToken {
+                denom: "usei".to_string(),
+            },
+            rewards_token: AssetInfo::NativeToken {
+                denom: "ukusd".to_string(),
+            },
+            reward_controller_addr: reward_controller_address.clone(),
+            duration: Uint128::from(2592000u128),
+            receipt_token: None,
+            factory: None,
+        },
+        &[],
+        String::from("Staking"),
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_native_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let reward_controller_address = Addr::unchecked(REWARD_CONTROLLER_ADDR);
+    let tom_address = Addr::unchecked("tom".to_string());
+    let block_time = 10000000u64;
+    let mut app = mock_app(
+        creator.clone(),
+        vec![coin(10000000, "usei"), coin(10000000, "ukusd")],
+        Some(block_time.clone()),
+    );
+    app.send_tokens(
+        creator.clone(),
+        tom_address.clone(),
+        &coins(1000000, "usei"),
+    )
+    .unwrap();
+    app.send_tokens(
+        creator.clone(),
+        reward_controller_address.clone(),
+        &coins(1000000, "ukusd"),
+    )
+    .unwrap();
+
+    let staking_reward =
+        native_staking_reward_instance(&creator, &mut app, &reward_controller_address);
+    let staking_config = query_staking_config(&mut app, &staking_reward);
+    assert_eq!(
+        staking_config.staking_token,
+        AssetInfo::NativeToken {
+            denom: "usei".to_string()
+        }
+    );
+
+    // only the reward controller can notify, and only with the rewards denom
+    let res = app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(1000000, "usei"),
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(1000000, "ukusd"),
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(1000000, "ukusd"),
+    );
+    assert!(res.is_ok());
+
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(1000000, "usei"),
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &tom_address),
+        Uint128::from(1000000u128)
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(1296000u64);
+        block.height += 100000u64;
+    });
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    assert!(tom_earned > Uint128::zero());
+    get_reward(&tom_address, &mut app, &staking_reward);
+    assert_eq!(
+        app.wrap()
+            .query_balance(&tom_address, "ukusd")
+            .unwrap()
+            .amount,
+        tom_earned
+    );
+
+    withdraw(
+        &tom_address,
+        &mut app,
+        &staking_reward,
+        &Uint128::from(1000000u128),
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(&tom_address, "usei")
+            .unwrap()
+            .amount,
+        Uint128::from(1000000u128)
+    );
+}
+
+#[test]
+fn test_shutdown_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let gov = Addr::unchecked("gov");
+    let reward_controller_address = Addr::unchecked(REWARD_CONTROLLER_ADDR);
+    let tom_address = Addr::unchecked("tom".to_string());
+    let mut app = mock_app(
+        creator.clone(),
+        vec![coin(10000000, "usei"), coin(10000000, "ukusd")],
+        Some(10000000u64),
+    );
+    app.send_tokens(
+        creator.clone(),
+        tom_address.clone(),
+        &coins(1000000, "usei"),
+    )
+    .unwrap();
+    app.send_tokens(
+        creator.clone(),
+        reward_controller_address.clone(),
+        &coins(2592000, "ukusd"),
+    )
+    .unwrap();
+
+    // instantiated by the creator on behalf of gov and the factory, like the staking factory does
+    let factory = Addr::unchecked("factory");
+    let staking_reward_code_id = store_staking_reward_contract(&mut app);
+    let staking_reward = app
+        .instantiate_contract(
+            staking_reward_code_id,
+            creator.clone(),
+            &InstantiateMsg {
+                gov: Some(gov.clone()),
+                staking_token: AssetInfo::NativeToken {
+                    denom: "usei".to_string(),
+                },
+                rewards_token: AssetInfo::NativeToken {
+                    denom: "ukusd".to_string(),
+                },
+                reward_controller_addr: reward_controller_address.clone(),
+                duration: Uint128::from(2592000u128),
+                receipt_token: None,
+                factory: Some(factory.clone()),
+            },
+            &[],
+            String::from("Staking"),
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(2592000, "ukusd"),
+    )
+    .unwrap();
+    app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(1000000, "usei"),
+    )
+    .unwrap();
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(1296000u64);
+        block.height += 100000u64;
+    });
+
+    // the deployer is neither gov nor the factory
+    for sender in [&tom_address, &creator] {
+        let res = app.execute_contract(
+            sender.clone(),
+            staking_reward.clone(),
+            &ExecuteMsg::Shutdown {},
+            &[],
+        );
+        assert!(res.is_err());
+    }
+    app.execute_contract(
+        factory,
+        staking_reward.clone(),
+        &ExecuteMsg::Shutdown {},
+        &[],
+    )
+    .unwrap();
+    assert!(query_staking_config(&mut app, &staking_reward).shutdown);
+    let res = app.execute_contract(gov, staking_reward.clone(), &ExecuteMsg::Shutdown {}, &[]);
+    assert!(res.is_err());
+
+    // nothing is emitted after the shutdown, the rest of the period goes back to its funder
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    assert_eq!(tom_earned, Uint128::from(1296000u128));
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(1000u64);
+        block.height += 100u64;
+    });
+    assert_eq!(earned(&mut app, &staking_reward, &tom_address), tom_earned);
+    app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::RecoverUndistributed {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance(&reward_controller_address, "ukusd")
+            .unwrap()
+            .amount,
+        Uint128::from(1296000u128)
+    );
+
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(1000, "usei"),
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        reward_controller_address,
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(1000, "ukusd"),
+    );
+    assert!(res.is_err());
+
+    get_reward(&tom_address, &mut app, &staking_reward);
+    withdraw(
+        &tom_address,
+        &mut app,
+        &staking_reward,
+        &Uint128::from(1000000u128),
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(&tom_address, "usei")
+            .unwrap()
+            .amount,
+        Uint128::from(1000000u128)
+    );
+}
+
+#[test]
+fn test_rescue_tokens_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let reward_controller_address = Addr::unchecked(REWARD_CONTROLLER_ADDR);
+    let tom_address = Addr::unchecked("tom".to_string());
+    let recipient = Addr::unchecked("recipient".to_string());
+    let block_time = 10000000u64;
+    let mut app = mock_app(
+        creator.clone(),
+        vec![coin(10000000, "usei"), coin(10000000, "ukusd")],
+        Some(block_time.clone()),
+    );
+    app.send_tokens(
+        creator.clone(),
+        tom_address.clone(),
+        &coins(1000000, "usei"),
+    )
+    .unwrap();
+    app.send_tokens(
+        creator.clone(),
+        reward_controller_address.clone(),
+        &coins(1000000, "ukusd"),
+    )
+    .unwrap();
+    let staking_reward =
+        native_staking_reward_instance(&creator, &mut app, &reward_controller_address);
+
+    // half of the period passes with nothing staked, those rewards go back to the controller
+    let res = app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(1000000, "ukusd"),
+    );
+    assert!(res.is_ok());
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(1296000u64);
+        block.height += 100000u64;
+    });
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(1000000, "usei"),
+    );
+    assert!(res.is_ok());
+    // tokens sent to the pool by mistake
+    app.send_tokens(
+        creator.clone(),
+        staking_reward.clone(),
+        &[coin(1000, "usei"), coin(1000, "ukusd")],
+    )
+    .unwrap();
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(1296000u64);
+        block.height += 100000u64;
+    });
+
+    let rescue_msg = |denom: &str, amount: u128| ExecuteMsg::RescueTokens {
+        asset: AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        },
+        amount: Uint128::from(amount),
+        recipient: recipient.clone(),
+    };
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &rescue_msg("usei", 1000),
+        &[],
+    );
+    assert!(res.is_err());
+    // the staked tokens, the earned rewards and the undistributed rewards are reserved
+    for (denom, amount) in [("usei", 1001), ("ukusd", 1100)] {
+        let res = app.execute_contract(
+            creator.clone(),
+            staking_reward.clone(),
+            &rescue_msg(denom, amount),
+            &[],
+        );
+        assert!(res.is_err());
+    }
+    for denom in ["usei", "ukusd"] {
+        let res = app.execute_contract(
+            creator.clone(),
+            staking_reward.clone(),
+            &rescue_msg(denom, 1000),
+            &[],
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            app.wrap().query_balance(&recipient, denom).unwrap().amount,
+            Uint128::from(1000u128)
+        );
+    }
+
+    get_reward(&tom_address, &mut app, &staking_reward);
+    let res = app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::RecoverUndistributed {},
+        &[],
+    );
+    assert!(res.is_ok());
+    let left = app
+        .wrap()
+        .query_balance(&staking_reward, "ukusd")
+        .unwrap()
+        .amount;
+    assert!(left < Uint128::from(100u128));
+    assert_eq!(
+        app.wrap()
+            .query_balance(&staking_reward, "usei")
+            .unwrap()
+            .amount,
+        Uint128::from(1000000u128)
+    );
+}
+
+#[test]
+fn test_incentivizer_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let reward_controller_address = Addr::unchecked(REWARD_CONTROLLER_ADDR);
+    let alice_address = Addr::unchecked("alice".to_string());
+    let tom_address = Addr::unchecked("tom".to_string());
+    let block_time = 10000000u64;
+    let mut app = mock_app(
+        creator.clone(),
+        vec![coin(10000000, "usei"), coin(10000000, "ukusd")],
+        Some(block_time.clone()),
+    );
+    app.send_tokens(
+        creator.clone(),
+        tom_address.clone(),
+        &coins(1000000, "usei"),
+    )
+    .unwrap();
+    app.send_tokens(
+        creator.clone(),
+        alice_address.clone(),
+        &coins(2592000, "ukusd"),
+    )
+    .unwrap();
+    let staking_reward =
+        native_staking_reward_instance(&creator, &mut app, &reward_controller_address);
+
+    // only gov can whitelist, and only whitelisted incentivizers can notify
+    let notify_msg = ExecuteMsg::NotifyRewardAmount {};
+    let res = app.execute_contract(
+        alice_address.clone(),
+        staking_reward.clone(),
+        &notify_msg,
+        &coins(2592000, "ukusd"),
+    );
+    assert!(res.is_err());
+    let set_incentivizer_msg = ExecuteMsg::SetIncentivizer {
+        incentivizer: alice_address.clone(),
+        allowed: true,
+    };
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &set_incentivizer_msg,
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &set_incentivizer_msg,
+        &[],
+    );
+    assert!(res.is_ok());
+    let res: IncentivizersResponse = app
+        .wrap()
+        .query_wasm_smart(staking_reward.clone(), &QueryMsg::Incentivizers {})
+        .unwrap();
+    assert_eq!(res.incentivizers, vec![alice_address.clone()]);
+    let res = app.execute_contract(
+        alice_address.clone(),
+        staking_reward.clone(),
+        &notify_msg,
+        &coins(2592000, "ukusd"),
+    );
+    assert!(res.is_ok());
+
+    // nothing is staked for the first 1000 seconds, those rewards go back to alice
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(1000u64);
+        block.height += 100000u64;
+    });
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(1000000, "usei"),
+    );
+    assert!(res.is_ok());
+    let reward_funder: RewardFunderResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking_reward.clone(),
+            &QueryMsg::RewardFunder {
+                funder: alice_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(reward_funder.undistributed, Uint128::from(1000u128));
+    let recover_msg = ExecuteMsg::RecoverUndistributed {};
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &recover_msg,
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        alice_address.clone(),
+        staking_reward.clone(),
+        &recover_msg,
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        app.wrap()
+            .query_balance(&alice_address, "ukusd")
+            .unwrap()
+            .amount,
+        Uint128::from(1000u128)
+    );
+
+    // tom gets the rest of the period
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(2592000u64);
+        block.height += 100000u64;
+    });
+    get_reward(&tom_address, &mut app, &staking_reward);
+    let tom_rewards = app
+        .wrap()
+        .query_balance(&tom_address, "ukusd")
+        .unwrap()
+        .amount;
+    assert!(tom_rewards <= Uint128::from(2591000u128));
+    assert!(tom_rewards >= Uint128::from(2590990u128));
+}
+
+#[test]
+fn test_receipt_token_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let reward_controller_address = Addr::unchecked(REWARD_CONTROLLER_ADDR);
+    let tom_address = Addr::unchecked("tom".to_string());
+    let jerry_address = Addr::unchecked("jerry".to_string());
+    let block_time = 10000000u64;
+    let mut app = mock_app(
+        creator.clone(),
+        vec![coin(10000000, "usei"), coin(10000000, "ukusd")],
+        Some(block_time.clone()),
+    );
+    app.send_tokens(
+        creator.clone(),
+        tom_address.clone(),
+        &coins(1000000, "usei"),
+    )
+    .unwrap();
+    app.send_tokens(
+        creator.clone(),
+        reward_controller_address.clone(),
+        &coins(1000000, "ukusd"),
+    )
+    .unwrap();
+
+    let receipt_code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+        staking_receipt::contract::execute,
+        staking_receipt::contract::instantiate,
+        staking_receipt::contract::query,
+    )));
+    let staking_reward_code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(execute, instantiate, query).with_reply(reply),
+    ));
+    let staking_reward = app
+        .instantiate_contract(
+            staking_reward_code_id,
+            creator.clone(),
+            &InstantiateMsg {
+                gov: None,
+                staking_token: AssetInfo::NativeToken {
+                    denom: "usei".to_string(),
+                },
+                rewards_token: AssetInfo::NativeToken {
+                    denom: "ukusd".to_string(),
+                },
+                reward_controller_addr: reward_controller_address.clone(),
+                duration: Uint128::from(2592000u128),
+                receipt_token: Some(ReceiptTokenMsg {
+                    code_id: receipt_code_id,
+                    name: "Staked SEI".to_string(),
+                    symbol: "sSEI".to_string(),
+                    decimals: 6,
+                }),
+                factory: None,
+            },
+            &[],
+            String::from("Staking"),
+            None,
+        )
+        .unwrap();
+    let receipt_token = query_staking_config(&mut app, &staking_reward)
+        .receipt_token
+        .unwrap();
+
+    let res = app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(1000000, "ukusd"),
+    );
+    assert!(res.is_ok());
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(1000000, "usei"),
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &receipt_token, &tom_address).balance,
+        Uint128::from(1000000u128)
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+
+    // transferring receipts moves the stake, rewards accrued so far stay with tom
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    assert!(tom_earned > Uint128::zero());
+    cw20_transfer(
+        &mut app,
+        &receipt_token,
+        &tom_address,
+        &jerry_address,
+        Uint128::from(400000u128),
+    );
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &tom_address),
+        Uint128::from(600000u128)
+    );
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &jerry_address),
+        Uint128::from(400000u128)
+    );
+    assert_eq!(earned(&mut app, &staking_reward, &tom_address), tom_earned);
+    assert_eq!(
+        earned(&mut app, &staking_reward, &jerry_address),
+        Uint128::zero()
+    );
+
+    // only the receipt token can move stake
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::MoveStake {
+            from: tom_address.clone(),
+            to: jerry_address.clone(),
+            amount: Uint128::one(),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+    assert!(earned(&mut app, &staking_reward, &jerry_address) > Uint128::zero());
+
+    // withdrawing burns the receipts
+    withdraw(
+        &jerry_address,
+        &mut app,
+        &staking_reward,
+        &Uint128::from(400000u128),
+    );
+    assert_eq!(
+        cw20_balance(&mut app, &receipt_token, &jerry_address).balance,
+        Uint128::zero()
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(&jerry_address, "usei")
+            .unwrap()
+            .amount,
+        Uint128::from(400000u128)
+    );
+}
+
+#[test]
+fn test_pool_apr_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let reward_controller_address = Addr::unchecked(REWARD_CONTROLLER_ADDR);
+    let tom_address = Addr::unchecked("tom".to_string());
+    let block_time = 10000000u64;
+    let mut app = mock_app(
+        creator.clone(),
+        vec![coin(10000000, "usei"), coin(10000000, "ukusd")],
+        Some(block_time.clone()),
+    );
+    app.send_tokens(
+        creator.clone(),
+        tom_address.clone(),
+        &coins(1000000, "usei"),
+    )
+    .unwrap();
+    app.send_tokens(
+        creator.clone(),
+        reward_controller_address.clone(),
+        &coins(2592000, "ukusd"),
+    )
+    .unwrap();
+    let staking_reward =
+        native_staking_reward_instance(&creator, &mut app, &reward_controller_address);
+
+    let res = app.execute_contract(
+        reward_controller_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::NotifyRewardAmount {},
+        &coins(2592000, "ukusd"),
+    );
+    assert!(res.is_ok());
+    // nothing staked yet
+    assert_eq!(pool_apr(&mut app, &staking_reward).apr, Uint128::zero());
+
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Stake {},
+        &coins(1000000, "usei"),
+    );
+    assert!(res.is_ok());
+
+    // 1 ukusd per second for 1000000 usei staked
+    let apr_response = pool_apr(&mut app, &staking_reward);
+    assert_eq!(apr_response.reward_rate, Uint128::one());
+    assert_eq!(apr_response.remaining_seconds, Uint128::from(2592000u128));
+    assert_eq!(apr_response.remaining_rewards, Uint128::from(2592000u128));
+    assert_eq!(apr_response.apr, Uint128::from(31536000000000000000u128));
+
+    let elapsed = 100000u64;
+    let projection = projected_earnings(
+        &mut app,
+        &staking_reward,
+        &tom_address,
+        Uint128::from(elapsed),
+    );
+    assert_eq!(projection.earned, Uint128::zero());
+    assert_eq!(projection.projected, Uint128::from(elapsed));
+    assert_eq!(projection.apr, apr_response.apr);
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(elapsed);
+        block.height += 100000u64;
+    });
+    assert_eq!(
+        earned(&mut app, &staking_reward, &tom_address),
+        projection.projected
+    );
+
+    // projections stop at the end of the period
+    let projection = projected_earnings(
+        &mut app,
+        &staking_reward,
+        &tom_address,
+        Uint128::from(10000000u128),
+    );
+    assert_eq!(
+        projection.projected,
+        Uint128::from(2592000u128 - 100000u128)
+    );
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(2592000u64);
+        block.height += 100000u64;
+    });
+    assert_eq!(pool_apr(&mut app, &staking_reward).apr, Uint128::zero());
+}
+
 fn reward_per_token(app: &mut App, staking_reward: &Addr) -> Uint128 {
     let res = app.wrap().query_wasm_smart(
         staking_reward.clone().to_string(),
@@ -518,3 +1283,26 @@ fn notify_reward_amount(
         assert!(res.is_ok());
     }
 }
+
+fn pool_apr(app: &mut App, staking_reward: &Addr) -> PoolAprResponse {
+    app.wrap()
+        .query_wasm_smart(staking_reward.clone(), &QueryMsg::PoolApr {})
+        .unwrap()
+}
+
+fn projected_earnings(
+    app: &mut App,
+    staking_reward: &Addr,
+    account: &Addr,
+    seconds: Uint128,
+) -> ProjectedEarningsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            staking_reward.clone(),
+            &QueryMsg::ProjectedEarnings {
+                account: account.clone(),
+                seconds,
+            },
+        )
+        .unwrap()
+}
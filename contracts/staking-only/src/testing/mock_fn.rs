@@ -1,6 +1,6 @@
 use crate::constract::instantiate;
 use crate::error::ContractError;
-use crate::msg::InstantiateMsg;
+use crate::msg::{AssetInfo, InstantiateMsg};
 use cosmwasm_std::testing::{
     mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
@@ -14,10 +14,16 @@ pub const REWARD_TOKEN_ADDR: &str = "rewards_token";
 pub fn mock_instantiate_msg(staking_token: Addr, rewards_token: Addr) -> InstantiateMsg {
     InstantiateMsg {
         gov: None,
-        staking_token,
-        rewards_token,
+        staking_token: AssetInfo::Token {
+            contract_addr: staking_token,
+        },
+        rewards_token: AssetInfo::Token {
+            contract_addr: rewards_token,
+        },
         reward_controller_addr: Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string()),
         duration: Uint128::from(2592000u128),
+        receipt_token: None,
+        factory: None,
     }
 }
 
@@ -1,11 +1,20 @@
-use crate::handler::{update_staking_config, update_staking_duration};
-use crate::msg::UpdateStakingConfigStruct;
+use crate::constract::migrate;
+use crate::error::ContractError;
+use crate::handler::{
+    rescue_tokens, seed_unclaimed, update_staking_config, update_staking_duration,
+};
+use crate::helper::BASE_RATE_12;
+use crate::msg::{AssetInfo, MigrateMsg, UpdateStakingConfigStruct};
 use crate::querier::query_staking_state;
-use crate::state::read_staking_config;
+use crate::state::{
+    migrate_staking_config, read_staking_config, read_staking_state, store_balance_of,
+    store_rewards, store_staking_state,
+};
 use crate::testing::mock_fn::{
     mock_instantiate, mock_instantiate_msg, REWARD_TOKEN_ADDR, STAKING_TOKEN_ADDR,
 };
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::testing::{mock_dependencies, mock_info};
+use cosmwasm_std::{attr, Addr, Storage, Uint128, Uint256};
 
 #[test]
 fn test_instantiate() {
@@ -46,3 +55,103 @@ fn test_update_staking_duration() {
     let staking_state = query_staking_state(deps.as_ref()).unwrap();
     assert_eq!(staking_state.duration, Uint128::from(1000u128));
 }
+
+#[test]
+fn test_migrate_staking_config() {
+    let mut deps = mock_dependencies();
+    deps.storage.set(
+        b"staking_config",
+        br#"{"gov":"creator","staking_token":"staking_token","rewards_token":"rewards_token","reward_controller_addr":"reward_controller","new_gov":null}"#,
+    );
+    migrate_staking_config(&mut deps.storage).unwrap();
+    let staking_config = read_staking_config(&deps.storage).unwrap();
+    assert_eq!(
+        staking_config.staking_token,
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked(STAKING_TOKEN_ADDR)
+        }
+    );
+    assert_eq!(
+        staking_config.rewards_token,
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked(REWARD_TOKEN_ADDR)
+        }
+    );
+
+    // a second run keeps the migrated config
+    migrate_staking_config(&mut deps.storage).unwrap();
+    assert_eq!(read_staking_config(&deps.storage).unwrap(), staking_config);
+}
+
+#[test]
+fn test_migrate_seeds_unclaimed() {
+    let staking_token = Addr::unchecked(STAKING_TOKEN_ADDR.clone().to_string());
+    let rewards_token = Addr::unchecked(REWARD_TOKEN_ADDR.clone().to_string());
+    let msg = mock_instantiate_msg(staking_token, rewards_token);
+    let (mut deps, mut env, info, _) = mock_instantiate(msg.clone());
+
+    // a pool deployed before unclaimed rewards were tracked, paying 1 token per second
+    let now = Uint128::from(env.block.time.seconds());
+    let tom = Addr::unchecked("tom");
+    let jerry = Addr::unchecked("jerry");
+    let mut staking_state = read_staking_state(&deps.storage).unwrap();
+    staking_state.total_supply = Uint128::from(1000u128);
+    staking_state.reward_rate = Uint256::from(BASE_RATE_12);
+    staking_state.updated_at = now;
+    staking_state.finish_at = now + Uint128::from(100u128);
+    store_staking_state(&mut deps.storage, &staking_state).unwrap();
+    store_balance_of(&mut deps.storage, tom.clone(), &Uint128::from(600u128)).unwrap();
+    store_rewards(&mut deps.storage, tom, &Uint128::from(50u128)).unwrap();
+    store_balance_of(&mut deps.storage, jerry.clone(), &Uint128::from(400u128)).unwrap();
+    store_rewards(&mut deps.storage, jerry, &Uint128::zero()).unwrap();
+    cw2::set_contract_version(
+        &mut deps.storage,
+        "kryptonite.finance:cw20-ve-seilor-staking-only",
+        "0.1.0",
+    )
+    .unwrap();
+
+    // migrate only settles the pool, what stakers had earned is seeded afterwards
+    env.block.time = env.block.time.plus_seconds(40);
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        read_staking_state(&deps.storage).unwrap().unclaimed,
+        Uint128::zero()
+    );
+
+    // nothing can be rescued until every staker is seeded
+    let res = rescue_tokens(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        msg.rewards_token.clone(),
+        Uint128::one(),
+        Addr::unchecked("tom"),
+    );
+    assert!(res.is_err());
+
+    // gov adds what each staker had earned at the migration, one account at a time
+    let res = seed_unclaimed(deps.as_mut(), mock_info("tom", &[]), Some(1));
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+    seed_unclaimed(deps.as_mut(), info.clone(), Some(1)).unwrap();
+    assert_eq!(
+        read_staking_state(&deps.storage).unwrap().unclaimed,
+        Uint128::from(16u128)
+    );
+    seed_unclaimed(deps.as_mut(), info.clone(), Some(1)).unwrap();
+    assert_eq!(
+        read_staking_state(&deps.storage).unwrap().unclaimed,
+        Uint128::from(90u128)
+    );
+    let res = seed_unclaimed(deps.as_mut(), info.clone(), Some(1)).unwrap();
+    assert!(res.attributes.contains(&attr("done", "true")));
+    assert!(seed_unclaimed(deps.as_mut(), info, Some(1)).is_err());
+
+    // migrating again leaves it alone
+    env.block.time = env.block.time.plus_seconds(20);
+    migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+    assert_eq!(
+        read_staking_state(&deps.storage).unwrap().unclaimed,
+        Uint128::from(90u128)
+    );
+}
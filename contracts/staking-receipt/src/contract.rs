@@ -0,0 +1,122 @@
+use crate::handler::{burn, send, send_from, transfer, transfer_from};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::querier::query_receipt_config;
+use crate::state::{store_receipt_config, ReceiptConfig};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw2::set_contract_version;
+use cw20::MinterResponse;
+use cw20_base::allowances::{
+    execute_decrease_allowance, execute_increase_allowance, query_allowance,
+};
+use cw20_base::contract::{
+    execute_mint, instantiate as cw20_instantiate, query_balance, query_minter, query_token_info,
+};
+use cw20_base::enumerable::{query_all_accounts, query_owner_allowances};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw20_base::ContractError;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "kryptonite.finance:cw20-staking-receipt";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let pool = info.sender.clone();
+
+    let cw20_instantiate_msg = Cw20InstantiateMsg {
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+        initial_balances: vec![],
+        mint: Some(MinterResponse {
+            minter: pool.to_string(),
+            cap: None,
+        }),
+        marketing: None,
+    };
+    let ins_res = cw20_instantiate(deps.branch(), env, info, cw20_instantiate_msg);
+    if let Err(err) = ins_res {
+        return Err(ContractError::Std(StdError::generic_err(err.to_string())));
+    }
+
+    store_receipt_config(deps.storage, &ReceiptConfig { pool })?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, env, info, recipient, amount),
+        ExecuteMsg::Burn { owner, amount } => burn(deps, info, owner, amount),
+        ExecuteMsg::Transfer { recipient, amount } => transfer(deps, env, info, recipient, amount),
+        ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => send(deps, env, info, contract, amount, msg),
+        ExecuteMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => transfer_from(deps, env, info, owner, recipient, amount),
+        ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            msg,
+        } => send_from(deps, env, info, owner, contract, amount, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::ReceiptConfig {} => to_binary(&query_receipt_config(deps)?),
+        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
+        QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
+        QueryMsg::Allowance { owner, spender } => {
+            to_binary(&query_allowance(deps, owner, spender)?)
+        }
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_owner_allowances(deps, owner, start_after, limit)?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
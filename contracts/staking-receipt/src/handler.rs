@@ -0,0 +1,129 @@
+use crate::msg::PoolExecuteMsg;
+use crate::state::read_receipt_config;
+use cosmwasm_std::{
+    attr, to_binary, Addr, Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20_base::allowances::{execute_send_from, execute_transfer_from};
+use cw20_base::contract::{execute_send, execute_transfer};
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+use cw20_base::ContractError;
+
+fn move_stake_msg(pool: &Addr, from: Addr, to: Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::MoveStake { from, to, amount })?,
+        funds: vec![],
+    }))
+}
+
+// The stake has to move before any receive hook runs, so the pool message goes first
+fn with_move_stake(
+    pool: &Addr,
+    res: Response,
+    from: Addr,
+    to: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    Ok(Response::new()
+        .add_message(move_stake_msg(pool, from, to, amount)?)
+        .add_submessages(res.messages)
+        .add_attributes(res.attributes)
+        .add_events(res.events))
+}
+
+pub fn burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = read_receipt_config(deps.storage)?;
+    if info.sender != config.pool {
+        return Err(ContractError::Unauthorized {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid zero amount",
+        )));
+    }
+    let owner_addr = deps.api.addr_validate(&owner)?;
+
+    BALANCES.update(
+        deps.storage,
+        &owner_addr,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+        info.total_supply = info.total_supply.checked_sub(amount)?;
+        Ok(info)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "burn"),
+        attr("from", owner),
+        attr("amount", amount),
+    ]))
+}
+
+pub fn transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = read_receipt_config(deps.storage)?;
+    let to = deps.api.addr_validate(&recipient)?;
+    let from = info.sender.clone();
+    let res = execute_transfer(deps, env, info, recipient, amount)?;
+    with_move_stake(&config.pool, res, from, to, amount)
+}
+
+pub fn send(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let config = read_receipt_config(deps.storage)?;
+    let to = deps.api.addr_validate(&contract)?;
+    let from = info.sender.clone();
+    let res = execute_send(deps, env, info, contract, amount, msg)?;
+    with_move_stake(&config.pool, res, from, to, amount)
+}
+
+pub fn transfer_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = read_receipt_config(deps.storage)?;
+    let from = deps.api.addr_validate(&owner)?;
+    let to = deps.api.addr_validate(&recipient)?;
+    let res = execute_transfer_from(deps, env, info, owner, recipient, amount)?;
+    with_move_stake(&config.pool, res, from, to, amount)
+}
+
+pub fn send_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let config = read_receipt_config(deps.storage)?;
+    let from = deps.api.addr_validate(&owner)?;
+    let to = deps.api.addr_validate(&contract)?;
+    let res = execute_send_from(deps, env, info, owner, contract, amount, msg)?;
+    with_move_stake(&config.pool, res, from, to, amount)
+}
@@ -0,0 +1,8 @@
+pub mod contract;
+mod handler;
+pub mod msg;
+mod querier;
+mod state;
+
+#[cfg(test)]
+mod testing;
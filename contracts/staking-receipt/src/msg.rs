@@ -0,0 +1,110 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw_utils::Expiration;
+
+/// Instantiated by a staking pool, which becomes the only minter of the receipt.
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Only the pool. Mints receipts for a new unlocked stake
+    Mint { recipient: String, amount: Uint128 },
+    /// Only the pool. Burns the receipts of a withdrawn stake
+    Burn { owner: String, amount: Uint128 },
+    /// Implements CW20. Moves the receipts together with the underlying stake
+    Transfer { recipient: String, amount: Uint128 },
+    /// Implements CW20. Moves the receipts together with the underlying stake to a contract
+    /// and triggers an action on the receiving contract
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    /// Only with "approval" extension. Allows spender to access an additional amount tokens
+    /// from the owner's (env.sender) account. If expires is Some(), overwrites current allowance
+    /// expiration with this one.
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    /// Only with "approval" extension. Lowers the spender's access of tokens
+    /// from the owner's (env.sender) account by amount. If expires is Some(), overwrites current
+    /// allowance expiration with this one.
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    /// Only with "approval" extension. Transfers amount tokens from owner -> recipient
+    /// if `env.sender` has sufficient pre-approval. Moves the underlying stake as well
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Only with "approval" extension. Sends amount tokens from owner -> contract
+    /// if `env.sender` has sufficient pre-approval. Moves the underlying stake as well
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ReceiptConfigResponse)]
+    ReceiptConfig {},
+
+    /// Returns the current balance of the given address, 0 if unset.
+    #[returns(cw20::BalanceResponse)]
+    Balance { address: String },
+    /// Returns metadata on the contract - name, decimals, supply, etc.
+    #[returns(cw20::TokenInfoResponse)]
+    TokenInfo {},
+    /// Returns who can mint, always the staking pool.
+    #[returns(cw20::MinterResponse)]
+    Minter {},
+    /// Returns how much spender can use from owner account, 0 if unset.
+    #[returns(cw20::AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+    /// Returns all allowances this owner has approved. Supports pagination.
+    #[returns(cw20::AllAllowancesResponse)]
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns all accounts that have balances. Supports pagination.
+    #[returns(cw20::AllAccountsResponse)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ReceiptConfigResponse {
+    pub pool: Addr,
+}
+
+/// Sent to the pool ahead of every receipt transfer so the stake follows the receipt.
+#[cw_serde]
+pub enum PoolExecuteMsg {
+    MoveStake {
+        from: Addr,
+        to: Addr,
+        amount: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,8 @@
+use crate::msg::ReceiptConfigResponse;
+use crate::state::read_receipt_config;
+use cosmwasm_std::{Deps, StdResult};
+
+pub fn query_receipt_config(deps: Deps) -> StdResult<ReceiptConfigResponse> {
+    let config = read_receipt_config(deps.storage)?;
+    Ok(ReceiptConfigResponse { pool: config.pool })
+}
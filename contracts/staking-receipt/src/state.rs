@@ -0,0 +1,24 @@
+use cosmwasm_std::{Addr, StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReceiptConfig {
+    pub pool: Addr,
+}
+
+const RECEIPT_CONFIG: Item<ReceiptConfig> = Item::new("receipt_config");
+
+pub fn store_receipt_config(
+    storage: &mut dyn Storage,
+    receipt_config: &ReceiptConfig,
+) -> StdResult<()> {
+    RECEIPT_CONFIG.save(storage, receipt_config)
+}
+
+pub fn read_receipt_config(storage: &dyn Storage) -> StdResult<ReceiptConfig> {
+    RECEIPT_CONFIG
+        .load(storage)
+        .map_err(|_| StdError::generic_err("ReceiptConfig not found"))
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, PoolExecuteMsg, QueryMsg, ReceiptConfigResponse};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, to_binary, Addr, CosmosMsg, Deps, DepsMut, Uint128, WasmMsg};
+    use cw20::TokenInfoResponse;
+    use cw20_base::contract::query_balance;
+    use cw20_base::ContractError;
+
+    fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
+        query_balance(deps, address.into()).unwrap().balance
+    }
+
+    fn get_total_supply(deps: Deps) -> Uint128 {
+        let res = query(deps, mock_env(), QueryMsg::TokenInfo {}).unwrap();
+        let token_info: TokenInfoResponse = from_binary(&res).unwrap();
+        token_info.total_supply
+    }
+
+    fn default_instantiate(deps: DepsMut) {
+        let msg = InstantiateMsg {
+            name: "Staked kUSD".to_string(),
+            symbol: "skUSD".to_string(),
+            decimals: 6,
+        };
+        instantiate(deps, mock_env(), mock_info("pool", &[]), msg).unwrap();
+    }
+
+    fn mint(
+        deps: DepsMut,
+        sender: &str,
+        recipient: &str,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        let msg = ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        };
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ReceiptConfig {}).unwrap();
+        let config: ReceiptConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(config.pool, Addr::unchecked("pool"));
+        assert_eq!(get_total_supply(deps.as_ref()), Uint128::zero());
+    }
+
+    #[test]
+    fn test_mint_and_burn_only_pool() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+
+        let res = mint(deps.as_mut(), "user", "user", 100);
+        assert_eq!(res, Err(ContractError::Unauthorized {}));
+        mint(deps.as_mut(), "pool", "user", 100).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "user"), Uint128::from(100u128));
+
+        let burn_msg = ExecuteMsg::Burn {
+            owner: "user".to_string(),
+            amount: Uint128::from(40u128),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &[]),
+            burn_msg.clone(),
+        );
+        assert_eq!(res, Err(ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), mock_info("pool", &[]), burn_msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "user"), Uint128::from(60u128));
+        assert_eq!(get_total_supply(deps.as_ref()), Uint128::from(60u128));
+
+        // cannot burn more than the owner holds
+        let burn_msg = ExecuteMsg::Burn {
+            owner: "user".to_string(),
+            amount: Uint128::from(61u128),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("pool", &[]), burn_msg);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_transfer_moves_stake() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut());
+        mint(deps.as_mut(), "pool", "alice", 100).unwrap();
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: "bob".to_string(),
+            amount: Uint128::from(30u128),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pool".to_string(),
+                msg: to_binary(&PoolExecuteMsg::MoveStake {
+                    from: Addr::unchecked("alice"),
+                    to: Addr::unchecked("bob"),
+                    amount: Uint128::from(30u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(70u128));
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(30u128));
+
+        // the move has to happen before the receive hook of the contract
+        let msg = ExecuteMsg::Send {
+            contract: "vault".to_string(),
+            amount: Uint128::from(20u128),
+            msg: to_binary("deposit").unwrap(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+                assert_eq!(contract_addr, "pool")
+            }
+            _ => panic!("unexpected message"),
+        }
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+                assert_eq!(contract_addr, "vault")
+            }
+            _ => panic!("unexpected message"),
+        }
+    }
+}
@@ -1,20 +1,29 @@
 use crate::error::ContractError;
 use crate::handler::{
-    accept_gov, get_reward, notify_reward_amount, receive_cw20, set_gov, update_staking_config,
-    update_staking_duration, withdraw,
+    accept_gov, add_reward_token, claim_unbonded, emergency_withdraw, get_reward,
+    get_reward_early, get_rewards, kick, move_stake, notify_reward_amount, receipt_token_reply,
+    receive_cw20, rescue_tokens, schedule_rewards, seed_reward_budget, set_gov, set_lock_tiers, shutdown,
+    start_reward_budget_seed, update_reward_token, update_staking_config, update_staking_duration, withdraw,
+    withdraw_position, RECEIPT_TOKEN_REPLY_ID,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::querier::{
-    balance_of, earned, get_boost, get_user_reward_per_token_paid, get_user_updated_at,
-    last_time_reward_applicable, query_staking_config, query_staking_state, reward_per_token,
+    balance_of, base_earned, boosted_balance_of, earned, earned_all, get_boost,
+    get_user_reward_per_token_paid, get_user_updated_at, last_time_reward_applicable,
+    query_lock_tiers, query_pool_apr, query_projected_earnings, query_reward_budget,
+    query_reward_schedule, query_reward_tokens, query_staking_config,
+    query_staking_state, query_unbonding_requests, query_user_positions, reward_per_token,
+    working_balance_of,
 };
-use crate::state::{store_staking_config, store_staking_state, StakingConfig, StakingState, read_staking_state, read_staking_config};
+use crate::state::{store_staking_config, store_staking_state, StakingConfig, StakingState, read_staking_state};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, Uint256, StdError, QueryRequest, WasmQuery, CosmosMsg, WasmMsg, Addr};
+use cosmwasm_std::{
+    attr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+    StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+};
 use cw2::{get_contract_version, query_contract_info, set_contract_version};
-use cw20::{BalanceResponse, Cw20ExecuteMsg};
-use cw20::Cw20QueryMsg::Balance;
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "kryptonite.finance:cw20-ve-seilor-staking";
@@ -42,6 +51,12 @@ pub fn instantiate(
         fund: msg.fund,
         reward_controller_addr: msg.reward_controller_addr,
         new_gov: None,
+        boost_mode: msg.boost_mode.unwrap_or_default(),
+        unbonding_period: msg.unbonding_period.unwrap_or_default(),
+        forfeit_mode: Default::default(),
+        shutdown: false,
+        receipt_token: None,
+        factory: msg.factory,
     };
 
     store_staking_config(deps.storage, &staking_config)?;
@@ -49,15 +64,44 @@ pub fn instantiate(
     let staking_state = StakingState {
         duration: msg.duration,
         finish_at: Uint128::zero(),
+        started_at: Uint128::zero(),
         updated_at: Uint128::zero(),
         reward_rate: Uint256::zero(),
         reward_per_token_stored: Uint128::zero(),
         total_supply: Uint128::zero(),
+        working_supply: Uint128::zero(),
+        boosted_supply: Uint128::zero(),
+        total_notified: Uint128::zero(),
+        total_accrued: Uint128::zero(),
+        total_claimed: Uint128::zero(),
+        total_lock_bonus: Uint128::zero(),
+        total_unbonding: Uint128::zero(),
+        carried_rate: Uint256::zero(),
+        carried_until: Uint128::zero(),
+        scheduled: false,
     };
 
     store_staking_state(deps.storage, &staking_state)?;
 
-    Ok(Response::new().add_attributes(vec![
+    let mut sub_msgs = vec![];
+    if let Some(receipt_token) = msg.receipt_token {
+        sub_msgs.push(SubMsg::reply_on_success(
+            WasmMsg::Instantiate {
+                admin: Some(staking_config.gov.to_string()),
+                code_id: receipt_token.code_id,
+                msg: to_binary(&staking_receipt::msg::InstantiateMsg {
+                    name: receipt_token.name,
+                    symbol: receipt_token.symbol.clone(),
+                    decimals: receipt_token.decimals,
+                })?,
+                funds: vec![],
+                label: format!("staking receipt {}", receipt_token.symbol),
+            },
+            RECEIPT_TOKEN_REPLY_ID,
+        ));
+    }
+
+    Ok(Response::new().add_submessages(sub_msgs).add_attributes(vec![
         ("action", "instantiate"),
         ("owner", info.sender.as_str()),
     ]))
@@ -79,10 +123,48 @@ pub fn execute(
             update_staking_duration(deps, env, info, duration)
         }
         ExecuteMsg::GetReward {} => get_reward(deps, env, info),
-        ExecuteMsg::NotifyRewardAmount { amount } => notify_reward_amount(deps, env, info, amount),
+        ExecuteMsg::GetRewardEarly {} => get_reward_early(deps, env, info),
+        ExecuteMsg::NotifyRewardAmount { token, amount } => {
+            notify_reward_amount(deps, env, info, token, amount)
+        }
+        ExecuteMsg::ScheduleRewards { start, end, amount } => {
+            schedule_rewards(deps, env, info, start, end, amount)
+        }
+        ExecuteMsg::GetRewards {} => get_rewards(deps, env, info),
+        ExecuteMsg::RescueTokens {
+            asset,
+            amount,
+            recipient,
+        } => rescue_tokens(deps, env, info, asset, amount, recipient),
+        ExecuteMsg::SeedRewardBudget { limit } => seed_reward_budget(deps, info, limit),
+        ExecuteMsg::AddRewardToken {
+            token,
+            distributor,
+            duration,
+        } => add_reward_token(deps, env, info, token, distributor, duration),
+        ExecuteMsg::UpdateRewardToken {
+            token,
+            distributor,
+            duration,
+        } => update_reward_token(deps, env, info, token, distributor, duration),
         ExecuteMsg::Withdraw { amount } => withdraw(deps, env, info, amount),
+        ExecuteMsg::WithdrawPosition { id } => withdraw_position(deps, env, info, id),
+        ExecuteMsg::ClaimUnbonded {} => claim_unbonded(deps, env, info),
+        ExecuteMsg::SetLockTiers { lock_tiers } => set_lock_tiers(deps, info, lock_tiers),
+        ExecuteMsg::EmergencyWithdraw {} => emergency_withdraw(deps, env, info),
+        ExecuteMsg::Shutdown {} => shutdown(deps, env, info),
         ExecuteMsg::SetGov { gov } => set_gov(deps, info, gov),
         ExecuteMsg::AcceptGov {} => accept_gov(deps, info),
+        ExecuteMsg::Kick { user } => kick(deps, env, user),
+        ExecuteMsg::MoveStake { from, to, amount } => move_stake(deps, env, info, from, to, amount),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        RECEIPT_TOKEN_REPLY_ID => receipt_token_reply(deps, msg),
+        id => Err(ContractError::UnknownReplyId(id)),
     }
 }
 
@@ -95,6 +177,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::GetBoost { account } => to_binary(&get_boost(deps, account)?),
         QueryMsg::Earned { account } => to_binary(&earned(deps, env, account)?),
+        QueryMsg::BaseEarned { account } => to_binary(&base_earned(deps, env, account)?),
         QueryMsg::QueryStakingConfig {} => to_binary(&query_staking_config(deps)?),
         QueryMsg::QueryStakingState {} => to_binary(&query_staking_state(deps)?),
         QueryMsg::GetUserUpdatedAt { account } => to_binary(&get_user_updated_at(deps, account)?),
@@ -102,44 +185,62 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&get_user_reward_per_token_paid(deps, account)?)
         }
         QueryMsg::BalanceOf { account } => to_binary(&balance_of(deps, account)?),
+        QueryMsg::WorkingBalanceOf { account } => to_binary(&working_balance_of(deps, account)?),
+        QueryMsg::BoostedBalanceOf { account } => to_binary(&boosted_balance_of(deps, account)?),
+        QueryMsg::RewardBudget {} => to_binary(&query_reward_budget(deps)?),
+        QueryMsg::RewardSchedule {} => to_binary(&query_reward_schedule(deps, env)?),
+        QueryMsg::EarnedAll { account } => to_binary(&earned_all(deps, env, account)?),
+        QueryMsg::RewardTokens {} => to_binary(&query_reward_tokens(deps)?),
+        QueryMsg::UnbondingRequests { account } => {
+            to_binary(&query_unbonding_requests(deps, account)?)
+        }
+        QueryMsg::LockTiers {} => to_binary(&query_lock_tiers(deps)?),
+        QueryMsg::UserPositions {
+            account,
+            start_after,
+            limit,
+        } => to_binary(&query_user_positions(deps, account, start_after, limit)?),
+        QueryMsg::PoolApr {} => to_binary(&query_pool_apr(deps, env)?),
+        QueryMsg::ProjectedEarnings { account, seconds } => {
+            to_binary(&query_projected_earnings(deps, env, account, seconds)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    // Cannot upgrade sei-seilor /sei-stsei to staking pool
-
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = get_contract_version(deps.storage)?;
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from contract {}",
+            contract_version.contract
+        )));
+    }
+    let stored_version = parse_version(&contract_version.version)?;
+    if stored_version > parse_version(CONTRACT_VERSION)? {
+        return Err(StdError::generic_err(format!(
+            "Can't migrate from version {} to older version {}",
+            contract_version.version, CONTRACT_VERSION
+        )));
+    }
 
-    // let contract_version = get_contract_version(deps.storage)?.version;
-    // let old_version = "1.0.1".to_string();
-    // let next_version = "1.0.2".to_string();
-    // if contract_version != old_version {
-    //     return Err(StdError::generic_err(format!(
-    //         "This contract is at version {}, but we need to migrate from {}",
-    //         contract_version, old_version
-    //     )));
-    // }
-    let staking_config = read_staking_config(deps.storage)?;
-    let balanceRequest: BalanceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: staking_config.staking_token.to_string(),
-        msg: to_binary(&Balance {
-            address: env.contract.address.to_string(),
-        })?,
-    }))?;
-    if balanceRequest.balance.gt(&Uint128::zero()) {
-        let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: staking_config.staking_token.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: Addr::unchecked("sei1wl38643tpena47ec76zkjxyt55g3r2vsgwgaww").to_string(),
-                amount: balanceRequest.balance,
-            })?,
-            funds: vec![],
-        })];
-        return Ok(Response::new()
-            .add_messages(messages)
-            .add_attribute("action", "migrate"));
+    // before 0.2.0 rewards were shared by total_supply and claims were not capped by a budget
+    if stored_version < Version::new(0, 2, 0) {
+        // stakers not yet updated count with a 1x boost, see read_boosted_balance_of
+        let mut staking_state = read_staking_state(deps.storage)?;
+        staking_state.boosted_supply = staking_state.total_supply;
+        store_staking_state(deps.storage, &staking_state)?;
+        start_reward_budget_seed(deps.branch(), &env)?;
     }
 
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "migrate"),
+        attr("from_version", contract_version.version),
+        attr("to_version", CONTRACT_VERSION),
+    ]))
+}
 
-    Ok(Response::default())
+fn parse_version(version: &str) -> StdResult<Version> {
+    Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()))
 }
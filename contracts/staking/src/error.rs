@@ -1,3 +1,4 @@
+use common::rescue::RescueError;
 use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
@@ -18,4 +19,10 @@ pub enum ContractError {
     NoNewGov {},
     #[error("Invalid Duration")]
     InvalidDuration {},
+    #[error("Pool is shut down")]
+    PoolShutdown {},
+    #[error("Unknown reply id {0}")]
+    UnknownReplyId(u64),
+    #[error("{0}")]
+    Rescue(#[from] RescueError),
 }
@@ -1,19 +1,45 @@
 use crate::error::ContractError;
-use crate::helper::{BASE_RATE_18};
-use crate::msg::{Cw20HookMsg, UpdateStakingConfigStruct};
-use crate::querier::{earned, is_empty_address, last_time_reward_applicable, reward_per_token};
+use crate::helper::{BASE_RATE_18, BASE_RATE_8};
+use crate::msg::{
+    AssetInfo, BoostMode, Cw20HookMsg, ForfeitMode, LockTierItem, UpdateStakingConfigStruct,
+};
+use crate::querier::{
+    base_earned, current_staking_state, earned, is_empty_address, last_time_reward_applicable,
+    query_current_boost, query_working_balance, reserved_balance, reward_balance_of,
+    reward_per_token, reward_supply, token_earned, token_reward_per_token, weighted_balance_of,
+    weighted_supply,
+};
 use crate::state::{
-    read_balance_of, read_rewards, read_staking_config, read_staking_state, store_balance_of,
-    store_rewards, store_staking_config, store_staking_state, store_user_reward_per_token_paid,
-    store_user_updated_at,
+    is_seeded_account, next_position_id, next_reward_schedule_id, next_unbonding_id,
+    read_all_positions, read_balance_of, read_base_rewards, read_boosted_balance_of,
+    read_lock_bonus_of, read_lock_tiers, read_position, read_reward_accounts,
+    read_reward_budget_seed, read_reward_schedules, read_reward_token, read_reward_tokens,
+    read_rewards, read_staking_config, read_staking_state, read_unbonding_entries,
+    read_user_reward_per_token_paid, read_user_token_rewards, read_working_balance_of,
+    remove_position, remove_reward_budget_seed, remove_reward_schedule, remove_unbonding_entry,
+    store_balance_of, store_base_rewards, store_boosted_balance_of, store_lock_bonus_of,
+    store_lock_tiers, store_position, store_reward_budget_seed, store_reward_schedule,
+    store_reward_token, store_rewards, store_seeded_account, store_staking_config,
+    store_staking_state, store_unbonding_entry, store_user_reward_per_token_paid,
+    store_user_token_reward_per_token_paid, store_user_token_rewards, store_user_updated_at,
+    store_working_balance_of, LockTier, Position, RewardBudgetSeed, RewardSchedule, RewardToken,
+    StakingConfig, UnbondingEntry, DEFAULT_LIMIT, MAX_LIMIT, MAX_LOCK_MULTIPLIER, MAX_LOCK_TIERS,
+    MAX_REWARD_SCHEDULES, MAX_REWARD_TOKENS, MAX_UNBONDING_ENTRIES, MAX_USER_POSITIONS,
 };
 
+use boost::state::TOKENLESS_PRODUCTION;
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest,
-    Response, StdError, SubMsg, Uint128, Uint256, WasmMsg, WasmQuery,
+    attr, from_binary, to_binary, Addr, Attribute, CosmosMsg, DepsMut, Env, MessageInfo,
+    QueryRequest, Reply, Response, StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+    WasmQuery,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
-use std::ops::Add;
+use cw_utils::parse_reply_instantiate_data;
+use staking_receipt::msg::ExecuteMsg as ReceiptExecuteMsg;
+use std::ops::{Add, Div, Mul};
+use std::str::FromStr;
+
+pub const RECEIPT_TOKEN_REPLY_ID: u64 = 1;
 
 pub fn update_staking_config(
     deps: DepsMut,
@@ -60,6 +86,14 @@ pub fn update_staking_config(
             reward_controller_addr.to_string(),
         ));
     }
+    if let Some(unbonding_period) = update_struct.unbonding_period {
+        staking_config.unbonding_period = unbonding_period;
+        attrs.push(attr("unbonding_period", unbonding_period.to_string()));
+    }
+    if let Some(forfeit_mode) = update_struct.forfeit_mode {
+        attrs.push(attr("forfeit_mode", format!("{:?}", forfeit_mode)));
+        staking_config.forfeit_mode = forfeit_mode;
+    }
 
     store_staking_config(deps.storage, &staking_config)?;
 
@@ -98,8 +132,211 @@ pub fn update_staking_duration(
     ]))
 }
 
+// Recompute the user's working balance from the current stake and ve_seilor share.
+fn _update_working_balance(deps: DepsMut, account: Addr) -> Result<Uint128, ContractError> {
+    let mut staking_state = read_staking_state(deps.storage)?;
+    let balance_of = weighted_balance_of(deps.storage, &account);
+    let working_balance = query_working_balance(
+        deps.as_ref(),
+        account.clone(),
+        balance_of,
+        weighted_supply(&staking_state),
+    )?;
+    let old_working_balance = read_working_balance_of(deps.storage, account.clone());
+    staking_state.working_supply = staking_state
+        .working_supply
+        .checked_sub(old_working_balance)?
+        .checked_add(working_balance)?;
+
+    store_working_balance_of(deps.storage, account, &working_balance)?;
+    store_staking_state(deps.storage, &staking_state)?;
+    Ok(working_balance)
+}
+
+// Recompute the user's boosted balance from the current stake and the lock's current mining boost.
+fn _update_boosted_balance(
+    deps: DepsMut,
+    env: Env,
+    account: Addr,
+) -> Result<Uint128, ContractError> {
+    let mut staking_state = read_staking_state(deps.storage)?;
+    let balance_of = weighted_balance_of(deps.storage, &account);
+    let user_boost = query_current_boost(deps.as_ref(), env, account.clone())?;
+    let boosted_balance = balance_of.multiply_ratio(
+        Uint128::new(BASE_RATE_8).checked_add(user_boost)?,
+        Uint128::new(BASE_RATE_8),
+    );
+    let old_boosted_balance = read_boosted_balance_of(deps.storage, account.clone());
+    staking_state.boosted_supply = staking_state
+        .boosted_supply
+        .checked_sub(old_boosted_balance)?
+        .checked_add(boosted_balance)?;
+
+    store_boosted_balance_of(deps.storage, account, &boosted_balance)?;
+    store_staking_state(deps.storage, &staking_state)?;
+    Ok(boosted_balance)
+}
+
+// Recompute the user's share of the rewards after their stake or boost changed,
+// rewards must be settled before calling it.
+fn _update_reward_balance(
+    deps: DepsMut,
+    env: Env,
+    account: Addr,
+) -> Result<Uint128, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    match staking_config.boost_mode {
+        BoostMode::LockTier => _update_boosted_balance(deps, env, account),
+        BoostMode::WorkingBalance => _update_working_balance(deps, account),
+    }
+}
+
+// Drops the bonus of the user's positions whose lock has expired, rewards must be settled before calling it.
+fn _expire_positions(deps: DepsMut, env: &Env, account: &Addr) -> Result<(), ContractError> {
+    let current_time = Uint128::from(env.block.time.seconds());
+    let mut expired_bonus = Uint128::zero();
+    for mut position in read_all_positions(deps.storage, account)? {
+        if position.bonus.is_zero() || position.unlock_time > current_time {
+            continue;
+        }
+        expired_bonus += position.bonus;
+        position.bonus = Uint128::zero();
+        store_position(deps.storage, account, &position)?;
+    }
+    if !expired_bonus.is_zero() {
+        let lock_bonus = read_lock_bonus_of(deps.storage, account).checked_sub(expired_bonus)?;
+        store_lock_bonus_of(deps.storage, account, &lock_bonus)?;
+        let mut staking_state = read_staking_state(deps.storage)?;
+        staking_state.total_lock_bonus =
+            staking_state.total_lock_bonus.checked_sub(expired_bonus)?;
+        store_staking_state(deps.storage, &staking_state)?;
+    }
+    Ok(())
+}
+
+// Same as _update_reward for every partner reward token, the user's share must not have changed yet.
+fn _update_token_rewards(deps: DepsMut, env: &Env, account: &Addr) -> Result<(), ContractError> {
+    let current_time = Uint128::from(env.block.time.seconds());
+    let staking_config = read_staking_config(deps.storage)?;
+    let staking_state = read_staking_state(deps.storage)?;
+    for mut reward_token in read_reward_tokens(deps.storage)? {
+        let reward_per_token_stored = token_reward_per_token(deps.as_ref(), env, &reward_token)?;
+        let accrued = Uint256::from(reward_supply(&staking_config, &staking_state))
+            .mul(Uint256::from(
+                reward_per_token_stored - reward_token.reward_per_token_stored,
+            ))
+            .div(Uint256::from(BASE_RATE_18));
+        reward_token.unclaimed += Uint128::from_str(&accrued.to_string())?;
+        if !is_empty_address(account.as_str()) {
+            let earned = token_earned(deps.as_ref(), env, &reward_token, account)?;
+            store_user_token_rewards(deps.storage, &reward_token.token, account, &earned)?;
+            store_user_token_reward_per_token_paid(
+                deps.storage,
+                &reward_token.token,
+                account,
+                &reward_per_token_stored,
+            )?;
+        }
+        reward_token.reward_per_token_stored = reward_per_token_stored;
+        reward_token.updated_at = reward_token.finish_at.min(current_time);
+        store_reward_token(deps.storage, &reward_token)?;
+    }
+    Ok(())
+}
+
+// Starts the reward budget of a pool upgraded from before it existed with what is left of the
+// running period. What stakers had earned by now joins it account by account, see _seed_account
+pub fn start_reward_budget_seed(deps: DepsMut, env: &Env) -> StdResult<()> {
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.reward_per_token_stored =
+        reward_per_token(deps.as_ref(), env.clone())?.reward_per_token;
+    staking_state.updated_at =
+        last_time_reward_applicable(deps.as_ref(), env.clone())?.last_time_reward_applicable;
+
+    let current_time = Uint128::from(env.block.time.seconds());
+    let remaining_rewards = if current_time < staking_state.finish_at {
+        Uint256::from(staking_state.finish_at - current_time)
+            .multiply_ratio(staking_state.reward_rate, Uint256::from(BASE_RATE_18))
+    } else {
+        Uint256::zero()
+    };
+    staking_state.total_accrued = Uint128::zero();
+    staking_state.total_claimed = Uint128::zero();
+    staking_state.total_notified = Uint128::from_str(&remaining_rewards.to_string())?;
+    store_staking_state(deps.storage, &staking_state)?;
+    store_reward_budget_seed(
+        deps.storage,
+        &RewardBudgetSeed {
+            reward_per_token: staking_state.reward_per_token_stored,
+            last_account: None,
+        },
+    )
+}
+
+// Adds what the account had earned at the migration to the reward budget, once. Its rewards and
+// paid reward per token are still those of the migration until its first update, which seeds it
+fn _seed_account(deps: DepsMut, seed: &RewardBudgetSeed, account: &Addr) -> StdResult<()> {
+    if is_seeded_account(deps.storage, account) {
+        return Ok(());
+    }
+    let reward_balance = reward_balance_of(deps.as_ref(), account.clone())?;
+    let user_reward_per_token_paid = read_user_reward_per_token_paid(deps.storage, account.clone());
+    let new_earned = Uint256::from(reward_balance)
+        .mul(Uint256::from(
+            seed.reward_per_token
+                .saturating_sub(user_reward_per_token_paid),
+        ))
+        .div(Uint256::from(BASE_RATE_18));
+    let earned =
+        Uint128::from_str(&new_earned.to_string())? + read_rewards(deps.storage, account.clone());
+
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.total_accrued += earned;
+    staking_state.total_notified += earned;
+    store_staking_state(deps.storage, &staking_state)?;
+    store_seeded_account(deps.storage, account)
+}
+
+// Seeds the reward budget for the next `limit` accounts, the seed is done once they have all been
+// walked
+pub fn seed_reward_budget(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut seed = read_reward_budget_seed(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("reward budget is already seeded"))?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let accounts = read_reward_accounts(deps.storage, seed.last_account.clone(), limit)?;
+    for account in accounts.iter() {
+        _seed_account(deps.branch(), &seed, account)?;
+    }
+
+    let done = accounts.len() < limit;
+    if done {
+        remove_reward_budget_seed(deps.storage);
+    } else {
+        seed.last_account = accounts.last().cloned();
+        store_reward_budget_seed(deps.storage, &seed)?;
+    }
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "seed_reward_budget"),
+        attr("seeded", accounts.len().to_string()),
+        attr("done", done.to_string()),
+    ]))
+}
+
 // Update user's claimable reward data and record the timestamp.
-fn _update_reward(deps: DepsMut, env: Env, account: Addr) -> Result<Response, ContractError> {
+fn _update_reward(mut deps: DepsMut, env: Env, account: Addr) -> Result<Response, ContractError> {
+    if !is_empty_address(account.as_str()) {
+        if let Some(seed) = read_reward_budget_seed(deps.storage)? {
+            _seed_account(deps.branch(), &seed, &account)?;
+        }
+    }
     let reward_per_token_response = reward_per_token(deps.as_ref(), env.clone())?;
     let reward_per_token_stored = reward_per_token_response.reward_per_token;
 
@@ -107,20 +344,42 @@ fn _update_reward(deps: DepsMut, env: Env, account: Addr) -> Result<Response, Co
         last_time_reward_applicable(deps.as_ref(), env.clone())?;
     let updated_at = last_time_reward_applicable_response.last_time_reward_applicable;
 
-    let mut staking_state = read_staking_state(deps.storage)?;
+    let staking_config = read_staking_config(deps.storage)?;
+    let last_reward_per_token_stored = read_staking_state(deps.storage)?.reward_per_token_stored;
+    // starts the scheduled epochs due by now, reward_per_token above already accounts for them
+    let mut staking_state = current_staking_state(deps.as_ref(), &env)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    for schedule in read_reward_schedules(deps.storage)? {
+        if schedule.start > current_time {
+            break;
+        }
+        remove_reward_schedule(deps.storage, schedule.id);
+    }
+    // rewards shared out since the last update, rounded down like each user's share of them
+    let accrued = Uint256::from(reward_supply(&staking_config, &staking_state))
+        .mul(Uint256::from(
+            reward_per_token_stored - last_reward_per_token_stored,
+        ))
+        .div(Uint256::from(BASE_RATE_18));
+    staking_state.total_accrued += Uint128::from_str(&accrued.to_string())?;
     staking_state.reward_per_token_stored = reward_per_token_stored.clone();
     staking_state.updated_at = updated_at.clone();
     store_staking_state(deps.storage, &staking_state)?;
+    _update_token_rewards(deps.branch(), &env, &account)?;
 
     if !is_empty_address(account.as_str()) {
         let earned = earned(deps.as_ref(), env.clone(), account.clone())?.earned;
+        let base_earned = base_earned(deps.as_ref(), env.clone(), account.clone())?.base_earned;
         store_rewards(deps.storage, account.clone(), &earned)?;
+        store_base_rewards(deps.storage, &account, &base_earned)?;
         store_user_reward_per_token_paid(deps.storage, account.clone(), &reward_per_token_stored)?;
         store_user_updated_at(
             deps.storage,
             account.clone(),
             &Uint128::from(env.block.time.seconds()),
         )?;
+        _expire_positions(deps.branch(), &env, &account)?;
+        _update_reward_balance(deps.branch(), env.clone(), account.clone())?;
     }
     Ok(Response::new().add_attributes(vec![
         attr("action", "update_reward"),
@@ -132,13 +391,17 @@ fn _update_reward(deps: DepsMut, env: Env, account: Addr) -> Result<Response, Co
     ]))
 }
 
-// Allows users to stake a specified amount of tokens
+// Allows users to stake a specified amount of tokens, optionally into a position locked for a lock tier's duration
 pub fn stake(
     mut deps: DepsMut,
     env: Env,
     user: Addr,
     amount: Uint128,
+    lock_duration: Option<Uint128>,
 ) -> Result<Response, ContractError> {
+    if read_staking_config(deps.storage)?.shutdown {
+        return Err(ContractError::PoolShutdown {});
+    }
     _update_reward(deps.branch(), env.clone(), user.clone())?;
     if amount.is_zero() {
         return Err(ContractError::Std(StdError::generic_err("amount = 0")));
@@ -149,13 +412,60 @@ pub fn stake(
     balance_of += amount;
     staking_state.total_supply += amount;
 
-    store_balance_of(deps.storage, user.clone(), &balance_of.clone())?;
-    store_staking_state(deps.storage, &staking_state)?;
-    Ok(Response::new().add_attributes(vec![
+    let mut attrs = vec![
         attr("action", "stake"),
         attr("user", user.to_string()),
         attr("amount", amount.to_string()),
-    ]))
+    ];
+    if let Some(duration) = lock_duration {
+        let lock_tier = read_lock_tiers(deps.storage)
+            .into_iter()
+            .find(|lock_tier| lock_tier.duration == duration)
+            .ok_or_else(|| StdError::generic_err("lock tier not found"))?;
+        if read_all_positions(deps.storage, &user)?.len() >= MAX_USER_POSITIONS {
+            return Err(ContractError::Std(StdError::generic_err(
+                "too many positions",
+            )));
+        }
+        let current_time = Uint128::from(env.block.time.seconds());
+        let bonus = amount.multiply_ratio(
+            lock_tier.multiplier - Uint128::new(BASE_RATE_8),
+            Uint128::new(BASE_RATE_8),
+        );
+        let position = Position {
+            id: next_position_id(deps.storage)?,
+            amount,
+            duration,
+            multiplier: lock_tier.multiplier,
+            start_time: current_time,
+            unlock_time: current_time + duration,
+            bonus,
+        };
+        store_position(deps.storage, &user, &position)?;
+        let lock_bonus = read_lock_bonus_of(deps.storage, &user) + bonus;
+        store_lock_bonus_of(deps.storage, &user, &lock_bonus)?;
+        staking_state.total_lock_bonus += bonus;
+        attrs.push(attr("position_id", position.id.to_string()));
+        attrs.push(attr("unlock_time", position.unlock_time.to_string()));
+    }
+
+    store_balance_of(deps.storage, user.clone(), &balance_of.clone())?;
+    store_staking_state(deps.storage, &staking_state)?;
+    _update_reward_balance(deps.branch(), env.clone(), user.clone())?;
+
+    // positions stay bound to the staker, only the unlocked stake gets receipts
+    let messages = if lock_duration.is_none() {
+        _receipt_msgs(
+            &read_staking_config(deps.storage)?,
+            ReceiptExecuteMsg::Mint {
+                recipient: user.to_string(),
+                amount,
+            },
+        )?
+    } else {
+        vec![]
+    };
+    Ok(Response::new().add_messages(messages).add_attributes(attrs))
 }
 
 // Allows users to withdraw a specified amount of staked tokens
@@ -171,32 +481,404 @@ pub fn withdraw(
         return Err(ContractError::Std(StdError::generic_err("amount = 0")));
     }
 
-    let staking_token = read_staking_config(deps.storage)?.staking_token;
+    let staking_config = read_staking_config(deps.storage)?;
 
     let mut balance_of = read_balance_of(deps.storage, user.clone());
+    // tokens in positions only leave through withdraw_position
+    let locked_amount: Uint128 = read_all_positions(deps.storage, &user)?
+        .iter()
+        .map(|position| position.amount)
+        .sum();
+    if amount > balance_of.checked_sub(locked_amount)? {
+        return Err(ContractError::Std(StdError::generic_err(
+            "insufficient unlocked balance",
+        )));
+    }
     let mut staking_state = read_staking_state(deps.storage)?;
     balance_of -= amount;
     staking_state.total_supply -= amount;
 
     store_balance_of(deps.storage, user.clone(), &balance_of)?;
     store_staking_state(deps.storage, &staking_state)?;
+    _update_reward_balance(deps.branch(), env.clone(), user.clone())?;
+
+    let receipt_messages = _receipt_msgs(
+        &staking_config,
+        ReceiptExecuteMsg::Burn {
+            owner: user.to_string(),
+            amount,
+        },
+    )?;
+    let (messages, unbonding_attrs) = _unbond(deps, &env, &user, amount)?;
 
+    Ok(Response::new()
+        .add_messages(receipt_messages)
+        .add_messages(messages)
+        .add_attributes(vec![
+            attr("action", "withdraw"),
+            attr("user", user.to_string()),
+            attr("amount", amount.to_string()),
+        ])
+        .add_attributes(unbonding_attrs))
+}
+
+// Pays out tokens that left the staked balance, or queues them until the unbonding period has passed
+fn _unbond(
+    deps: DepsMut,
+    env: &Env,
+    user: &Addr,
+    amount: Uint128,
+) -> Result<(Vec<CosmosMsg>, Vec<Attribute>), ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if staking_config.unbonding_period.is_zero() {
+        let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: staking_config.staking_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: user.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })];
+        return Ok((messages, vec![]));
+    }
+
+    if read_unbonding_entries(deps.storage, user)?.len() >= MAX_UNBONDING_ENTRIES {
+        return Err(ContractError::Std(StdError::generic_err(
+            "too many unbonding requests",
+        )));
+    }
+    let entry = UnbondingEntry {
+        id: next_unbonding_id(deps.storage)?,
+        amount,
+        release_time: Uint128::from(env.block.time.seconds()) + staking_config.unbonding_period,
+    };
+    store_unbonding_entry(deps.storage, user, &entry)?;
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.total_unbonding += amount;
+    store_staking_state(deps.storage, &staking_state)?;
+    Ok((
+        vec![],
+        vec![
+            attr("unbonding_id", entry.id.to_string()),
+            attr("release_time", entry.release_time.to_string()),
+        ],
+    ))
+}
+
+// Pays out every unbonding entry of the user past its release time
+pub fn claim_unbonded(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let user = info.sender;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let mut amount = Uint128::zero();
+    for entry in read_unbonding_entries(deps.storage, &user)? {
+        if entry.release_time > current_time {
+            continue;
+        }
+        amount += entry.amount;
+        remove_unbonding_entry(deps.storage, &user, entry.id);
+    }
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "no unbonded tokens to claim",
+        )));
+    }
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.total_unbonding = staking_state.total_unbonding.checked_sub(amount)?;
+    store_staking_state(deps.storage, &staking_state)?;
+
+    let staking_token = read_staking_config(deps.storage)?.staking_token;
     let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: staking_token.to_string(),
         msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: user.clone().to_string(),
+            recipient: user.to_string(),
             amount,
         })?,
         funds: vec![],
     })];
-
     Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "withdraw"),
+        attr("action", "claim_unbonded"),
         attr("user", user.to_string()),
         attr("amount", amount.to_string()),
     ]))
 }
 
+// Drops every unclaimed reward of the user, rewards must be settled up to now before calling it.
+fn _forfeit_all_rewards(
+    deps: DepsMut,
+    env: &Env,
+    account: &Addr,
+) -> Result<Uint128, ContractError> {
+    let forfeited = earned(deps.as_ref(), env.clone(), account.clone())?.earned;
+    let reward_per_token_stored = read_staking_state(deps.storage)?.reward_per_token_stored;
+    store_rewards(deps.storage, account.clone(), &Uint128::zero())?;
+    store_base_rewards(deps.storage, account, &Uint128::zero())?;
+    store_user_reward_per_token_paid(deps.storage, account.clone(), &reward_per_token_stored)?;
+    store_user_updated_at(
+        deps.storage,
+        account.clone(),
+        &Uint128::from(env.block.time.seconds()),
+    )?;
+    for mut reward_token in read_reward_tokens(deps.storage)? {
+        let token_forfeited = token_earned(deps.as_ref(), env, &reward_token, account)?;
+        reward_token.unclaimed = reward_token.unclaimed.saturating_sub(token_forfeited);
+        store_reward_token(deps.storage, &reward_token)?;
+        store_user_token_rewards(deps.storage, &reward_token.token, account, &Uint128::zero())?;
+        store_user_token_reward_per_token_paid(
+            deps.storage,
+            &reward_token.token,
+            account,
+            &reward_token.reward_per_token_stored,
+        )?;
+    }
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.total_accrued = staking_state.total_accrued.saturating_sub(forfeited);
+    store_staking_state(deps.storage, &staking_state)?;
+    Ok(forfeited)
+}
+
+// Sets the user's share of the rewards to its base balance, which needs no boost query
+fn _set_base_reward_balance(deps: DepsMut, account: &Addr) -> Result<(), ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let mut staking_state = read_staking_state(deps.storage)?;
+    let weighted_balance = weighted_balance_of(deps.storage, account);
+    match staking_config.boost_mode {
+        BoostMode::LockTier => {
+            let old_boosted_balance = read_boosted_balance_of(deps.storage, account.clone());
+            staking_state.boosted_supply = staking_state
+                .boosted_supply
+                .checked_sub(old_boosted_balance)?
+                .checked_add(weighted_balance)?;
+            store_boosted_balance_of(deps.storage, account.clone(), &weighted_balance)?;
+        }
+        BoostMode::WorkingBalance => {
+            let working_balance = weighted_balance.multiply_ratio(TOKENLESS_PRODUCTION, 100u128);
+            let old_working_balance = read_working_balance_of(deps.storage, account.clone());
+            staking_state.working_supply = staking_state
+                .working_supply
+                .checked_sub(old_working_balance)?
+                .checked_add(working_balance)?;
+            store_working_balance_of(deps.storage, account.clone(), &working_balance)?;
+        }
+    }
+    store_staking_state(deps.storage, &staking_state)?;
+    Ok(())
+}
+
+// Withdraws without touching boost or fund, so stakers can leave even if those contracts fail.
+// Every unclaimed reward is forfeited. Before a shutdown still locked positions stay and the
+// unbonding period applies, after it everything staked or unbonding is paid out immediately.
+pub fn emergency_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let user = info.sender;
+    // only settles the pool, which reads nothing but this contract's storage
+    _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
+    let forfeited = _forfeit_all_rewards(deps.branch(), &env, &user)?;
+
+    let staking_config = read_staking_config(deps.storage)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let mut locked_amount = Uint128::zero();
+    let mut position_amount = Uint128::zero();
+    let mut released_bonus = Uint128::zero();
+    for position in read_all_positions(deps.storage, &user)? {
+        position_amount += position.amount;
+        if !staking_config.shutdown && position.unlock_time > current_time {
+            locked_amount += position.amount;
+            continue;
+        }
+        released_bonus += position.bonus;
+        remove_position(deps.storage, &user, position.id);
+    }
+    let balance_of = read_balance_of(deps.storage, user.clone());
+    let amount = balance_of.checked_sub(locked_amount)?;
+    // receipts only cover the stake outside of positions
+    let receipt_amount = balance_of.checked_sub(position_amount)?;
+
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.total_supply = staking_state.total_supply.checked_sub(amount)?;
+    staking_state.total_lock_bonus = staking_state.total_lock_bonus.checked_sub(released_bonus)?;
+    let lock_bonus = read_lock_bonus_of(deps.storage, &user).checked_sub(released_bonus)?;
+    store_lock_bonus_of(deps.storage, &user, &lock_bonus)?;
+    store_balance_of(deps.storage, user.clone(), &locked_amount)?;
+
+    let mut unbonded = Uint128::zero();
+    if staking_config.shutdown {
+        for entry in read_unbonding_entries(deps.storage, &user)? {
+            unbonded += entry.amount;
+            remove_unbonding_entry(deps.storage, &user, entry.id);
+        }
+        staking_state.total_unbonding = staking_state.total_unbonding.checked_sub(unbonded)?;
+    }
+    store_staking_state(deps.storage, &staking_state)?;
+    _set_base_reward_balance(deps.branch(), &user)?;
+
+    if amount.is_zero() && unbonded.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "nothing to withdraw",
+        )));
+    }
+    let receipt_messages = if receipt_amount.is_zero() {
+        vec![]
+    } else {
+        _receipt_msgs(
+            &staking_config,
+            ReceiptExecuteMsg::Burn {
+                owner: user.to_string(),
+                amount: receipt_amount,
+            },
+        )?
+    };
+    let (messages, unbonding_attrs) = if staking_config.shutdown {
+        let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: staking_config.staking_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: user.to_string(),
+                amount: amount + unbonded,
+            })?,
+            funds: vec![],
+        })];
+        (messages, vec![attr("unbonded", unbonded.to_string())])
+    } else {
+        _unbond(deps, &env, &user, amount)?
+    };
+
+    Ok(Response::new()
+        .add_messages(receipt_messages)
+        .add_messages(messages)
+        .add_attributes(vec![
+            attr("action", "emergency_withdraw"),
+            attr("user", user.to_string()),
+            attr("amount", amount.to_string()),
+            attr("forfeited", forfeited.to_string()),
+        ])
+        .add_attributes(unbonding_attrs))
+}
+
+// Ends every reward stream now and drops the scheduled epochs, the pool can't be restarted
+pub fn shutdown(mut deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut staking_config = read_staking_config(deps.storage)?;
+    // the factory that instantiated the pool shuts it down when it's deprecated
+    if info.sender != staking_config.gov && Some(info.sender.clone()) != staking_config.factory {
+        return Err(ContractError::Unauthorized {});
+    }
+    if staking_config.shutdown {
+        return Err(ContractError::PoolShutdown {});
+    }
+    _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
+
+    let current_time = Uint128::from(env.block.time.seconds());
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.finish_at = staking_state.finish_at.min(current_time);
+    staking_state.reward_rate = Uint256::zero();
+    staking_state.carried_until = staking_state.carried_until.min(current_time);
+    staking_state.carried_rate = Uint256::zero();
+    store_staking_state(deps.storage, &staking_state)?;
+    for schedule in read_reward_schedules(deps.storage)? {
+        remove_reward_schedule(deps.storage, schedule.id);
+    }
+    for mut reward_token in read_reward_tokens(deps.storage)? {
+        reward_token.finish_at = reward_token.finish_at.min(current_time);
+        reward_token.reward_rate = Uint256::zero();
+        store_reward_token(deps.storage, &reward_token)?;
+    }
+
+    staking_config.shutdown = true;
+    store_staking_config(deps.storage, &staking_config)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "shutdown"),
+        attr("finish_at", staking_state.finish_at.to_string()),
+    ]))
+}
+
+// Withdraws a whole position once its lock has expired
+pub fn withdraw_position(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let user = info.sender;
+    // settles the rewards and drops the bonus of the position if it has expired
+    _update_reward(deps.branch(), env.clone(), user.clone())?;
+    let position = read_position(deps.storage, &user, id)
+        .map_err(|_| StdError::generic_err("position not found"))?;
+    if Uint128::from(env.block.time.seconds()) < position.unlock_time {
+        return Err(ContractError::Std(StdError::generic_err(
+            "position still locked",
+        )));
+    }
+
+    let balance_of = read_balance_of(deps.storage, user.clone()).checked_sub(position.amount)?;
+    let mut staking_state = read_staking_state(deps.storage)?;
+    staking_state.total_supply = staking_state.total_supply.checked_sub(position.amount)?;
+
+    remove_position(deps.storage, &user, id);
+    store_balance_of(deps.storage, user.clone(), &balance_of)?;
+    store_staking_state(deps.storage, &staking_state)?;
+    _update_reward_balance(deps.branch(), env.clone(), user.clone())?;
+
+    let (messages, unbonding_attrs) = _unbond(deps, &env, &user, position.amount)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![
+            attr("action", "withdraw_position"),
+            attr("user", user.to_string()),
+            attr("position_id", id.to_string()),
+            attr("amount", position.amount.to_string()),
+        ])
+        .add_attributes(unbonding_attrs))
+}
+
+pub fn set_lock_tiers(
+    deps: DepsMut,
+    info: MessageInfo,
+    lock_tiers: Vec<LockTierItem>,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    if lock_tiers.len() > MAX_LOCK_TIERS {
+        return Err(ContractError::Std(StdError::generic_err(
+            "too many lock tiers",
+        )));
+    }
+    let mut tiers: Vec<LockTier> = vec![];
+    for lock_tier in lock_tiers {
+        if lock_tier.duration.is_zero() {
+            return Err(ContractError::InvalidDuration {});
+        }
+        if lock_tier.multiplier < Uint128::new(BASE_RATE_8)
+            || lock_tier.multiplier > Uint128::new(MAX_LOCK_MULTIPLIER)
+        {
+            return Err(ContractError::Std(StdError::generic_err(
+                "invalid lock multiplier",
+            )));
+        }
+        if tiers.iter().any(|tier| tier.duration == lock_tier.duration) {
+            return Err(ContractError::Std(StdError::generic_err(
+                "duplicate lock duration",
+            )));
+        }
+        tiers.push(LockTier {
+            duration: lock_tier.duration,
+            multiplier: lock_tier.multiplier,
+        });
+    }
+    store_lock_tiers(deps.storage, &tiers)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_lock_tiers"),
+        attr("lock_tiers", tiers.len().to_string()),
+    ]))
+}
+
 /// ## Description
 /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
 /// If the template is not found in the received message, then an [`ContractError`] is returned,
@@ -225,7 +907,16 @@ pub fn receive_cw20(
                     "not staking token",
                 )));
             }
-            stake(deps, env, msg_sender, cw20_msg.amount)
+            stake(deps, env, msg_sender, cw20_msg.amount, None)
+        }
+        Ok(Cw20HookMsg::StakeLocked { duration }) => {
+            let staking_config = read_staking_config(deps.storage)?;
+            if contract_addr.ne(&staking_config.staking_token) {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "not staking token",
+                )));
+            }
+            stake(deps, env, msg_sender, cw20_msg.amount, Some(duration))
         }
         Err(_) => Err(ContractError::Std(StdError::generic_err(
             "data should be given",
@@ -233,11 +924,25 @@ pub fn receive_cw20(
     }
 }
 
-// Allows users to claim their earned rewards
-pub fn get_reward(
+// Allows users to claim their earned rewards once their boost lock is unlocked
+pub fn get_reward(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    _get_reward(deps, env, info, false)
+}
+
+// Allows users to claim before their boost lock is unlocked, forfeiting the boost portion of the rewards
+pub fn get_reward_early(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    _get_reward(deps, env, info, true)
+}
+
+fn _get_reward(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    early: bool,
 ) -> Result<Response, ContractError> {
     _update_reward(deps.branch(), env.clone(), info.sender.clone())?;
 
@@ -254,17 +959,33 @@ pub fn get_reward(
         }))?;
     let unlock_time = unlock_time_response.unlock_time;
     let current_time = Uint128::from(env.block.time.seconds());
-    if current_time < unlock_time {
+    let locked = current_time < unlock_time;
+    if locked && !early {
         return Err(ContractError::Std(StdError::generic_err(
             "not unlocked yet",
         )));
     }
-    let reward = read_rewards(deps.storage, sender.clone());
+    let earned = read_rewards(deps.storage, sender.clone());
+    let base_earned = read_base_rewards(deps.storage, &sender).min(earned);
+    // claiming early only pays out what was earned without boost
+    let claimable = if locked { base_earned } else { earned };
+    let forfeited = earned - claimable;
+    // never pay out more than was notified, whatever is over the budget stays owed
+    let mut staking_state = read_staking_state(deps.storage)?;
+    let budget = staking_state
+        .total_notified
+        .saturating_sub(staking_state.total_claimed);
+    let reward = claimable.min(budget);
 
     let mut sub_msgs = vec![];
+    if reward > Uint128::zero() || forfeited > Uint128::zero() {
+        let owed = claimable - reward;
+        store_rewards(deps.storage, sender.clone(), &owed)?;
+        store_base_rewards(deps.storage, &sender, &base_earned.min(owed))?;
+        staking_state.total_claimed += reward;
+        store_staking_state(deps.storage, &staking_state)?;
+    }
     if reward > Uint128::zero() {
-        store_rewards(deps.storage, sender.clone(), &Uint128::zero())?;
-
         //Unified mint function for ve token, including reward updates
         let ve_mint_msg = fund::msg::ExecuteMsg::VeFundMint {
             user: sender.clone(),
@@ -299,6 +1020,9 @@ pub fn get_reward(
         // });
         // sub_msgs.push(mint_sub_msg);
     }
+    if forfeited > Uint128::zero() {
+        sub_msgs.extend(_forfeit(deps.branch(), &env, forfeited)?);
+    }
 
     Ok(Response::new()
         .add_submessages(sub_msgs)
@@ -306,16 +1030,92 @@ pub fn get_reward(
             attr("action", "get_reward"),
             attr("sender", sender),
             attr("reward", reward),
+            attr("forfeited", forfeited),
         ]))
 }
 
+// Hands the boost portion forfeited by an early claim back to the pool or the reward controller
+fn _forfeit(deps: DepsMut, env: &Env, amount: Uint128) -> Result<Vec<SubMsg>, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let mut staking_state = read_staking_state(deps.storage)?;
+    // the forfeited rewards were accrued but will never be claimed by the user
+    staking_state.total_accrued = staking_state.total_accrued.saturating_sub(amount);
+    let mut sub_msgs = vec![];
+    match staking_config.forfeit_mode {
+        // a shut down pool accrues nothing anymore, the forfeited rewards are left unspent
+        ForfeitMode::Redistribute if staking_config.shutdown => {}
+        ForfeitMode::Redistribute => {
+            let current_time = Uint128::from(env.block.time.seconds());
+            // the next epoch waiting to start pays them out on top of its amount
+            if let Some(mut schedule) = read_reward_schedules(deps.storage)?.into_iter().next() {
+                schedule.redistributed += amount;
+                store_reward_schedule(deps.storage, &schedule)?;
+            } else if current_time < staking_state.finish_at {
+                // or they are spread over the rest of the running period
+                let remaining_time = staking_state.finish_at - current_time;
+                let remaining_rewards = Uint256::from(remaining_time)
+                    .multiply_ratio(staking_state.reward_rate, Uint256::from(BASE_RATE_18));
+                staking_state.reward_rate = (Uint256::from(amount).add(remaining_rewards))
+                    .multiply_ratio(Uint256::from(BASE_RATE_18), Uint256::from(remaining_time));
+                staking_state.updated_at = current_time;
+            } else if current_time < staking_state.carried_until {
+                // or over the rest of the leftover carried past the last epoch
+                let remaining_time = staking_state.carried_until - current_time;
+                let remaining_rewards = Uint256::from(remaining_time)
+                    .multiply_ratio(staking_state.carried_rate, Uint256::from(BASE_RATE_18));
+                staking_state.carried_rate = (Uint256::from(amount).add(remaining_rewards))
+                    .multiply_ratio(Uint256::from(BASE_RATE_18), Uint256::from(remaining_time));
+                staking_state.updated_at = current_time;
+            } else {
+                // or over a new period when nothing runs anymore
+                staking_state.reward_rate = Uint256::from(amount).multiply_ratio(
+                    Uint256::from(BASE_RATE_18),
+                    Uint256::from(staking_state.duration),
+                );
+                staking_state.started_at = current_time;
+                staking_state.finish_at = current_time + staking_state.duration;
+                staking_state.scheduled = false;
+                staking_state.updated_at = current_time;
+            }
+        }
+        ForfeitMode::ReturnToController => {
+            let budget = staking_state
+                .total_notified
+                .saturating_sub(staking_state.total_claimed);
+            let amount = amount.min(budget);
+            if !amount.is_zero() {
+                staking_state.total_claimed += amount;
+                sub_msgs.push(SubMsg::new(WasmMsg::Execute {
+                    contract_addr: staking_config.fund.to_string(),
+                    msg: to_binary(&fund::msg::ExecuteMsg::VeFundMint {
+                        user: staking_config.reward_controller_addr,
+                        amount,
+                    })?,
+                    funds: vec![],
+                }));
+            }
+        }
+    }
+    store_staking_state(deps.storage, &staking_state)?;
+    Ok(sub_msgs)
+}
+
 // Allows the owner to set the mining rewards.
 pub fn notify_reward_amount(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    token: Option<Addr>,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    if read_staking_config(deps.storage)?.shutdown {
+        return Err(ContractError::PoolShutdown {});
+    }
+    if let Some(token) = token {
+        if token != read_staking_config(deps.storage)?.rewards_token {
+            return notify_token_reward_amount(deps, env, info, token, amount);
+        }
+    }
     _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
     if !amount.is_zero() {
         let staking_config = read_staking_config(deps.storage)?;
@@ -326,9 +1126,21 @@ pub fn notify_reward_amount(
 
         let current_time = Uint128::from(env.block.time.seconds());
         let mut staking_state = read_staking_state(deps.storage)?;
+        if staking_state.scheduled && current_time < staking_state.finish_at {
+            return Err(ContractError::Std(StdError::generic_err(
+                "a scheduled epoch is running, schedule the rewards after it instead",
+            )));
+        }
+        // the leftover carried past an epoch joins the new period like the running one does
+        let carried_rewards =
+            Uint256::from(staking_state.carried_until.saturating_sub(current_time))
+                .multiply_ratio(staking_state.carried_rate, Uint256::from(BASE_RATE_18));
+        staking_state.carried_rate = Uint256::zero();
+        staking_state.carried_until = current_time;
+        let amount_with_carried = Uint256::from(amount) + carried_rewards;
         if current_time >= staking_state.finish_at {
             // staking_state.reward_rate = amount / staking_state.duration;
-            staking_state.reward_rate = Uint256::from(amount).multiply_ratio(
+            staking_state.reward_rate = amount_with_carried.multiply_ratio(
                 Uint256::from(BASE_RATE_18),
                 Uint256::from(staking_state.duration),
             );
@@ -337,7 +1149,7 @@ pub fn notify_reward_amount(
             let remaining_rewards = Uint256::from(staking_state.finish_at - current_time)
                 .multiply_ratio(staking_state.reward_rate, Uint256::from(BASE_RATE_18));
             // staking_state.reward_rate = (amount + remaining_rewards) / staking_state.duration;
-            staking_state.reward_rate = (Uint256::from(amount).add(remaining_rewards))
+            staking_state.reward_rate = (amount_with_carried.add(remaining_rewards))
                 .multiply_ratio(
                     Uint256::from(BASE_RATE_18),
                     Uint256::from(staking_state.duration),
@@ -349,8 +1161,11 @@ pub fn notify_reward_amount(
             )));
         }
 
+        staking_state.started_at = current_time;
         staking_state.finish_at = current_time + staking_state.duration;
         staking_state.updated_at = current_time;
+        staking_state.scheduled = false;
+        staking_state.total_notified += amount;
 
         store_staking_state(deps.storage, &staking_state)?;
     }
@@ -361,6 +1176,371 @@ pub fn notify_reward_amount(
     ]))
 }
 
+// Queues rewards for a future epoch, started by _update_reward once its start time has passed
+pub fn schedule_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start: Uint128,
+    end: Uint128,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.reward_controller_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+    if staking_config.shutdown {
+        return Err(ContractError::PoolShutdown {});
+    }
+    let current_time = Uint128::from(env.block.time.seconds());
+    if start < current_time {
+        return Err(ContractError::Std(StdError::generic_err(
+            "schedule start is in the past",
+        )));
+    }
+    if end <= start {
+        return Err(ContractError::InvalidDuration {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("amount = 0")));
+    }
+    let reward_rate = Uint256::from(amount)
+        .multiply_ratio(Uint256::from(BASE_RATE_18), Uint256::from(end - start));
+    if reward_rate.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "reward rate is zero",
+        )));
+    }
+    if read_reward_schedules(deps.storage)?.len() >= MAX_REWARD_SCHEDULES {
+        return Err(ContractError::Std(StdError::generic_err(
+            "too many reward schedules",
+        )));
+    }
+
+    let id = next_reward_schedule_id(deps.storage)?;
+    store_reward_schedule(
+        deps.storage,
+        &RewardSchedule {
+            id,
+            start,
+            end,
+            amount,
+            redistributed: Uint128::zero(),
+        },
+    )?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "schedule_rewards"),
+        attr("id", id.to_string()),
+        attr("start", start.to_string()),
+        attr("end", end.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+// Pulls a partner reward token from its distributor and spreads it over the token's duration
+fn notify_token_reward_amount(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
+    let mut reward_token = read_reward_token(deps.storage, &token)
+        .map_err(|_| StdError::generic_err("reward token not found"))?;
+    if info.sender != reward_token.distributor {
+        return Err(ContractError::Unauthorized {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("amount = 0")));
+    }
+
+    let current_time = Uint128::from(env.block.time.seconds());
+    if current_time >= reward_token.finish_at {
+        reward_token.reward_rate = Uint256::from(amount).multiply_ratio(
+            Uint256::from(BASE_RATE_18),
+            Uint256::from(reward_token.duration),
+        );
+    } else {
+        let remaining_rewards = Uint256::from(reward_token.finish_at - current_time)
+            .multiply_ratio(reward_token.reward_rate, Uint256::from(BASE_RATE_18));
+        reward_token.reward_rate = (Uint256::from(amount).add(remaining_rewards)).multiply_ratio(
+            Uint256::from(BASE_RATE_18),
+            Uint256::from(reward_token.duration),
+        );
+    }
+    if reward_token.reward_rate.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "reward rate is zero",
+        )));
+    }
+    reward_token.finish_at = current_time + reward_token.duration;
+    reward_token.updated_at = current_time;
+    store_reward_token(deps.storage, &reward_token)?;
+
+    let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: info.sender.to_string(),
+            recipient: env.contract.address.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })];
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "notify_reward_amount"),
+        attr("sender", info.sender),
+        attr("token", token.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+// Claims the ve_seilor rewards like get_reward, then pays out every partner reward token
+pub fn get_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let response = get_reward(deps.branch(), env, info.clone())?;
+
+    let sender = info.sender;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attrs = vec![attr("action", "get_rewards")];
+    for mut reward_token in read_reward_tokens(deps.storage)? {
+        let reward = read_user_token_rewards(deps.storage, &reward_token.token, &sender);
+        if reward.is_zero() {
+            continue;
+        }
+        reward_token.unclaimed = reward_token.unclaimed.saturating_sub(reward);
+        store_reward_token(deps.storage, &reward_token)?;
+        store_user_token_rewards(deps.storage, &reward_token.token, &sender, &Uint128::zero())?;
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: reward_token.token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: reward,
+            })?,
+            funds: vec![],
+        }));
+        attrs.push(attr(reward_token.token.to_string(), reward.to_string()));
+    }
+    Ok(response.add_messages(messages).add_attributes(attrs))
+}
+
+// Rescues tokens sent to the pool by mistake, keeping back what is owed to stakers.
+pub fn rescue_tokens(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    // settles the partner rewards accrued so far into what is reserved for stakers
+    _update_reward(deps.branch(), env.clone(), Addr::unchecked(""))?;
+    let reserved = reserved_balance(deps.as_ref(), &env, &asset)?;
+    Ok(common::rescue::rescue_tokens(
+        deps.as_ref(),
+        &env,
+        info.sender,
+        asset,
+        amount,
+        recipient,
+        reserved,
+    )?)
+}
+
+pub fn add_reward_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: Addr,
+    distributor: Addr,
+    duration: Uint128,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    deps.api.addr_validate(token.as_str())?;
+    deps.api.addr_validate(distributor.as_str())?;
+    if duration.is_zero() {
+        return Err(ContractError::InvalidDuration {});
+    }
+    // staked tokens must never be paid out as rewards
+    if token == staking_config.staking_token || token == staking_config.rewards_token {
+        return Err(ContractError::Std(StdError::generic_err(
+            "invalid reward token",
+        )));
+    }
+    let reward_tokens = read_reward_tokens(deps.storage)?;
+    if reward_tokens
+        .iter()
+        .any(|reward_token| reward_token.token == token)
+    {
+        return Err(ContractError::Std(StdError::generic_err(
+            "reward token already exists",
+        )));
+    }
+    if reward_tokens.len() >= MAX_REWARD_TOKENS {
+        return Err(ContractError::Std(StdError::generic_err(
+            "too many reward tokens",
+        )));
+    }
+
+    let current_time = Uint128::from(env.block.time.seconds());
+    let reward_token = RewardToken {
+        token: token.clone(),
+        distributor: distributor.clone(),
+        duration,
+        finish_at: current_time,
+        updated_at: current_time,
+        reward_rate: Uint256::zero(),
+        reward_per_token_stored: Uint128::zero(),
+        unclaimed: Uint128::zero(),
+    };
+    store_reward_token(deps.storage, &reward_token)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_reward_token"),
+        attr("token", token.to_string()),
+        attr("distributor", distributor.to_string()),
+        attr("duration", duration.to_string()),
+    ]))
+}
+
+pub fn update_reward_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: Addr,
+    distributor: Option<Addr>,
+    duration: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if info.sender != staking_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut reward_token = read_reward_token(deps.storage, &token)
+        .map_err(|_| StdError::generic_err("reward token not found"))?;
+
+    let mut attrs = vec![
+        attr("action", "update_reward_token"),
+        attr("token", token.to_string()),
+    ];
+    if let Some(distributor) = distributor {
+        deps.api.addr_validate(distributor.as_str())?;
+        reward_token.distributor = distributor.clone();
+        attrs.push(attr("distributor", distributor.to_string()));
+    }
+    if let Some(duration) = duration {
+        if duration.is_zero() {
+            return Err(ContractError::InvalidDuration {});
+        }
+        if reward_token.finish_at > Uint128::from(env.block.time.seconds()) {
+            return Err(ContractError::Std(StdError::generic_err(
+                "duration can only be updated after the end of the current period",
+            )));
+        }
+        reward_token.duration = duration;
+        attrs.push(attr("duration", duration.to_string()));
+    }
+    store_reward_token(deps.storage, &reward_token)?;
+    Ok(Response::new().add_attributes(attrs))
+}
+
+// Mints or burns receipts, nothing to do when the pool has no receipt token
+fn _receipt_msgs(
+    staking_config: &StakingConfig,
+    msg: ReceiptExecuteMsg,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut messages = vec![];
+    if let Some(receipt_token) = &staking_config.receipt_token {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: receipt_token.to_string(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        }));
+    }
+    Ok(messages)
+}
+
+// Stores the receipt token instantiated along with the pool
+pub fn receipt_token_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res =
+        parse_reply_instantiate_data(msg).map_err(|e| StdError::generic_err(e.to_string()))?;
+    let receipt_token = deps.api.addr_validate(&res.contract_address)?;
+    let mut staking_config = read_staking_config(deps.storage)?;
+    staking_config.receipt_token = Some(receipt_token.clone());
+    store_staking_config(deps.storage, &staking_config)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_receipt_token"),
+        attr("receipt_token", receipt_token.to_string()),
+    ]))
+}
+
+// Moves unlocked stake along with a receipt transfer, both sides are settled at their old share
+pub fn move_stake(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: Addr,
+    to: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let staking_config = read_staking_config(deps.storage)?;
+    if staking_config.receipt_token != Some(info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if to == env.contract.address || to == info.sender {
+        return Err(ContractError::Std(StdError::generic_err(
+            "cannot move stake to the pool or its receipt token",
+        )));
+    }
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("amount = 0")));
+    }
+    _update_reward(deps.branch(), env.clone(), from.clone())?;
+    _update_reward(deps.branch(), env.clone(), to.clone())?;
+
+    let from_balance = read_balance_of(deps.storage, from.clone());
+    let locked_amount: Uint128 = read_all_positions(deps.storage, &from)?
+        .iter()
+        .map(|position| position.amount)
+        .sum();
+    if amount > from_balance.checked_sub(locked_amount)? {
+        return Err(ContractError::Std(StdError::generic_err(
+            "insufficient unlocked balance",
+        )));
+    }
+    store_balance_of(deps.storage, from.clone(), &(from_balance - amount))?;
+    let to_balance = read_balance_of(deps.storage, to.clone());
+    store_balance_of(deps.storage, to.clone(), &(to_balance + amount))?;
+    _update_reward_balance(deps.branch(), env.clone(), from.clone())?;
+    _update_reward_balance(deps.branch(), env, to.clone())?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "move_stake"),
+        attr("from", from.to_string()),
+        attr("to", to.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+// Settles a user's rewards and recomputes their share, e.g. after their ve_seilor changed or their lock expired
+pub fn kick(mut deps: DepsMut, env: Env, user: Addr) -> Result<Response, ContractError> {
+    _update_reward(deps.branch(), env.clone(), user.clone())?;
+    let reward_balance = reward_balance_of(deps.as_ref(), user.clone())?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "kick"),
+        attr("user", user.to_string()),
+        attr("reward_balance", reward_balance.to_string()),
+    ]))
+}
+
 pub fn set_gov(deps: DepsMut, info: MessageInfo, gov: Addr) -> Result<Response, ContractError> {
     let mut staking_config = read_staking_config(deps.storage)?;
     if staking_config.gov != info.sender {
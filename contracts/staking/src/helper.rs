@@ -1,4 +1,5 @@
 pub const BASE_RATE_8: u128 = 100000000u128;
 // pub const BASE_RATE_12: u128 = 1000000000000u128;
 pub const BASE_RATE_18: u128 = 1000000000000000000u128;
-pub const BASE_RATE_26: u128 = 100000000000000000000000000u128;
+pub const SECONDS_PER_YEAR: u128 = 31536000u128;
+// pub const BASE_RATE_26: u128 = 100000000000000000000000000u128;
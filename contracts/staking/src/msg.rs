@@ -1,3 +1,4 @@
+pub use common::asset::AssetInfo;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint256};
 use cw20::Cw20ReceiveMsg;
@@ -9,6 +10,9 @@ pub struct UpdateStakingConfigStruct {
     pub boost: Option<Addr>,
     pub fund: Option<Addr>,
     pub reward_controller_addr: Option<Addr>,
+    /// Only applies to withdrawals made after the update.
+    pub unbonding_period: Option<Uint128>,
+    pub forfeit_mode: Option<ForfeitMode>,
 }
 
 #[cw_serde]
@@ -26,6 +30,33 @@ pub struct EarnedResponse {
     pub earned: Uint128,
 }
 
+#[cw_serde]
+pub struct TokenEarned {
+    pub token: Addr,
+    pub earned: Uint128,
+}
+
+#[cw_serde]
+pub struct EarnedAllResponse {
+    pub rewards: Vec<TokenEarned>,
+}
+
+#[cw_serde]
+pub struct RewardTokenResponse {
+    pub token: Addr,
+    pub distributor: Addr,
+    pub duration: Uint128,
+    pub finish_at: Uint128,
+    pub updated_at: Uint128,
+    pub reward_rate: Uint256,
+    pub reward_per_token_stored: Uint128,
+}
+
+#[cw_serde]
+pub struct RewardTokensResponse {
+    pub reward_tokens: Vec<RewardTokenResponse>,
+}
+
 #[cw_serde]
 pub struct GetBoostResponse {
     pub boost: Uint128,
@@ -46,10 +77,148 @@ pub struct BalanceOfResponse {
     pub balance_of: Uint128,
 }
 
+#[cw_serde]
+pub struct WorkingBalanceOfResponse {
+    pub working_balance: Uint128,
+}
+
+#[cw_serde]
+pub struct BoostedBalanceOfResponse {
+    pub boosted_balance: Uint128,
+}
+
+#[cw_serde]
+pub struct BaseEarnedResponse {
+    pub base_earned: Uint128,
+}
+
+#[cw_serde]
+pub struct LockTierItem {
+    pub duration: Uint128,
+    /// Multiplier on the reward weight of positions locked for `duration`, 1e8 = 1x.
+    pub multiplier: Uint128,
+}
+
+#[cw_serde]
+pub struct LockTiersResponse {
+    pub lock_tiers: Vec<LockTierItem>,
+}
+
+#[cw_serde]
+pub struct PositionResponse {
+    pub id: u64,
+    pub amount: Uint128,
+    pub duration: Uint128,
+    pub multiplier: Uint128,
+    pub start_time: Uint128,
+    pub unlock_time: Uint128,
+    /// Extra reward weight of the position, 0 once the lock has expired.
+    pub bonus: Uint128,
+}
+
+#[cw_serde]
+pub struct UserPositionsResponse {
+    pub positions: Vec<PositionResponse>,
+}
+
+#[cw_serde]
+pub struct UnbondingRequestResponse {
+    pub id: u64,
+    pub amount: Uint128,
+    pub release_time: Uint128,
+}
+
+#[cw_serde]
+pub struct UnbondingRequestsResponse {
+    pub requests: Vec<UnbondingRequestResponse>,
+}
+
+#[cw_serde]
+pub struct ActiveRewardPeriodResponse {
+    pub start: Uint128,
+    pub end: Uint128,
+    pub reward_rate: Uint256,
+    /// Rate of the leftover of the period running when the epoch started, paid next to it.
+    pub carried_rate: Uint256,
+    /// When the carried leftover runs out.
+    pub carried_until: Uint128,
+}
+
+#[cw_serde]
+pub struct ScheduledRewardsResponse {
+    pub id: u64,
+    pub start: Uint128,
+    pub end: Uint128,
+    pub amount: Uint128,
+    /// Rewards forfeited by early claims, paid out with the epoch on top of `amount`.
+    pub redistributed: Uint128,
+}
+
+#[cw_serde]
+pub struct RewardScheduleResponse {
+    /// The reward period running now, if any.
+    pub active: Option<ActiveRewardPeriodResponse>,
+    /// Scheduled epochs not started yet, in the order they start.
+    pub upcoming: Vec<ScheduledRewardsResponse>,
+}
+
+#[cw_serde]
+pub struct PoolAprResponse {
+    /// Rewards emitted per second, without the 1e18 scale of `reward_rate` in the state.
+    pub reward_rate: Uint128,
+    /// Seconds until the running period finishes, 0 once it has.
+    pub remaining_seconds: Uint128,
+    /// Rewards left to emit in the running period.
+    pub remaining_rewards: Uint128,
+    /// The supply rewards are shared by, boosted or working supply depending on the boost mode.
+    pub reward_supply: Uint128,
+    /// Yearly rewards per staked token at 1x boost, scaled by 1e18. 0 when nothing is staked
+    /// or no period is running.
+    pub apr: Uint128,
+}
+
+#[cw_serde]
+pub struct ProjectedEarningsResponse {
+    /// Rewards earned so far and not claimed yet.
+    pub earned: Uint128,
+    /// Rewards earned on top of `earned` over the projected seconds.
+    pub projected: Uint128,
+    /// The boost the account's rewards are currently weighted with, 1x = 1e8.
+    pub boost: Uint128,
+    /// Yearly rewards per staked token of the account with its boost, scaled by 1e18.
+    pub apr: Uint128,
+}
+
+/// How the boost contract weighs a staker's rewards.
+#[cw_serde]
+#[derive(Default)]
+pub enum BoostMode {
+    /// Rewards are shared by balances multiplied by the mining boost of the user's boost lock tier.
+    #[default]
+    LockTier,
+    /// Rewards are shared by working balances derived from the user's ve_seilor share (Curve style).
+    WorkingBalance,
+}
+
+/// Where the boost portion of the rewards forfeited by `GetRewardEarly` goes.
+#[cw_serde]
+#[derive(Default)]
+pub enum ForfeitMode {
+    /// Added back to the current reward period, shared by all stakers.
+    #[default]
+    Redistribute,
+    /// Minted to the reward controller.
+    ReturnToController,
+}
+
 /// This structure describes a CW20 hook message.
 #[cw_serde]
 pub enum Cw20HookMsg {
     Stake {},
+    /// Stakes into a new position locked for the duration of one of the lock tiers.
+    StakeLocked {
+        duration: Uint128,
+    },
     // Withdraw {},
 }
 
@@ -63,6 +232,20 @@ pub struct InstantiateMsg {
     pub reward_controller_addr: Addr,
 
     pub duration: Uint128,
+    pub boost_mode: Option<BoostMode>,
+    pub unbonding_period: Option<Uint128>,
+    /// Instantiates a receipt CW20 minted for unlocked stakes, transferring it moves the stake.
+    pub receipt_token: Option<ReceiptTokenMsg>,
+    /// The factory instantiating the pool, it can shut the pool down next to gov.
+    pub factory: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ReceiptTokenMsg {
+    pub code_id: u64,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
 }
 
 #[cw_serde]
@@ -75,17 +258,82 @@ pub enum ExecuteMsg {
     UpdateStakingState {
         duration: Uint128,
     },
+    /// Fails while the user's boost lock has not been unlocked yet.
     GetReward {},
+    /// Claims before the boost lock has been unlocked, only the rewards the user would have earned without boost
+    /// are paid out and the rest is forfeited. Same as `GetReward` once unlocked.
+    GetRewardEarly {},
+    /// Withdraws from the unlocked balance, tokens in positions are withdrawn with `WithdrawPosition`.
+    /// With an unbonding period the tokens go into the unbonding queue instead of being paid out.
     Withdraw {
         amount: Uint128,
     },
+    /// Withdraws a whole position once its lock has expired, through the unbonding queue like `Withdraw`.
+    WithdrawPosition {
+        id: u64,
+    },
+    /// Pays out every unbonding entry past its release time.
+    ClaimUnbonded {},
+    /// Withdraws the unlocked balance without querying boost or fund, forfeiting every unclaimed reward.
+    /// Once the pool is shut down it also withdraws locked positions and the unbonding queue, paid out immediately.
+    EmergencyWithdraw {},
+    /// Stops reward accrual for good and lets emergency withdrawals ignore locks and the unbonding period.
+    Shutdown {},
+    /// Replaces the lock tier table, existing positions keep the multiplier they were staked with.
+    SetLockTiers {
+        lock_tiers: Vec<LockTierItem>,
+    },
+    /// Without a token (or with the rewards token) notifies the ve_seilor rewards, otherwise pulls `amount`
+    /// of a partner reward token from its distributor, which has to approve it first.
     NotifyRewardAmount {
+        token: Option<Addr>,
+        amount: Uint128,
+    },
+    /// Schedules `amount` of ve_seilor rewards over `[start, end)`. The epoch starts lazily once `start` has passed,
+    /// rolling the leftover of the running period into it like `NotifyRewardAmount`.
+    ScheduleRewards {
+        start: Uint128,
+        end: Uint128,
+        amount: Uint128,
+    },
+    /// Claims the ve_seilor rewards and every partner reward token.
+    GetRewards {},
+    /// Sends tokens that ended up in the contract by mistake to `recipient`. Staked and unbonding
+    /// tokens and partner rewards still owed to stakers can't be rescued.
+    RescueTokens {
+        asset: AssetInfo,
         amount: Uint128,
+        recipient: Addr,
+    },
+    /// After a migration from before 0.2.0, adds what the next `limit` stakers had earned to the reward budget.
+    SeedRewardBudget {
+        limit: Option<u32>,
+    },
+    AddRewardToken {
+        token: Addr,
+        distributor: Addr,
+        duration: Uint128,
+    },
+    UpdateRewardToken {
+        token: Addr,
+        distributor: Option<Addr>,
+        duration: Option<Uint128>,
     },
     SetGov {
         gov: Addr,
     },
     AcceptGov {},
+    /// Recomputes a stale working balance or boosted balance, e.g. after the user's lock expired.
+    Kick {
+        user: Addr,
+    },
+    /// Only the receipt token. Moves unlocked stake along with a receipt transfer, settling the
+    /// rewards of both sides first.
+    MoveStake {
+        from: Addr,
+        to: Addr,
+        amount: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -99,6 +347,9 @@ pub enum QueryMsg {
     GetBoost { account: Addr },
     #[returns(EarnedResponse)]
     Earned { account: Addr },
+    /// The part of `Earned` that `GetRewardEarly` pays out before the boost lock is unlocked.
+    #[returns(BaseEarnedResponse)]
+    BaseEarned { account: Addr },
     #[returns(StakingConfigResponse)]
     QueryStakingConfig {},
     #[returns(StakingStateResponse)]
@@ -109,6 +360,35 @@ pub enum QueryMsg {
     GetUserRewardPerTokenPaid { account: Addr },
     #[returns(BalanceOfResponse)]
     BalanceOf { account: Addr },
+    #[returns(WorkingBalanceOfResponse)]
+    WorkingBalanceOf { account: Addr },
+    #[returns(BoostedBalanceOfResponse)]
+    BoostedBalanceOf { account: Addr },
+    #[returns(RewardBudgetResponse)]
+    RewardBudget {},
+    #[returns(RewardScheduleResponse)]
+    RewardSchedule {},
+    #[returns(EarnedAllResponse)]
+    EarnedAll { account: Addr },
+    #[returns(RewardTokensResponse)]
+    RewardTokens {},
+    #[returns(UnbondingRequestsResponse)]
+    UnbondingRequests { account: Addr },
+    #[returns(LockTiersResponse)]
+    LockTiers {},
+    #[returns(UserPositionsResponse)]
+    UserPositions {
+        account: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The veSEILOR rewards of the running period annualized for a staker without boost.
+    #[returns(PoolAprResponse)]
+    PoolApr {},
+    /// The veSEILOR rewards the account earns over the next `seconds` at its current share,
+    /// capped at the end of the running period.
+    #[returns(ProjectedEarningsResponse)]
+    ProjectedEarnings { account: Addr, seconds: Uint128 },
 }
 
 #[cw_serde]
@@ -120,6 +400,12 @@ pub struct StakingConfigResponse {
     pub fund: Addr,
     pub reward_controller_addr: Addr,
     pub new_gov: Option<Addr>,
+    pub boost_mode: BoostMode,
+    pub unbonding_period: Uint128,
+    pub forfeit_mode: ForfeitMode,
+    pub shutdown: bool,
+    pub receipt_token: Option<Addr>,
+    pub factory: Option<Addr>,
 }
 
 #[cw_serde]
@@ -130,6 +416,19 @@ pub struct StakingStateResponse {
     pub reward_rate: Uint256,
     pub reward_per_token_stored: Uint128,
     pub total_supply: Uint128,
+    pub working_supply: Uint128,
+    pub boosted_supply: Uint128,
+    pub total_lock_bonus: Uint128,
+    pub total_unbonding: Uint128,
+    pub carried_rate: Uint256,
+    pub carried_until: Uint128,
+}
+
+#[cw_serde]
+pub struct RewardBudgetResponse {
+    pub total_notified: Uint128,
+    pub total_accrued: Uint128,
+    pub total_claimed: Uint128,
 }
 
 #[cw_serde]
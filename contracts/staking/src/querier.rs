@@ -1,17 +1,27 @@
-use std::ops::{Div, Mul};
-use crate::helper::{BASE_RATE_26, BASE_RATE_8};
+use crate::helper::{BASE_RATE_18, BASE_RATE_8, SECONDS_PER_YEAR};
 use crate::msg::{
-    BalanceOfResponse, EarnedResponse, GetBoostResponse, GetUserRewardPerTokenPaidResponse,
-    GetUserUpdatedAtResponse, LastTimeRewardApplicableResponse, RewardPerTokenResponse,
-    StakingConfigResponse, StakingStateResponse,
+    ActiveRewardPeriodResponse, AssetInfo, BalanceOfResponse, BaseEarnedResponse, BoostMode,
+    BoostedBalanceOfResponse, EarnedAllResponse, EarnedResponse, GetBoostResponse,
+    GetUserRewardPerTokenPaidResponse, GetUserUpdatedAtResponse, LastTimeRewardApplicableResponse,
+    LockTierItem, LockTiersResponse, PoolAprResponse, PositionResponse, ProjectedEarningsResponse,
+    RewardBudgetResponse, RewardPerTokenResponse, RewardScheduleResponse, RewardTokenResponse,
+    RewardTokensResponse, ScheduledRewardsResponse, StakingConfigResponse, StakingStateResponse,
+    TokenEarned, UnbondingRequestResponse, UnbondingRequestsResponse, UserPositionsResponse,
+    WorkingBalanceOfResponse,
 };
 use crate::state::{
-    read_balance_of, read_rewards, read_staking_config, read_staking_state,
-    read_user_reward_per_token_paid, read_user_updated_at,
+    read_balance_of, read_base_rewards, read_boosted_balance_of, read_lock_bonus_of,
+    read_lock_tiers, read_positions, read_reward_schedules, read_reward_tokens, read_rewards,
+    read_staking_config, read_staking_state, read_unbonding_entries,
+    read_user_reward_per_token_paid, read_user_token_reward_per_token_paid,
+    read_user_token_rewards, read_user_updated_at, read_working_balance_of, RewardSchedule,
+    RewardToken, StakingConfig, StakingState,
 };
+use boost::state::TOKENLESS_PRODUCTION;
 use cosmwasm_std::{
-    to_binary, Addr, Deps, Env, QueryRequest, StdResult, Uint128, Uint256, WasmQuery,
+    to_binary, Addr, Deps, Env, QueryRequest, StdResult, Storage, Uint128, Uint256, WasmQuery,
 };
+use std::ops::{Div, Mul};
 use std::str::FromStr;
 
 // Returns the last time the reward was applicable
@@ -20,87 +30,351 @@ pub fn last_time_reward_applicable(
     env: Env,
 ) -> StdResult<LastTimeRewardApplicableResponse> {
     let block_time = Uint128::from(env.block.time.seconds());
-    let finish_at = read_staking_state(deps.storage)?.finish_at;
+    let reward_end = reward_end(&current_staking_state(deps, &env)?);
     Ok(LastTimeRewardApplicableResponse {
-        last_time_reward_applicable: _min(finish_at, block_time),
+        last_time_reward_applicable: _min(reward_end, block_time),
     })
 }
 
 // Calculates and returns the reward per token
 pub fn reward_per_token(deps: Deps, env: Env) -> StdResult<RewardPerTokenResponse> {
-    let staking_state = read_staking_state(deps.storage)?;
-
-    if staking_state.total_supply.is_zero() {
-        return Ok(RewardPerTokenResponse {
-            reward_per_token: staking_state.reward_per_token_stored,
-        });
-    }
+    let staking_config = read_staking_config(deps.storage)?;
+    let staking_state = current_staking_state(deps, &env)?;
+    let block_time = Uint128::from(env.block.time.seconds());
+    let reward_per_token = reward_per_token_at(&staking_config, &staking_state, block_time)?;
+    Ok(RewardPerTokenResponse { reward_per_token })
+}
 
-    let last_time_reward_applicable_response = last_time_reward_applicable(deps, env)?;
-    let last_time_reward_applicable =
-        last_time_reward_applicable_response.last_time_reward_applicable;
-    let reward_rate = staking_state.reward_rate;
-    let updated_at = staking_state.updated_at;
+// Calculates the reward per token at `time` from the rates running since the state's last update
+pub fn reward_per_token_at(
+    staking_config: &StakingConfig,
+    staking_state: &StakingState,
+    time: Uint128,
+) -> StdResult<Uint128> {
+    let reward_supply = reward_supply(staking_config, staking_state);
     let reward_per_token_stored = staking_state.reward_per_token_stored;
+    if reward_supply.is_zero() {
+        return Ok(reward_per_token_stored);
+    }
 
-    // let rewards_256 = reward_rate.multiply_ratio(
-    //     Uint256::from(last_time_reward_applicable - updated_at),
-    //     Uint256::from(BASE_RATE_6),
-    // );
-    let diffTime = last_time_reward_applicable - updated_at;
-    let rewards_256 = reward_rate.mul(Uint256::from(diffTime));
-    let add_reward_per_token_256 = rewards_256.div(Uint256::from(staking_state.total_supply));
+    let rewards_256 = rewards_between(staking_state, staking_state.updated_at, time);
+    let add_reward_per_token_256 = rewards_256.div(Uint256::from(reward_supply));
 
     let add_reward_per_token = Uint128::from_str(&add_reward_per_token_256.to_string())?;
-    let reward_per_token = reward_per_token_stored + add_reward_per_token;
-    // println!("rewards_256: {} ,reward_rate:{} ,diffTime:{},reward_per_token_stored:{},total_supply:{},reward_per_token:{}",
-    //          rewards_256, reward_rate, diffTime, reward_per_token_stored, staking_state.total_supply, reward_per_token);
-    Ok(RewardPerTokenResponse { reward_per_token })
+    Ok(reward_per_token_stored + add_reward_per_token)
 }
 
-pub fn get_boost(deps: Deps, account: Addr) -> StdResult<GetBoostResponse> {
+// Rewards (scaled by 1e18) emitted between `from` and `to` by the running period and the carried
+// leftover
+pub fn rewards_between(staking_state: &StakingState, from: Uint128, to: Uint128) -> Uint256 {
+    let period_time = _min(staking_state.finish_at, to).saturating_sub(from);
+    let carried_time = _min(staking_state.carried_until, to).saturating_sub(from);
+    staking_state.reward_rate.mul(Uint256::from(period_time))
+        + staking_state.carried_rate.mul(Uint256::from(carried_time))
+}
+
+// Rewards (scaled by 1e18) emitted per second at `time`
+pub fn reward_rate_at(staking_state: &StakingState, time: Uint128) -> Uint256 {
+    let mut reward_rate = Uint256::zero();
+    if staking_state.finish_at > time {
+        reward_rate += staking_state.reward_rate;
+    }
+    if staking_state.carried_until > time {
+        reward_rate += staking_state.carried_rate;
+    }
+    reward_rate
+}
+
+// When the last reward stream, the running period or the carried leftover, runs out
+pub fn reward_end(staking_state: &StakingState) -> Uint128 {
+    staking_state.finish_at.max(staking_state.carried_until)
+}
+
+// The staking state with the scheduled epochs due by now started, as the next update will store it
+pub fn current_staking_state(deps: Deps, env: &Env) -> StdResult<StakingState> {
     let staking_config = read_staking_config(deps.storage)?;
-    let staking_state = read_staking_state(deps.storage)?;
+    let mut staking_state = read_staking_state(deps.storage)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    for schedule in read_reward_schedules(deps.storage)? {
+        if schedule.start > current_time {
+            break;
+        }
+        start_reward_schedule(&staking_config, &mut staking_state, &schedule)?;
+    }
+    Ok(staking_state)
+}
 
-    let user_updated_at = read_user_updated_at(deps.storage, account.clone());
-    let finish_at = staking_state.finish_at;
+// Settles the streams up to the schedule's start and starts the epoch at its own rate. What is left
+// of the running period keeps paying at its rate next to it
+fn start_reward_schedule(
+    staking_config: &StakingConfig,
+    staking_state: &mut StakingState,
+    schedule: &RewardSchedule,
+) -> StdResult<()> {
+    staking_state.reward_per_token_stored =
+        reward_per_token_at(staking_config, staking_state, schedule.start)?;
+    let (carried_rate, carried_until) = carried_stream(staking_state, schedule.start)?;
+    staking_state.carried_rate = carried_rate;
+    staking_state.carried_until = carried_until;
+    staking_state.reward_rate = Uint256::from(schedule.amount + schedule.redistributed)
+        .multiply_ratio(
+            Uint256::from(BASE_RATE_18),
+            Uint256::from(schedule.end - schedule.start),
+        );
+    staking_state.started_at = schedule.start;
+    staking_state.finish_at = schedule.end;
+    staking_state.updated_at = schedule.start;
+    staking_state.scheduled = true;
+    staking_state.total_notified += schedule.amount;
+    Ok(())
+}
 
-    let msg = boost::msg::QueryMsg::GetUserBoost {
-        user: account.clone(),
-        user_updated_at,
-        finish_at,
-    };
+// The streams still running at `time` as one. When both the running period and an earlier carried
+// leftover are, they are paid at the sum of their rates until their leftovers run out
+fn carried_stream(staking_state: &StakingState, time: Uint128) -> StdResult<(Uint256, Uint128)> {
+    let reward_rate = reward_rate_at(staking_state, time);
+    if reward_rate.is_zero() {
+        return Ok((Uint256::zero(), time));
+    }
+    if staking_state.carried_until <= time {
+        return Ok((reward_rate, staking_state.finish_at));
+    }
+    if staking_state.finish_at <= time {
+        return Ok((reward_rate, staking_state.carried_until));
+    }
+    let leftover = rewards_between(staking_state, time, reward_end(staking_state));
+    let leftover_time = Uint128::from_str(&leftover.div(reward_rate).to_string())?;
+    Ok((reward_rate, time + leftover_time))
+}
 
-    let get_user_boost_res: boost::msg::GetUserBoostResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: staking_config.boost.to_string(),
-            msg: to_binary(&msg)?,
-        }))?;
+// The supply rewards are shared by, the boosted supply or the working supply depending on the boost mode
+pub fn reward_supply(staking_config: &StakingConfig, staking_state: &StakingState) -> Uint128 {
+    match staking_config.boost_mode {
+        BoostMode::LockTier => staking_state.boosted_supply,
+        BoostMode::WorkingBalance => staking_state.working_supply,
+    }
+}
+
+// The user's share of reward_supply
+pub fn reward_balance_of(deps: Deps, account: Addr) -> StdResult<Uint128> {
+    let staking_config = read_staking_config(deps.storage)?;
+    Ok(match staking_config.boost_mode {
+        BoostMode::LockTier => read_boosted_balance_of(deps.storage, account),
+        BoostMode::WorkingBalance => read_working_balance_of(deps.storage, account),
+    })
+}
 
-    let user_boost = get_user_boost_res.user_boost;
-    let boost = Uint128::new(BASE_RATE_8) + user_boost;
+// The staked balance with the lock multipliers of unexpired positions applied, boosts apply on top of it
+pub fn weighted_balance_of(storage: &dyn Storage, account: &Addr) -> Uint128 {
+    read_balance_of(storage, account.clone()) + read_lock_bonus_of(storage, account)
+}
+
+pub fn weighted_supply(staking_state: &StakingState) -> Uint128 {
+    staking_state.total_supply + staking_state.total_lock_bonus
+}
+
+// The boost the user's rewards are currently weighted with, from 1x (1e8) up
+pub fn get_boost(deps: Deps, account: Addr) -> StdResult<GetBoostResponse> {
+    let balance_of = weighted_balance_of(deps.storage, &account);
+    if balance_of.is_zero() {
+        return Ok(GetBoostResponse {
+            boost: Uint128::new(BASE_RATE_8),
+        });
+    }
+    let staking_config = read_staking_config(deps.storage)?;
+    let boost = match staking_config.boost_mode {
+        BoostMode::LockTier => {
+            let boosted_balance = read_boosted_balance_of(deps.storage, account);
+            boosted_balance.multiply_ratio(BASE_RATE_8, balance_of)
+        }
+        // boost = working_balance / (0.4 * balance), from 1x to 2.5x
+        BoostMode::WorkingBalance => {
+            let working_balance = read_working_balance_of(deps.storage, account);
+            working_balance.multiply_ratio(
+                BASE_RATE_8 * 100u128,
+                balance_of * Uint128::new(TOKENLESS_PRODUCTION),
+            )
+        }
+    };
     Ok(GetBoostResponse { boost })
 }
 
+// Rewards accrue to the user's share of the reward supply, which already carries the boost
 pub fn earned(deps: Deps, env: Env, account: Addr) -> StdResult<EarnedResponse> {
     let rewards = read_rewards(deps.storage, account.clone());
-    let balance_of = read_balance_of(deps.storage, account.clone());
-    let reward_per_token_response: RewardPerTokenResponse = reward_per_token(deps, env)?;
-    let reward_per_token = reward_per_token_response.reward_per_token;
-    let user_reward_per_token_paid = read_user_reward_per_token_paid(deps.storage, account.clone());
+    let reward_balance = reward_balance_of(deps, account.clone())?;
+    let reward_per_token = reward_per_token(deps, env)?.reward_per_token;
+    let user_reward_per_token_paid = read_user_reward_per_token_paid(deps.storage, account);
 
-    let boost = get_boost(deps, account.clone())?.boost;
-    let newEarned = Uint256::from(balance_of)
-        .mul(Uint256::from(boost))
+    let new_earned = Uint256::from(reward_balance)
         .mul(Uint256::from(reward_per_token - user_reward_per_token_paid))
-        .div(Uint256::from(BASE_RATE_26));
-    // let earned = ((balance_of * boost * (reward_per_token - user_reward_per_token_paid))
-    //     / Uint128::new(BASE_RATE_26))
-    //     + rewards;
-    let earned = Uint128::from_str(&newEarned.to_string())? + rewards;
+        .div(Uint256::from(BASE_RATE_18));
+    let earned = Uint128::from_str(&new_earned.to_string())? + rewards;
     Ok(EarnedResponse { earned })
 }
 
+// The user's share of reward_supply without boost, the weighted balance in lock tier mode and the
+// tokenless part of it in working balance mode
+pub fn base_balance_of(deps: Deps, account: Addr) -> StdResult<Uint128> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let weighted_balance = weighted_balance_of(deps.storage, &account);
+    let base_balance = match staking_config.boost_mode {
+        BoostMode::LockTier => weighted_balance,
+        BoostMode::WorkingBalance => weighted_balance.multiply_ratio(TOKENLESS_PRODUCTION, 100u128),
+    };
+    Ok(base_balance.min(reward_balance_of(deps, account)?))
+}
+
+// The part of earned the user would have earned without boost, what an early claim pays out
+pub fn base_earned(deps: Deps, env: Env, account: Addr) -> StdResult<BaseEarnedResponse> {
+    let base_rewards = read_base_rewards(deps.storage, &account);
+    let base_balance = base_balance_of(deps, account.clone())?;
+    let reward_per_token = reward_per_token(deps, env)?.reward_per_token;
+    let user_reward_per_token_paid = read_user_reward_per_token_paid(deps.storage, account);
+
+    let new_base_earned = Uint256::from(base_balance)
+        .mul(Uint256::from(reward_per_token - user_reward_per_token_paid))
+        .div(Uint256::from(BASE_RATE_18));
+    let base_earned = Uint128::from_str(&new_base_earned.to_string())? + base_rewards;
+    Ok(BaseEarnedResponse { base_earned })
+}
+
+// Yearly rewards of `reward_rate` (scaled by 1e18) for a `weight` out of `reward_supply`, per
+// `balance` staked tokens and scaled by 1e18
+fn _apr(
+    reward_rate: Uint256,
+    weight: Uint128,
+    reward_supply: Uint128,
+    balance: Uint128,
+) -> StdResult<Uint128> {
+    if reward_supply.is_zero() || balance.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    let apr = reward_rate
+        .mul(Uint256::from(SECONDS_PER_YEAR))
+        .multiply_ratio(weight, reward_supply)
+        .div(Uint256::from(balance));
+    Ok(Uint128::from_str(&apr.to_string())?)
+}
+
+pub fn query_pool_apr(deps: Deps, env: Env) -> StdResult<PoolAprResponse> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let staking_state = current_staking_state(deps, &env)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let remaining_seconds = reward_end(&staking_state).saturating_sub(current_time);
+    let reward_supply = reward_supply(&staking_config, &staking_state);
+    let reward_rate = reward_rate_at(&staking_state, current_time);
+    let remaining_rewards =
+        rewards_between(&staking_state, current_time, reward_end(&staking_state))
+            .div(Uint256::from(BASE_RATE_18));
+    // a staked token's share of reward_supply without boost
+    let base_weight = match staking_config.boost_mode {
+        BoostMode::LockTier => Uint128::new(100u128),
+        BoostMode::WorkingBalance => Uint128::new(TOKENLESS_PRODUCTION),
+    };
+    let apr = if remaining_seconds.is_zero() {
+        Uint128::zero()
+    } else {
+        _apr(
+            reward_rate,
+            base_weight,
+            reward_supply,
+            Uint128::new(100u128),
+        )?
+    };
+    Ok(PoolAprResponse {
+        reward_rate: Uint128::from_str(&reward_rate.div(Uint256::from(BASE_RATE_18)).to_string())?,
+        remaining_seconds,
+        remaining_rewards: Uint128::from_str(&remaining_rewards.to_string())?,
+        reward_supply,
+        apr,
+    })
+}
+
+// Projects the account's current share of reward_supply over the running period, a stale boost
+// is only refreshed by the account's next update or a kick
+pub fn query_projected_earnings(
+    deps: Deps,
+    env: Env,
+    account: Addr,
+    seconds: Uint128,
+) -> StdResult<ProjectedEarningsResponse> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let staking_state = current_staking_state(deps, &env)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let remaining_seconds = reward_end(&staking_state).saturating_sub(current_time);
+    let reward_supply = reward_supply(&staking_config, &staking_state);
+    let reward_balance = reward_balance_of(deps, account.clone())?;
+
+    let projected = if reward_supply.is_zero() {
+        Uint256::zero()
+    } else {
+        rewards_between(
+            &staking_state,
+            current_time,
+            current_time.saturating_add(seconds),
+        )
+        .multiply_ratio(reward_balance, reward_supply)
+        .div(Uint256::from(BASE_RATE_18))
+    };
+    let apr = if remaining_seconds.is_zero() {
+        Uint128::zero()
+    } else {
+        let balance = read_balance_of(deps.storage, account.clone());
+        _apr(
+            reward_rate_at(&staking_state, current_time),
+            reward_balance,
+            reward_supply,
+            balance,
+        )?
+    };
+    Ok(ProjectedEarningsResponse {
+        earned: earned(deps, env, account.clone())?.earned,
+        projected: Uint128::from_str(&projected.to_string())?,
+        boost: get_boost(deps, account)?.boost,
+        apr,
+    })
+}
+
+// Asks boost for the user's current mining boost in this pool, 0 once the lock has expired
+pub fn query_current_boost(deps: Deps, env: Env, account: Addr) -> StdResult<Uint128> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let msg = boost::msg::QueryMsg::GetUserBoost {
+        user: account,
+        user_updated_at: current_time,
+        finish_at: Uint128::MAX,
+        pool: Some(env.contract.address),
+    };
+    let res: boost::msg::GetUserBoostResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: staking_config.boost.to_string(),
+            msg: to_binary(&msg)?,
+        }))?;
+    Ok(res.user_boost)
+}
+
+// Asks boost for the working balance of a staked balance
+pub fn query_working_balance(
+    deps: Deps,
+    account: Addr,
+    balance: Uint128,
+    total_supply: Uint128,
+) -> StdResult<Uint128> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let msg = boost::msg::QueryMsg::GetUserWorkingBalance {
+        user: account,
+        balance,
+        total_supply,
+    };
+    let res: boost::msg::GetUserWorkingBalanceResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: staking_config.boost.to_string(),
+            msg: to_binary(&msg)?,
+        }))?;
+    Ok(res.working_balance)
+}
+
 pub fn is_empty_address(address: &str) -> bool {
     address.trim().is_empty()
 }
@@ -123,6 +397,12 @@ pub fn query_staking_config(deps: Deps) -> StdResult<StakingConfigResponse> {
         fund: staking_config.fund,
         reward_controller_addr: staking_config.reward_controller_addr,
         new_gov: staking_config.new_gov,
+        boost_mode: staking_config.boost_mode,
+        unbonding_period: staking_config.unbonding_period,
+        forfeit_mode: staking_config.forfeit_mode,
+        shutdown: staking_config.shutdown,
+        receipt_token: staking_config.receipt_token,
+        factory: staking_config.factory,
     })
 }
 
@@ -135,6 +415,12 @@ pub fn query_staking_state(deps: Deps) -> StdResult<StakingStateResponse> {
         reward_per_token_stored: staking_state.reward_per_token_stored,
         finish_at: staking_state.finish_at,
         updated_at: staking_state.updated_at,
+        working_supply: staking_state.working_supply,
+        boosted_supply: staking_state.boosted_supply,
+        total_lock_bonus: staking_state.total_lock_bonus,
+        total_unbonding: staking_state.total_unbonding,
+        carried_rate: staking_state.carried_rate,
+        carried_until: staking_state.carried_until,
     })
 }
 
@@ -159,3 +445,194 @@ pub fn balance_of(deps: Deps, account: Addr) -> StdResult<BalanceOfResponse> {
     let balance_of = read_balance_of(deps.storage, account.clone());
     Ok(BalanceOfResponse { balance_of })
 }
+
+pub fn working_balance_of(deps: Deps, account: Addr) -> StdResult<WorkingBalanceOfResponse> {
+    let working_balance = read_working_balance_of(deps.storage, account);
+    Ok(WorkingBalanceOfResponse { working_balance })
+}
+
+pub fn boosted_balance_of(deps: Deps, account: Addr) -> StdResult<BoostedBalanceOfResponse> {
+    let boosted_balance = read_boosted_balance_of(deps.storage, account);
+    Ok(BoostedBalanceOfResponse { boosted_balance })
+}
+
+pub fn query_reward_budget(deps: Deps) -> StdResult<RewardBudgetResponse> {
+    let staking_state = read_staking_state(deps.storage)?;
+    Ok(RewardBudgetResponse {
+        total_notified: staking_state.total_notified,
+        total_accrued: staking_state.total_accrued,
+        total_claimed: staking_state.total_claimed,
+    })
+}
+
+// Calculates the reward per token of a partner reward token, shared by the same balances as the ve_seilor rewards
+pub fn token_reward_per_token(
+    deps: Deps,
+    env: &Env,
+    reward_token: &RewardToken,
+) -> StdResult<Uint128> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let staking_state = read_staking_state(deps.storage)?;
+    let reward_supply = reward_supply(&staking_config, &staking_state);
+    let last_time_reward_applicable = _min(
+        reward_token.finish_at,
+        Uint128::from(env.block.time.seconds()),
+    );
+    if reward_supply.is_zero() || last_time_reward_applicable <= reward_token.updated_at {
+        return Ok(reward_token.reward_per_token_stored);
+    }
+
+    let rewards_256 = reward_token.reward_rate.mul(Uint256::from(
+        last_time_reward_applicable - reward_token.updated_at,
+    ));
+    let add_reward_per_token_256 = rewards_256.div(Uint256::from(reward_supply));
+    let add_reward_per_token = Uint128::from_str(&add_reward_per_token_256.to_string())?;
+    Ok(reward_token.reward_per_token_stored + add_reward_per_token)
+}
+
+pub fn token_earned(
+    deps: Deps,
+    env: &Env,
+    reward_token: &RewardToken,
+    account: &Addr,
+) -> StdResult<Uint128> {
+    let rewards = read_user_token_rewards(deps.storage, &reward_token.token, account);
+    let reward_balance = reward_balance_of(deps, account.clone())?;
+    let reward_per_token = token_reward_per_token(deps, env, reward_token)?;
+    let user_reward_per_token_paid =
+        read_user_token_reward_per_token_paid(deps.storage, &reward_token.token, account);
+
+    let new_earned = Uint256::from(reward_balance)
+        .mul(Uint256::from(reward_per_token - user_reward_per_token_paid))
+        .div(Uint256::from(BASE_RATE_18));
+    Ok(Uint128::from_str(&new_earned.to_string())? + rewards)
+}
+
+// Returns the ve_seilor rewards (as the rewards token) followed by every partner reward token
+pub fn earned_all(deps: Deps, env: Env, account: Addr) -> StdResult<EarnedAllResponse> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let mut rewards = vec![TokenEarned {
+        token: staking_config.rewards_token,
+        earned: earned(deps, env.clone(), account.clone())?.earned,
+    }];
+    for reward_token in read_reward_tokens(deps.storage)? {
+        rewards.push(TokenEarned {
+            token: reward_token.token.clone(),
+            earned: token_earned(deps, &env, &reward_token, &account)?,
+        });
+    }
+    Ok(EarnedAllResponse { rewards })
+}
+
+pub fn query_reward_tokens(deps: Deps) -> StdResult<RewardTokensResponse> {
+    let reward_tokens = read_reward_tokens(deps.storage)?
+        .into_iter()
+        .map(|reward_token| RewardTokenResponse {
+            token: reward_token.token,
+            distributor: reward_token.distributor,
+            duration: reward_token.duration,
+            finish_at: reward_token.finish_at,
+            updated_at: reward_token.updated_at,
+            reward_rate: reward_token.reward_rate,
+            reward_per_token_stored: reward_token.reward_per_token_stored,
+        })
+        .collect();
+    Ok(RewardTokensResponse { reward_tokens })
+}
+
+pub fn query_lock_tiers(deps: Deps) -> StdResult<LockTiersResponse> {
+    let lock_tiers = read_lock_tiers(deps.storage)
+        .into_iter()
+        .map(|lock_tier| LockTierItem {
+            duration: lock_tier.duration,
+            multiplier: lock_tier.multiplier,
+        })
+        .collect();
+    Ok(LockTiersResponse { lock_tiers })
+}
+
+pub fn query_user_positions(
+    deps: Deps,
+    account: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<UserPositionsResponse> {
+    let positions = read_positions(deps.storage, &account, start_after, limit)?
+        .into_iter()
+        .map(|position| PositionResponse {
+            id: position.id,
+            amount: position.amount,
+            duration: position.duration,
+            multiplier: position.multiplier,
+            start_time: position.start_time,
+            unlock_time: position.unlock_time,
+            bonus: position.bonus,
+        })
+        .collect();
+    Ok(UserPositionsResponse { positions })
+}
+
+pub fn query_unbonding_requests(deps: Deps, account: Addr) -> StdResult<UnbondingRequestsResponse> {
+    let requests = read_unbonding_entries(deps.storage, &account)?
+        .into_iter()
+        .map(|entry| UnbondingRequestResponse {
+            id: entry.id,
+            amount: entry.amount,
+            release_time: entry.release_time,
+        })
+        .collect();
+    Ok(UnbondingRequestsResponse { requests })
+}
+
+pub fn query_reward_schedule(deps: Deps, env: Env) -> StdResult<RewardScheduleResponse> {
+    let current_time = Uint128::from(env.block.time.seconds());
+    let staking_state = current_staking_state(deps, &env)?;
+    let active = if reward_end(&staking_state) > current_time {
+        Some(ActiveRewardPeriodResponse {
+            start: staking_state.started_at,
+            end: staking_state.finish_at,
+            reward_rate: staking_state.reward_rate,
+            carried_rate: staking_state.carried_rate,
+            carried_until: staking_state.carried_until,
+        })
+    } else {
+        None
+    };
+    let upcoming = read_reward_schedules(deps.storage)?
+        .into_iter()
+        .filter(|schedule| schedule.start > current_time)
+        .map(|schedule| ScheduledRewardsResponse {
+            id: schedule.id,
+            start: schedule.start,
+            end: schedule.end,
+            amount: schedule.amount,
+            redistributed: schedule.redistributed,
+        })
+        .collect();
+    Ok(RewardScheduleResponse { active, upcoming })
+}
+
+// The part of the contract's balance of the asset owed to stakers: staked and unbonding tokens,
+// partner rewards accrued and not claimed yet plus the rest of their running periods. Partner
+// rewards must be settled up to now.
+pub fn reserved_balance(deps: Deps, env: &Env, asset: &AssetInfo) -> StdResult<Uint128> {
+    let staking_config = read_staking_config(deps.storage)?;
+    let staking_state = read_staking_state(deps.storage)?;
+    let current_time = Uint128::from(env.block.time.seconds());
+    let mut reserved = Uint128::zero();
+    if asset.is_token(&staking_config.staking_token) {
+        reserved += staking_state.total_supply + staking_state.total_unbonding;
+    }
+    for reward_token in read_reward_tokens(deps.storage)? {
+        if !asset.is_token(&reward_token.token) {
+            continue;
+        }
+        reserved += reward_token.unclaimed;
+        if reward_token.finish_at > current_time {
+            let remaining = Uint256::from(reward_token.finish_at - current_time)
+                .multiply_ratio(reward_token.reward_rate, Uint256::from(BASE_RATE_18));
+            reserved += Uint128::from_str(&remaining.to_string())?;
+        }
+    }
+    Ok(reserved)
+}
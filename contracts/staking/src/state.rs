@@ -1,5 +1,6 @@
-use cosmwasm_std::{Addr, StdResult, Storage, Uint128, Uint256};
-use cw_storage_plus::{Item, Map};
+use crate::msg::{BoostMode, ForfeitMode};
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128, Uint256};
+use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +14,23 @@ pub struct StakingConfig {
     pub fund: Addr,
     pub reward_controller_addr: Addr,
     pub new_gov: Option<Addr>,
+    #[serde(default)]
+    pub boost_mode: BoostMode,
+    // Seconds withdrawn tokens wait in the unbonding queue, 0 pays them out immediately
+    #[serde(default)]
+    pub unbonding_period: Uint128,
+    // Where the boost portion forfeited by early claims goes
+    #[serde(default)]
+    pub forfeit_mode: ForfeitMode,
+    // Set by gov when the pool is shut down: rewards stop accruing and emergency withdrawals ignore locks
+    #[serde(default)]
+    pub shutdown: bool,
+    // Receipt CW20 minted for unlocked stakes, set once it has been instantiated
+    #[serde(default)]
+    pub receipt_token: Option<Addr>,
+    // Factory that instantiated the pool, it can shut the pool down next to gov
+    #[serde(default)]
+    pub factory: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -21,6 +39,9 @@ pub struct StakingState {
     pub duration: Uint128,
     // Timestamp of when the rewards finish
     pub finish_at: Uint128,
+    // Timestamp of when the current reward period started
+    #[serde(default)]
+    pub started_at: Uint128,
     // Minimum of last updated time and reward finish time
     pub updated_at: Uint128,
     // Reward to be paid out per second
@@ -29,16 +50,156 @@ pub struct StakingState {
     pub reward_per_token_stored: Uint128,
 
     pub total_supply: Uint128,
+    // Sum of working balances, replaces total_supply in working balance boost mode
+    #[serde(default)]
+    pub working_supply: Uint128,
+    // Sum of balance * boost, replaces total_supply in lock tier boost mode
+    #[serde(default)]
+    pub boosted_supply: Uint128,
+    // Rewards notified, shared out to stakers and paid out so far, claims never exceed what was notified
+    #[serde(default)]
+    pub total_notified: Uint128,
+    #[serde(default)]
+    pub total_accrued: Uint128,
+    #[serde(default)]
+    pub total_claimed: Uint128,
+    // Sum of the extra weight of unexpired lock positions, total_supply + total_lock_bonus is the weighted supply
+    #[serde(default)]
+    pub total_lock_bonus: Uint128,
+    // Withdrawn tokens waiting in the unbonding queue, no longer part of total_supply
+    #[serde(default)]
+    pub total_unbonding: Uint128,
+    // Leftover of the period running when a scheduled epoch started, paid at its own rate next to
+    // reward_rate until carried_until
+    #[serde(default)]
+    pub carried_rate: Uint256,
+    #[serde(default)]
+    pub carried_until: Uint128,
+    // Set while the running period is a scheduled epoch, NotifyRewardAmount waits for its end
+    #[serde(default)]
+    pub scheduled: bool,
 }
 
+// A partner token rewarded next to the ve_seilor rewards, paid out of the tokens its distributor notified
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardToken {
+    pub token: Addr,
+    pub distributor: Addr,
+    pub duration: Uint128,
+    pub finish_at: Uint128,
+    pub updated_at: Uint128,
+    pub reward_rate: Uint256,
+    pub reward_per_token_stored: Uint128,
+    // rewards accrued to stakers and not paid out yet, kept out of RescueTokens
+    #[serde(default)]
+    pub unclaimed: Uint128,
+}
+
+// Rewards the reward controller scheduled for a future epoch, started once start has passed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardSchedule {
+    pub id: u64,
+    pub start: Uint128,
+    pub end: Uint128,
+    pub amount: Uint128,
+    // Forfeited rewards paid out with the epoch on top of amount, they were notified already
+    #[serde(default)]
+    pub redistributed: Uint128,
+}
+
+// A lock duration positions can be staked with and the multiplier on their reward weight (1e8 = 1x)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockTier {
+    pub duration: Uint128,
+    pub multiplier: Uint128,
+}
+
+// Tokens staked with a lock, they can not be withdrawn before unlock_time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Position {
+    pub id: u64,
+    pub amount: Uint128,
+    pub duration: Uint128,
+    pub multiplier: Uint128,
+    pub start_time: Uint128,
+    pub unlock_time: Uint128,
+    // amount * (multiplier - 1x), dropped once the lock has expired
+    pub bonus: Uint128,
+}
+
+// Withdrawn tokens that no longer earn rewards and can be claimed from release_time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingEntry {
+    pub id: u64,
+    pub amount: Uint128,
+    pub release_time: Uint128,
+}
+
+// Seeding of the reward budget of a pool migrated from before 0.2.0, removed once every account is seeded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardBudgetSeed {
+    // reward_per_token_stored at the migration, what each account had earned by then joins the budget
+    pub reward_per_token: Uint128,
+    // Last account walked by SeedRewardBudget
+    pub last_account: Option<Addr>,
+}
+
+// Cap on the partner reward tokens of a pool, every one of them is settled on each user update
+pub const MAX_REWARD_TOKENS: usize = 5;
+
+pub const MAX_LOCK_TIERS: usize = 10;
+pub const MAX_LOCK_MULTIPLIER: u128 = 1000000000u128;
+// Cap on the open positions of a user, every one of them is checked on each user update
+pub const MAX_USER_POSITIONS: usize = 20;
+
+// Cap on the pending unbonding entries of a user
+pub const MAX_UNBONDING_ENTRIES: usize = 20;
+
+// Cap on the scheduled epochs not started yet, every one of them is checked on each update
+pub const MAX_REWARD_SCHEDULES: usize = 20;
+
+pub const MAX_LIMIT: u32 = 30;
+pub const DEFAULT_LIMIT: u32 = 10;
+
 const STAKING_CONFIG: Item<StakingConfig> = Item::new("staking_config");
 const STAKING_STATE: Item<StakingState> = Item::new("staking_state");
 
 const USER_REWARD_PER_TOKEN_PAID: Map<Addr, Uint128> = Map::new("user_reward_per_token_paid");
 const REWARDS: Map<Addr, Uint128> = Map::new("rewards");
+// User address => the part of the rewards that was earned with the base weight, without boost
+const BASE_REWARDS: Map<&Addr, Uint128> = Map::new("base_rewards");
 const USER_UPDATED_AT: Map<Addr, Uint128> = Map::new("user_updated_at");
 // User address => staked amount
 const STAKING_USER_BALANCE_OF: Map<&Addr, Uint128> = Map::new("staking_user_balance_of");
+// User address => working balance, only used in working balance boost mode
+const STAKING_USER_WORKING_BALANCE_OF: Map<&Addr, Uint128> =
+    Map::new("staking_user_working_balance_of");
+// User address => balance * boost, only used in lock tier boost mode
+const STAKING_USER_BOOSTED_BALANCE_OF: Map<&Addr, Uint128> =
+    Map::new("staking_user_boosted_balance_of");
+// Reward token address => reward token
+const REWARD_TOKENS: Map<&Addr, RewardToken> = Map::new("reward_tokens");
+// (Reward token address, user address) => reward per token paid
+const USER_TOKEN_REWARD_PER_TOKEN_PAID: Map<(&Addr, &Addr), Uint128> =
+    Map::new("user_token_reward_per_token_paid");
+// (Reward token address, user address) => rewards
+const USER_TOKEN_REWARDS: Map<(&Addr, &Addr), Uint128> = Map::new("user_token_rewards");
+const REWARD_SCHEDULE_COUNT: Item<u64> = Item::new("reward_schedule_count");
+// Reward schedule id => reward schedule, removed once started
+const REWARD_SCHEDULES: Map<u64, RewardSchedule> = Map::new("reward_schedules");
+const LOCK_TIERS: Item<Vec<LockTier>> = Item::new("lock_tiers");
+const POSITION_COUNT: Item<u64> = Item::new("position_count");
+// (User address, position id) => position
+const USER_POSITIONS: Map<(&Addr, u64), Position> = Map::new("user_positions");
+const UNBONDING_COUNT: Item<u64> = Item::new("unbonding_count");
+// (User address, unbonding id) => unbonding entry
+const USER_UNBONDING_ENTRIES: Map<(&Addr, u64), UnbondingEntry> =
+    Map::new("user_unbonding_entries");
+// User address => sum of the bonus of the user's positions
+const STAKING_USER_LOCK_BONUS_OF: Map<&Addr, Uint128> = Map::new("staking_user_lock_bonus_of");
+const REWARD_BUDGET_SEED: Item<RewardBudgetSeed> = Item::new("reward_budget_seed");
+// Accounts already added to the reward budget while it is being seeded
+const SEEDED_ACCOUNTS: Map<&Addr, bool> = Map::new("seeded_accounts");
 
 pub fn store_user_reward_per_token_paid(
     storage: &mut dyn Storage,
@@ -62,6 +223,54 @@ pub fn read_rewards(storage: &dyn Storage, user: Addr) -> Uint128 {
     REWARDS.load(storage, user).unwrap_or(Uint128::zero())
 }
 
+// Accounts that ever had their rewards updated, walked by SeedRewardBudget
+pub fn read_reward_accounts(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: usize,
+) -> StdResult<Vec<Addr>> {
+    let start = start_after.map(Bound::exclusive);
+    REWARDS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn store_reward_budget_seed(
+    storage: &mut dyn Storage,
+    seed: &RewardBudgetSeed,
+) -> StdResult<()> {
+    REWARD_BUDGET_SEED.save(storage, seed)
+}
+
+pub fn read_reward_budget_seed(storage: &dyn Storage) -> StdResult<Option<RewardBudgetSeed>> {
+    REWARD_BUDGET_SEED.may_load(storage)
+}
+
+pub fn remove_reward_budget_seed(storage: &mut dyn Storage) {
+    REWARD_BUDGET_SEED.remove(storage)
+}
+
+pub fn store_seeded_account(storage: &mut dyn Storage, account: &Addr) -> StdResult<()> {
+    SEEDED_ACCOUNTS.save(storage, account, &true)
+}
+
+pub fn is_seeded_account(storage: &dyn Storage, account: &Addr) -> bool {
+    SEEDED_ACCOUNTS.has(storage, account)
+}
+
+pub fn store_base_rewards(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    base_rewards: &Uint128,
+) -> StdResult<()> {
+    BASE_REWARDS.save(storage, user, base_rewards)
+}
+
+pub fn read_base_rewards(storage: &dyn Storage, user: &Addr) -> Uint128 {
+    BASE_REWARDS.load(storage, user).unwrap_or(Uint128::zero())
+}
+
 pub fn store_user_updated_at(
     storage: &mut dyn Storage,
     user: Addr,
@@ -86,6 +295,36 @@ pub fn read_balance_of(storage: &dyn Storage, user: Addr) -> Uint128 {
         .unwrap_or(Uint128::zero())
 }
 
+pub fn store_working_balance_of(
+    storage: &mut dyn Storage,
+    user: Addr,
+    working_balance: &Uint128,
+) -> StdResult<()> {
+    STAKING_USER_WORKING_BALANCE_OF.save(storage, &user, working_balance)
+}
+
+pub fn read_working_balance_of(storage: &dyn Storage, user: Addr) -> Uint128 {
+    STAKING_USER_WORKING_BALANCE_OF
+        .load(storage, &user)
+        .unwrap_or(Uint128::zero())
+}
+
+pub fn store_boosted_balance_of(
+    storage: &mut dyn Storage,
+    user: Addr,
+    boosted_balance: &Uint128,
+) -> StdResult<()> {
+    STAKING_USER_BOOSTED_BALANCE_OF.save(storage, &user, boosted_balance)
+}
+
+// Stakers who have not been updated since the boosted supply was introduced count with a 1x boost
+pub fn read_boosted_balance_of(storage: &dyn Storage, user: Addr) -> Uint128 {
+    STAKING_USER_BOOSTED_BALANCE_OF
+        .may_load(storage, &user)
+        .unwrap_or(None)
+        .unwrap_or_else(|| read_balance_of(storage, user))
+}
+
 pub fn store_staking_config(storage: &mut dyn Storage, config: &StakingConfig) -> StdResult<()> {
     STAKING_CONFIG.save(storage, config)?;
     Ok(())
@@ -102,3 +341,176 @@ pub fn store_staking_state(storage: &mut dyn Storage, state: &StakingState) -> S
 pub fn read_staking_state(storage: &dyn Storage) -> StdResult<StakingState> {
     STAKING_STATE.load(storage)
 }
+
+pub fn store_reward_token(storage: &mut dyn Storage, reward_token: &RewardToken) -> StdResult<()> {
+    REWARD_TOKENS.save(storage, &reward_token.token, reward_token)
+}
+
+pub fn read_reward_token(storage: &dyn Storage, token: &Addr) -> StdResult<RewardToken> {
+    REWARD_TOKENS.load(storage, token)
+}
+
+pub fn read_reward_tokens(storage: &dyn Storage) -> StdResult<Vec<RewardToken>> {
+    REWARD_TOKENS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub fn store_user_token_reward_per_token_paid(
+    storage: &mut dyn Storage,
+    token: &Addr,
+    user: &Addr,
+    reward_per_token_paid: &Uint128,
+) -> StdResult<()> {
+    USER_TOKEN_REWARD_PER_TOKEN_PAID.save(storage, (token, user), reward_per_token_paid)
+}
+
+pub fn read_user_token_reward_per_token_paid(
+    storage: &dyn Storage,
+    token: &Addr,
+    user: &Addr,
+) -> Uint128 {
+    USER_TOKEN_REWARD_PER_TOKEN_PAID
+        .load(storage, (token, user))
+        .unwrap_or(Uint128::zero())
+}
+
+pub fn store_user_token_rewards(
+    storage: &mut dyn Storage,
+    token: &Addr,
+    user: &Addr,
+    rewards: &Uint128,
+) -> StdResult<()> {
+    USER_TOKEN_REWARDS.save(storage, (token, user), rewards)
+}
+
+pub fn read_user_token_rewards(storage: &dyn Storage, token: &Addr, user: &Addr) -> Uint128 {
+    USER_TOKEN_REWARDS
+        .load(storage, (token, user))
+        .unwrap_or(Uint128::zero())
+}
+
+pub fn store_lock_tiers(storage: &mut dyn Storage, lock_tiers: &Vec<LockTier>) -> StdResult<()> {
+    LOCK_TIERS.save(storage, lock_tiers)
+}
+
+pub fn read_lock_tiers(storage: &dyn Storage) -> Vec<LockTier> {
+    LOCK_TIERS.load(storage).unwrap_or_default()
+}
+
+pub fn next_position_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = POSITION_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    POSITION_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn store_position(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    position: &Position,
+) -> StdResult<()> {
+    USER_POSITIONS.save(storage, (user, position.id), position)
+}
+
+pub fn remove_position(storage: &mut dyn Storage, user: &Addr, id: u64) {
+    USER_POSITIONS.remove(storage, (user, id))
+}
+
+pub fn read_position(storage: &dyn Storage, user: &Addr, id: u64) -> StdResult<Position> {
+    USER_POSITIONS.load(storage, (user, id))
+}
+
+pub fn read_positions(
+    storage: &dyn Storage,
+    user: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Position>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    USER_POSITIONS
+        .prefix(user)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub fn read_all_positions(storage: &dyn Storage, user: &Addr) -> StdResult<Vec<Position>> {
+    USER_POSITIONS
+        .prefix(user)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub fn store_lock_bonus_of(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    lock_bonus: &Uint128,
+) -> StdResult<()> {
+    STAKING_USER_LOCK_BONUS_OF.save(storage, user, lock_bonus)
+}
+
+pub fn read_lock_bonus_of(storage: &dyn Storage, user: &Addr) -> Uint128 {
+    STAKING_USER_LOCK_BONUS_OF
+        .load(storage, user)
+        .unwrap_or(Uint128::zero())
+}
+
+pub fn next_unbonding_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = UNBONDING_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    UNBONDING_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn store_unbonding_entry(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    entry: &UnbondingEntry,
+) -> StdResult<()> {
+    USER_UNBONDING_ENTRIES.save(storage, (user, entry.id), entry)
+}
+
+pub fn remove_unbonding_entry(storage: &mut dyn Storage, user: &Addr, id: u64) {
+    USER_UNBONDING_ENTRIES.remove(storage, (user, id))
+}
+
+pub fn read_unbonding_entries(
+    storage: &dyn Storage,
+    user: &Addr,
+) -> StdResult<Vec<UnbondingEntry>> {
+    USER_UNBONDING_ENTRIES
+        .prefix(user)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+pub fn next_reward_schedule_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = REWARD_SCHEDULE_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    REWARD_SCHEDULE_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn store_reward_schedule(
+    storage: &mut dyn Storage,
+    schedule: &RewardSchedule,
+) -> StdResult<()> {
+    REWARD_SCHEDULES.save(storage, schedule.id, schedule)
+}
+
+pub fn remove_reward_schedule(storage: &mut dyn Storage, id: u64) {
+    REWARD_SCHEDULES.remove(storage, id)
+}
+
+// Returns the schedules not started yet, in the order they start
+pub fn read_reward_schedules(storage: &dyn Storage) -> StdResult<Vec<RewardSchedule>> {
+    let mut schedules = REWARD_SCHEDULES
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<Vec<RewardSchedule>>>()?;
+    schedules.sort_by_key(|schedule| (schedule.start, schedule.id));
+    Ok(schedules)
+}
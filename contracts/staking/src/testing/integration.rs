@@ -1,11 +1,15 @@
-use std::ops::{Div, Mul};
-use crate::constract::{execute, instantiate, query};
-use crate::msg::Cw20HookMsg::Stake;
+use crate::constract::{execute, instantiate, query, reply};
+use crate::helper::BASE_RATE_18;
+use crate::msg::Cw20HookMsg::{Stake, StakeLocked};
 use crate::msg::ExecuteMsg::NotifyRewardAmount;
 use crate::msg::{
-    BalanceOfResponse, EarnedResponse, ExecuteMsg, GetBoostResponse,
+    AssetInfo, BalanceOfResponse, BaseEarnedResponse, BoostMode, BoostedBalanceOfResponse,
+    EarnedAllResponse, EarnedResponse, ExecuteMsg, ForfeitMode, GetBoostResponse,
     GetUserRewardPerTokenPaidResponse, GetUserUpdatedAtResponse, LastTimeRewardApplicableResponse,
-    QueryMsg, RewardPerTokenResponse, StakingConfigResponse, StakingStateResponse,
+    LockTierItem, LockTiersResponse, PoolAprResponse, ProjectedEarningsResponse, QueryMsg,
+    ReceiptTokenMsg, RewardBudgetResponse, RewardPerTokenResponse, RewardScheduleResponse,
+    StakingConfigResponse, StakingStateResponse, UnbondingRequestsResponse,
+    UpdateStakingConfigStruct, UserPositionsResponse, WorkingBalanceOfResponse,
 };
 use crate::testing::mock_fn::{mock_instantiate_msg, CREATOR, REWARD_CONTROLLER_ADDR};
 use crate::testing::mock_third_fn::{
@@ -13,9 +17,10 @@ use crate::testing::mock_third_fn::{
     mock_staking_token_instance_msg, mock_ve_seilor_instance_msg,
 };
 use cosmwasm_std::testing::mock_env;
-use cosmwasm_std::{to_binary, Addr, Coin, Timestamp, Uint128};
+use cosmwasm_std::{to_binary, Addr, Coin, Timestamp, Uint128, Uint256};
 use cw20::BalanceResponse;
 use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use std::ops::{Div, Mul};
 
 fn mock_app(owner: Addr, coins: Vec<Coin>, block_time: Option<u64>) -> App {
     let mut block = mock_env().block;
@@ -78,6 +83,15 @@ fn store_staking_reward_contract(app: &mut App) -> u64 {
     app.store_code(staking_reward_contract)
 }
 
+fn store_staking_receipt_contract(app: &mut App) -> u64 {
+    let staking_receipt_contract = Box::new(ContractWrapper::new_with_empty(
+        staking_receipt::contract::execute,
+        staking_receipt::contract::instantiate,
+        staking_receipt::contract::query,
+    ));
+    app.store_code(staking_receipt_contract)
+}
+
 fn staking_token_instance(creator: &Addr, mut app: &mut App) -> Addr {
     let staking_token_code_id = store_staking_token_contract(&mut app);
     let staking_token_instance_msg = mock_staking_token_instance_msg();
@@ -94,9 +108,9 @@ fn staking_token_instance(creator: &Addr, mut app: &mut App) -> Addr {
     staking_token
 }
 
-fn boost_instance(creator: &Addr, mut app: &mut App) -> Addr {
+fn boost_instance(creator: &Addr, mut app: &mut App, ve_seilor: &Addr) -> Addr {
     let boost_code_id = store_boost_contract(&mut app);
-    let boost_instance_msg = mock_boost_instance_msg();
+    let boost_instance_msg = mock_boost_instance_msg(ve_seilor);
     let boost = app
         .instantiate_contract(
             boost_code_id,
@@ -207,7 +221,7 @@ fn test_integration() {
     println!("seilor: {:?}", seilor);
 
     // deploy ve seilor boost contract
-    let boost = boost_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
     println!("boost: {:?}", boost);
 
     // deploy seilor fund contract
@@ -246,13 +260,13 @@ fn test_integration() {
     let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
     notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
 
-
-
     // query token staking token balance
     let balance = cw20_balance(&mut app, &staking_token, &tom_address);
     assert_eq!(balance.balance, Uint128::zero());
 
-    let stake_amount = Uint128::from(10u128).pow(18).mul(Uint128::from(1000000u128));
+    let stake_amount = Uint128::from(10u128)
+        .pow(18)
+        .mul(Uint128::from(1000000u128));
 
     stake(
         &tom_address,
@@ -262,7 +276,6 @@ fn test_integration() {
         &stake_amount,
     );
 
-
     // send staking token to tom
     let send_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
     cw20_transfer(
@@ -403,195 +416,1981 @@ fn test_integration() {
     assert_eq!(query_earned_3, query_earned_2);
 }
 
-fn reward_per_token(app: &mut App, staking_reward: &Addr) -> Uint128 {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::RewardPerToken {},
-    );
-    let res: RewardPerTokenResponse = res.unwrap();
-    res.reward_per_token
-}
+#[test]
+fn test_working_balance_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
 
-fn last_time_reward_applicable(app: &mut App, staking_reward: &Addr) -> Uint128 {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::LastTimeRewardApplicable {},
-    );
-    let res: LastTimeRewardApplicableResponse = res.unwrap();
-    res.last_time_reward_applicable
-}
+    let tom_address = Addr::unchecked("tom".to_string());
+    let jerry_address = Addr::unchecked("jerry".to_string());
 
-fn get_boost(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::GetBoost {
-            account: user.clone(),
-        },
-    );
-    let res: GetBoostResponse = res.unwrap();
-    res.boost
-}
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
 
-fn earned(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::Earned {
-            account: user.clone(),
-        },
+    let staking_reward_code_id = store_staking_reward_contract(&mut app);
+    let mut instance_msg = mock_instantiate_msg(
+        staking_token.clone(),
+        ve_seilor.clone(),
+        boost.clone(),
+        fund.clone(),
     );
-    let res: EarnedResponse = res.unwrap();
-    res.earned
-}
+    instance_msg.boost_mode = Some(BoostMode::WorkingBalance);
+    let staking_reward = app
+        .instantiate_contract(
+            staking_reward_code_id,
+            creator.clone(),
+            &instance_msg,
+            &[], // no funds
+            String::from("Staking"),
+            None,
+        )
+        .unwrap();
 
-fn query_staking_config(app: &mut App, staking_reward: &Addr) -> StakingConfigResponse {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::QueryStakingConfig {},
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, creator.clone(), true);
+
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+
+    // jerry holds all of the ve_seilor
+    ve_fund_mint(
+        &creator,
+        &mut app,
+        &fund,
+        &jerry_address,
+        Uint128::from(1000000u128),
     );
-    let res: StakingConfigResponse = res.unwrap();
-    res
-}
 
-fn query_staking_state(app: &mut App, staking_reward: &Addr) -> StakingStateResponse {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::QueryStakingState {},
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    for user in [&tom_address, &jerry_address] {
+        cw20_transfer(
+            &mut app,
+            &staking_token,
+            &creator,
+            user,
+            stake_amount.clone(),
+        );
+        stake(
+            user,
+            &mut app,
+            &staking_token,
+            &staking_reward,
+            &stake_amount,
+        );
+    }
+
+    // tom: 0.4 * stake, jerry: min(0.4 * stake + 0.6 * 2 * stake, stake)
+    let tom_working_balance = stake_amount.multiply_ratio(40u128, 100u128);
+    assert_eq!(
+        working_balance_of(&mut app, &staking_reward, &tom_address),
+        tom_working_balance
     );
-    let res: StakingStateResponse = res.unwrap();
-    res
-}
-fn get_user_updated_at(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::GetUserUpdatedAt {
-            account: user.clone(),
-        },
+    assert_eq!(
+        working_balance_of(&mut app, &staking_reward, &jerry_address),
+        stake_amount
     );
-    let res: GetUserUpdatedAtResponse = res.unwrap();
-    res.updated_at
-}
-fn get_user_reward_per_token_paid(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::GetUserRewardPerTokenPaid {
-            account: user.clone(),
-        },
+    let staking_state = query_staking_state(&mut app, &staking_reward);
+    assert_eq!(
+        staking_state.working_supply,
+        tom_working_balance + stake_amount
     );
-    let res: GetUserRewardPerTokenPaidResponse = res.unwrap();
-    res.reward_per_token_paid
-}
-
-fn balance_of(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
-    let res = app.wrap().query_wasm_smart(
-        staking_reward.clone().to_string(),
-        &QueryMsg::BalanceOf {
-            account: user.clone(),
-        },
+    assert_eq!(
+        get_boost(&mut app, &staking_reward, &tom_address),
+        Uint128::from(100000000u128)
+    );
+    assert_eq!(
+        get_boost(&mut app, &staking_reward, &jerry_address),
+        Uint128::from(250000000u128)
     );
-    let res: BalanceOfResponse = res.unwrap();
-    res.balance_of
-}
-
-// fn set_ve_seilor_miners(
-//     creator: &Addr,
-//     app: &mut App,
-//     ve_seilor: &Addr,
-//     contracts: Vec<Addr>,
-//     is_minter: Vec<bool>,
-// ) {
-//     let ve_seilor_miner_msg = ve_seilor::msg::ExecuteMsg::SetMinters {
-//         contracts,
-//         is_minter,
-//     };
-//     let res = app.execute_contract(
-//         creator.clone(),
-//         ve_seilor.clone(),
-//         &ve_seilor_miner_msg,
-//         &[],
-//     );
-//     assert!(res.is_ok());
-// }
 
-fn set_ve_seilor_to_fund(creator: &Addr, app: &mut App, ve_seilor: &Addr, fund: &Addr) {
-    let ve_seilor_config = ve_seilor::msg::ExecuteMsg::UpdateConfig {
-        max_minted: None,
-        fund: Some(fund.clone()),
-    };
-    let res = app.execute_contract(creator.clone(), ve_seilor.clone(), &ve_seilor_config, &[]);
-    assert!(res.is_ok());
-}
+    let elapsed = 1000000u64;
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(elapsed);
+        block.height += 1000000u64;
+    });
 
-fn set_seilor_to_fund(creator: &Addr, app: &mut App, seilor: &Addr, fund: &Addr) {
-    let update_seilor_fund_msg = seilor::msg::ExecuteMsg::UpdateConfig {
-        fund: Some(fund.clone()),
-        distribute: None,
-        cross_chain_swap_contract: None,
-    };
-    let res = app.execute_contract(
-        creator.clone(),
-        seilor.clone(),
-        &update_seilor_fund_msg,
-        &[],
+    // jerry earns 2.5x tom, and both together stay within what was emitted
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    let jerry_earned = earned(&mut app, &staking_reward, &jerry_address);
+    let (jerry_weighted, tom_weighted) = (
+        jerry_earned.mul(Uint128::from(2u128)),
+        tom_earned.mul(Uint128::from(5u128)),
     );
-    assert!(res.is_ok());
-}
+    assert!(jerry_weighted <= tom_weighted + Uint128::from(5u128));
+    assert!(tom_weighted <= jerry_weighted + Uint128::from(5u128));
+    let emitted = reward_amount.multiply_ratio(elapsed, staking_state.duration);
+    assert!(tom_earned + jerry_earned <= emitted);
 
-fn notify_reward_amount(
-    creator: &Addr,
-    app: &mut App,
-    staking_reward: &Addr,
-    reward_amount: &Uint128,
-) {
-    let reward_msg = NotifyRewardAmount {
-        amount: reward_amount.clone(),
-    };
+    // tom gets ve_seilor, his working balance is stale until kicked
+    ve_fund_mint(
+        &creator,
+        &mut app,
+        &fund,
+        &tom_address,
+        Uint128::from(1000000u128),
+    );
+    assert_eq!(
+        working_balance_of(&mut app, &staking_reward, &tom_address),
+        tom_working_balance
+    );
     let res = app.execute_contract(
-        creator.clone(),
+        jerry_address.clone(),
         staking_reward.clone(),
-        &reward_msg,
-        &[], // no funds
+        &ExecuteMsg::Kick {
+            user: tom_address.clone(),
+        },
+        &[],
     );
     assert!(res.is_ok());
+    assert_eq!(
+        working_balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount
+    );
+    // rewards accrued before the kick are kept
+    assert_eq!(earned(&mut app, &staking_reward, &tom_address), tom_earned);
 }
 
-fn get_reward(user: &Addr, app: &mut App, staking_reward: &Addr) {
-    let res = app.execute_contract(
-        user.clone(),
-        staking_reward.clone(),
-        &ExecuteMsg::GetReward {},
-        &[], // no funds
+#[test]
+fn test_boosted_supply_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+    let jerry_address = Addr::unchecked("jerry".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
     );
-    assert!(res.is_ok());
-}
 
-fn withdraw(user: &Addr, app: &mut App, staking_reward: &Addr, amount: &Uint128) {
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, creator.clone(), true);
+
+    // tom locks for a year, doubling his rewards
+    ve_fund_mint(
+        &creator,
+        &mut app,
+        &fund,
+        &tom_address,
+        Uint128::from(1000000u128),
+    );
     let res = app.execute_contract(
-        user.clone(),
-        staking_reward.clone(),
-        &ExecuteMsg::Withdraw {
-            amount: amount.clone(),
+        tom_address.clone(),
+        boost.clone(),
+        &boost::msg::ExecuteMsg::Lock {
+            index: 3,
+            amount: Uint128::from(1000000u128),
         },
-        &[], // no funds
+        &[],
     );
-    println!("{:?}", res);
     assert!(res.is_ok());
-}
 
-fn cw20_balance(app: &mut App, cw20_token: &Addr, user: &Addr) -> BalanceResponse {
-    let query_msg = cw20_base::msg::QueryMsg::Balance {
-        address: user.clone().to_string(),
-    };
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
 
-    let res: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(cw20_token.clone().to_string(), &query_msg)
-        .unwrap();
-    res
-}
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    for user in [&tom_address, &jerry_address] {
+        cw20_transfer(
+            &mut app,
+            &staking_token,
+            &creator,
+            user,
+            stake_amount.clone(),
+        );
+        stake(
+            user,
+            &mut app,
+            &staking_token,
+            &staking_reward,
+            &stake_amount,
+        );
+    }
+    assert_eq!(
+        boosted_balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount.mul(Uint128::from(2u128))
+    );
+    assert_eq!(
+        boosted_balance_of(&mut app, &staking_reward, &jerry_address),
+        stake_amount
+    );
+    let staking_state = query_staking_state(&mut app, &staking_reward);
+    assert_eq!(
+        staking_state.boosted_supply,
+        stake_amount.mul(Uint128::from(3u128))
+    );
+    assert_eq!(
+        get_boost(&mut app, &staking_reward, &tom_address),
+        Uint128::from(200000000u128)
+    );
 
-fn cw20_transfer(
-    app: &mut App,
-    cw20_token: &Addr,
+    let elapsed = 1000000u64;
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(elapsed);
+        block.height += 1000000u64;
+    });
+
+    // tom earns 2x jerry, and the boost comes out of the notified rewards
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    let jerry_earned = earned(&mut app, &staking_reward, &jerry_address);
+    assert!(jerry_earned.mul(Uint128::from(2u128)) <= tom_earned + Uint128::from(2u128));
+    assert!(tom_earned <= jerry_earned.mul(Uint128::from(2u128)) + Uint128::from(2u128));
+    let emitted = reward_amount.multiply_ratio(elapsed, staking_state.duration);
+    assert!(tom_earned + jerry_earned <= emitted);
+
+    // past the reward period and tom's lock, everything notified has been shared out
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(31536000u64);
+        block.height += 1000000u64;
+    });
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    let jerry_earned = earned(&mut app, &staking_reward, &jerry_address);
+    assert!(tom_earned + jerry_earned <= reward_amount);
+    assert!(tom_earned + jerry_earned + Uint128::from(2u128) >= reward_amount);
+
+    get_reward(&tom_address, &mut app, &staking_reward);
+    get_reward(&jerry_address, &mut app, &staking_reward);
+    assert_eq!(
+        cw20_balance(&mut app, &ve_seilor, &jerry_address).balance,
+        jerry_earned
+    );
+    let reward_budget = reward_budget(&mut app, &staking_reward);
+    assert_eq!(reward_budget.total_notified, reward_amount);
+    assert_eq!(reward_budget.total_claimed, tom_earned + jerry_earned);
+    assert!(reward_budget.total_claimed <= reward_budget.total_accrued);
+    assert!(reward_budget.total_accrued <= reward_budget.total_notified);
+
+    // tom's lock expired, his boosted balance went back to his stake when he claimed
+    assert_eq!(
+        boosted_balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount
+    );
+    assert_eq!(
+        get_boost(&mut app, &staking_reward, &tom_address),
+        Uint128::from(100000000u128)
+    );
+}
+
+#[test]
+fn test_partner_reward_token_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+    let jerry_address = Addr::unchecked("jerry".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    // any cw20 does as the partner token
+    let partner_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+
+    let duration = Uint128::from(1000000u128);
+    let add_reward_token_msg = |token: &Addr| ExecuteMsg::AddRewardToken {
+        token: token.clone(),
+        distributor: creator.clone(),
+        duration,
+    };
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &add_reward_token_msg(&partner_token),
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &add_reward_token_msg(&staking_token),
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &add_reward_token_msg(&partner_token),
+        &[],
+    );
+    assert!(res.is_ok());
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    for user in [&tom_address, &jerry_address] {
+        cw20_transfer(
+            &mut app,
+            &staking_token,
+            &creator,
+            user,
+            stake_amount.clone(),
+        );
+        stake(
+            user,
+            &mut app,
+            &staking_token,
+            &staking_reward,
+            &stake_amount,
+        );
+    }
+
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+    // the distributor approves the pool, which pulls the partner rewards
+    let res = app.execute_contract(
+        creator.clone(),
+        partner_token.clone(),
+        &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+            spender: staking_reward.to_string(),
+            amount: reward_amount,
+            expires: None,
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &NotifyRewardAmount {
+            token: Some(partner_token.clone()),
+            amount: reward_amount,
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &partner_token, &staking_reward).balance,
+        reward_amount
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(1000000u64);
+        block.height += 1000000u64;
+    });
+
+    // the partner period is over, each staker earned half of it
+    let tom_earned_all = earned_all(&mut app, &staking_reward, &tom_address);
+    assert_eq!(tom_earned_all.rewards.len(), 2);
+    assert_eq!(tom_earned_all.rewards[0].token, ve_seilor);
+    assert_eq!(
+        tom_earned_all.rewards[0].earned,
+        earned(&mut app, &staking_reward, &tom_address)
+    );
+    assert_eq!(tom_earned_all.rewards[1].token, partner_token);
+    let tom_partner_earned = tom_earned_all.rewards[1].earned;
+    assert!(tom_partner_earned <= reward_amount.div(Uint128::from(2u128)));
+    assert!(tom_partner_earned + Uint128::from(1u128) >= reward_amount.div(Uint128::from(2u128)));
+
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetRewards {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &partner_token, &tom_address).balance,
+        tom_partner_earned
+    );
+    assert_eq!(
+        cw20_balance(&mut app, &ve_seilor, &tom_address).balance,
+        tom_earned_all.rewards[0].earned
+    );
+    let tom_earned_all = earned_all(&mut app, &staking_reward, &tom_address);
+    assert!(tom_earned_all
+        .rewards
+        .iter()
+        .all(|reward| reward.earned.is_zero()));
+}
+
+#[test]
+fn test_lock_positions_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+    let jerry_address = Addr::unchecked("jerry".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+
+    let lock_duration = Uint128::from(8640000u128);
+    let lock_tiers = vec![LockTierItem {
+        duration: lock_duration,
+        multiplier: Uint128::from(200000000u128),
+    }];
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::SetLockTiers {
+            lock_tiers: lock_tiers.clone(),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::SetLockTiers {
+            lock_tiers: lock_tiers.clone(),
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    let res: LockTiersResponse = app
+        .wrap()
+        .query_wasm_smart(staking_reward.clone(), &QueryMsg::LockTiers {})
+        .unwrap();
+    assert_eq!(res.lock_tiers, lock_tiers);
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    for user in [&tom_address, &jerry_address] {
+        cw20_transfer(
+            &mut app,
+            &staking_token,
+            &creator,
+            user,
+            stake_amount.clone(),
+        );
+    }
+    // only the configured durations can be locked
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_token.clone(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: staking_reward.to_string(),
+            amount: stake_amount,
+            msg: to_binary(&StakeLocked {
+                duration: Uint128::from(86400u128),
+            })
+            .unwrap(),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_token.clone(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: staking_reward.to_string(),
+            amount: stake_amount,
+            msg: to_binary(&StakeLocked {
+                duration: lock_duration,
+            })
+            .unwrap(),
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    stake(
+        &jerry_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+
+    // tom's position weighs 2x
+    let positions = user_positions(&mut app, &staking_reward, &tom_address).positions;
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].amount, stake_amount);
+    assert_eq!(
+        positions[0].unlock_time,
+        Uint128::from(block_time) + lock_duration
+    );
+    assert_eq!(positions[0].bonus, stake_amount);
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount
+    );
+    assert_eq!(
+        boosted_balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount.mul(Uint128::from(2u128))
+    );
+    let staking_state = query_staking_state(&mut app, &staking_reward);
+    assert_eq!(
+        staking_state.total_supply,
+        stake_amount.mul(Uint128::from(2u128))
+    );
+    assert_eq!(staking_state.total_lock_bonus, stake_amount);
+    assert_eq!(
+        staking_state.boosted_supply,
+        stake_amount.mul(Uint128::from(3u128))
+    );
+
+    // locked tokens can not be withdrawn, neither directly nor before the lock expires
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Withdraw {
+            amount: Uint128::one(),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::WithdrawPosition {
+            id: positions[0].id,
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(8640000u64);
+        block.height += 1000000u64;
+    });
+
+    // once expired the kick drops the bonus, then the position can be withdrawn
+    let res = app.execute_contract(
+        jerry_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Kick {
+            user: tom_address.clone(),
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        boosted_balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount
+    );
+    assert_eq!(
+        user_positions(&mut app, &staking_reward, &tom_address).positions[0].bonus,
+        Uint128::zero()
+    );
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::WithdrawPosition {
+            id: positions[0].id,
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &tom_address).balance,
+        stake_amount
+    );
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &tom_address),
+        Uint128::zero()
+    );
+    assert!(user_positions(&mut app, &staking_reward, &tom_address)
+        .positions
+        .is_empty());
+    let staking_state = query_staking_state(&mut app, &staking_reward);
+    assert_eq!(staking_state.total_supply, stake_amount);
+    assert_eq!(staking_state.total_lock_bonus, Uint128::zero());
+    assert_eq!(staking_state.boosted_supply, stake_amount);
+}
+
+#[test]
+fn test_unbonding_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+    update_unbonding_period(&creator, &mut app, &staking_reward, 604800u128);
+    assert_eq!(
+        query_staking_config(&mut app, &staking_reward).unbonding_period,
+        Uint128::from(604800u128)
+    );
+
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &tom_address,
+        stake_amount.clone(),
+    );
+    stake(
+        &tom_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+
+    // the withdrawn tokens wait in the queue and stop earning
+    withdraw(&tom_address, &mut app, &staking_reward, &stake_amount);
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &tom_address).balance,
+        Uint128::zero()
+    );
+    let requests = unbonding_requests(&mut app, &staking_reward, &tom_address).requests;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].amount, stake_amount);
+    let release_time = Uint128::from(block_time + 86400u64 + 604800u64);
+    assert_eq!(requests[0].release_time, release_time);
+    let staking_state = query_staking_state(&mut app, &staking_reward);
+    assert_eq!(staking_state.total_supply, Uint128::zero());
+    assert_eq!(staking_state.total_unbonding, stake_amount);
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+
+    // a longer period only applies to later withdrawals
+    update_unbonding_period(&creator, &mut app, &staking_reward, 1209600u128);
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+    assert_eq!(earned(&mut app, &staking_reward, &tom_address), tom_earned);
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::ClaimUnbonded {},
+        &[],
+    );
+    assert!(res.is_err());
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(518400u64);
+        block.height += 100000u64;
+    });
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::ClaimUnbonded {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &tom_address).balance,
+        stake_amount
+    );
+    assert!(unbonding_requests(&mut app, &staking_reward, &tom_address)
+        .requests
+        .is_empty());
+    assert_eq!(
+        query_staking_state(&mut app, &staking_reward).total_unbonding,
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_early_claim_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+    let jerry_address = Addr::unchecked("jerry".to_string());
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, creator.clone(), true);
+
+    // tom locks for a year, doubling his rewards
+    ve_fund_mint(
+        &creator,
+        &mut app,
+        &fund,
+        &tom_address,
+        Uint128::from(1000000u128),
+    );
+    let res = app.execute_contract(
+        tom_address.clone(),
+        boost.clone(),
+        &boost::msg::ExecuteMsg::Lock {
+            index: 3,
+            amount: Uint128::from(1000000u128),
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    for user in [&tom_address, &jerry_address] {
+        cw20_transfer(
+            &mut app,
+            &staking_token,
+            &creator,
+            user,
+            stake_amount.clone(),
+        );
+        stake(
+            user,
+            &mut app,
+            &staking_token,
+            &staking_reward,
+            &stake_amount,
+        );
+    }
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(864000u64);
+        block.height += 100000u64;
+    });
+
+    // half of tom's rewards come from his boost
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetReward {},
+        &[],
+    );
+    assert!(res.is_err());
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    let tom_base_earned = base_earned(&mut app, &staking_reward, &tom_address);
+    assert!(tom_base_earned.mul(Uint128::from(2u128)) <= tom_earned + Uint128::from(2u128));
+    assert!(tom_earned <= tom_base_earned.mul(Uint128::from(2u128)) + Uint128::from(2u128));
+    let jerry_earned = earned(&mut app, &staking_reward, &jerry_address);
+
+    // claiming early pays out the base part, the boost part goes back to the pool
+    let tom_ve_balance = cw20_balance(&mut app, &ve_seilor, &tom_address).balance;
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetRewardEarly {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &ve_seilor, &tom_address).balance,
+        tom_ve_balance + tom_base_earned
+    );
+    assert_eq!(
+        earned(&mut app, &staking_reward, &tom_address),
+        Uint128::zero()
+    );
+    let reward_budget = reward_budget(&mut app, &staking_reward);
+    assert_eq!(reward_budget.total_claimed, tom_base_earned);
+    assert!(reward_budget.total_accrued <= tom_base_earned + jerry_earned + Uint128::from(2u128));
+
+    // everything notified but the claimed base part is shared out by the end of the period
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(2592000u64);
+        block.height += 100000u64;
+    });
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    let jerry_earned = earned(&mut app, &staking_reward, &jerry_address);
+    assert!(tom_base_earned + tom_earned + jerry_earned <= reward_amount);
+    assert!(tom_base_earned + tom_earned + jerry_earned + Uint128::from(4u128) >= reward_amount);
+
+    // returned to the reward controller instead
+    update_forfeit_mode(
+        &creator,
+        &mut app,
+        &staking_reward,
+        ForfeitMode::ReturnToController,
+    );
+    let tom_base_earned = base_earned(&mut app, &staking_reward, &tom_address);
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetRewardEarly {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &ve_seilor, &kusd_reward_addr).balance,
+        tom_earned - tom_base_earned
+    );
+
+    // redistributed again, an epoch waiting to start takes them rather than the running period
+    update_forfeit_mode(
+        &creator,
+        &mut app,
+        &staking_reward,
+        ForfeitMode::Redistribute,
+    );
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+    let epoch_start = app.block_info().time.seconds() + 1000000u64;
+    let res = schedule_rewards(
+        &kusd_reward_addr,
+        &mut app,
+        &staking_reward,
+        epoch_start,
+        epoch_start + 10000u64,
+        reward_amount,
+    );
+    assert!(res);
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(864000u64);
+        block.height += 100000u64;
+    });
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    let tom_base_earned = base_earned(&mut app, &staking_reward, &tom_address);
+    assert!(tom_earned > tom_base_earned);
+    let reward_rate = query_staking_state(&mut app, &staking_reward).reward_rate;
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetRewardEarly {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        query_staking_state(&mut app, &staking_reward).reward_rate,
+        reward_rate
+    );
+    assert_eq!(
+        query_reward_schedule(&mut app, &staking_reward).upcoming[0].redistributed,
+        tom_earned - tom_base_earned
+    );
+}
+
+#[test]
+fn test_reward_schedule_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &tom_address,
+        stake_amount.clone(),
+    );
+    stake(
+        &tom_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+
+    // only the reward controller can schedule, and not in the past
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let first_start = block_time + 1000u64;
+    let first_end = first_start + 10000u64;
+    let res = schedule_rewards(
+        &tom_address,
+        &mut app,
+        &staking_reward,
+        first_start,
+        first_end,
+        reward_amount,
+    );
+    assert!(!res);
+    let res = schedule_rewards(
+        &kusd_reward_addr,
+        &mut app,
+        &staking_reward,
+        block_time - 1u64,
+        first_end,
+        reward_amount,
+    );
+    assert!(!res);
+    let res = schedule_rewards(
+        &kusd_reward_addr,
+        &mut app,
+        &staking_reward,
+        first_start,
+        first_end,
+        reward_amount,
+    );
+    assert!(res);
+    let second_start = first_end + 5000u64;
+    let second_end = second_start + 10000u64;
+    let res = schedule_rewards(
+        &kusd_reward_addr,
+        &mut app,
+        &staking_reward,
+        second_start,
+        second_end,
+        reward_amount,
+    );
+    assert!(res);
+
+    let reward_schedule = query_reward_schedule(&mut app, &staking_reward);
+    assert!(reward_schedule.active.is_none());
+    assert_eq!(reward_schedule.upcoming.len(), 2);
+    assert_eq!(
+        reward_schedule.upcoming[0].start,
+        Uint128::from(first_start)
+    );
+
+    // the first epoch starts lazily, nothing is earned before its start
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(6000u64);
+        block.height += 100000u64;
+    });
+    let half_reward = reward_amount.multiply_ratio(1u128, 2u128);
+    assert_eq!(earned(&mut app, &staking_reward, &tom_address), half_reward);
+    let reward_schedule = query_reward_schedule(&mut app, &staking_reward);
+    let active = reward_schedule.active.unwrap();
+    assert_eq!(active.start, Uint128::from(first_start));
+    assert_eq!(active.end, Uint128::from(first_end));
+    assert_eq!(reward_schedule.upcoming.len(), 1);
+    assert_eq!(
+        reward_budget(&mut app, &staking_reward).total_notified,
+        Uint128::zero()
+    );
+
+    get_reward(&tom_address, &mut app, &staking_reward);
+    assert_eq!(
+        cw20_balance(&mut app, &ve_seilor, &tom_address).balance,
+        half_reward
+    );
+    assert_eq!(
+        reward_budget(&mut app, &staking_reward).total_notified,
+        reward_amount
+    );
+
+    // both epochs run out without any update in between
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(40000u64);
+        block.height += 100000u64;
+    });
+    assert_eq!(
+        earned(&mut app, &staking_reward, &tom_address),
+        reward_amount.mul(Uint128::from(2u128)) - half_reward
+    );
+    let reward_schedule = query_reward_schedule(&mut app, &staking_reward);
+    assert!(reward_schedule.active.is_none());
+    assert!(reward_schedule.upcoming.is_empty());
+    get_reward(&tom_address, &mut app, &staking_reward);
+    let reward_budget = reward_budget(&mut app, &staking_reward);
+    assert_eq!(
+        reward_budget.total_notified,
+        reward_amount.mul(Uint128::from(2u128))
+    );
+    assert_eq!(
+        reward_budget.total_claimed,
+        reward_amount.mul(Uint128::from(2u128))
+    );
+}
+
+#[test]
+fn test_emergency_withdraw_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+    let lock_duration = Uint128::from(8640000u128);
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::SetLockTiers {
+            lock_tiers: vec![LockTierItem {
+                duration: lock_duration,
+                multiplier: Uint128::from(200000000u128),
+            }],
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &tom_address,
+        stake_amount.mul(Uint128::from(2u128)),
+    );
+    stake(
+        &tom_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_token.clone(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: staking_reward.to_string(),
+            amount: stake_amount,
+            msg: to_binary(&StakeLocked {
+                duration: lock_duration,
+            })
+            .unwrap(),
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+
+    // the unlocked balance comes back, the position stays and the rewards are gone
+    assert!(earned(&mut app, &staking_reward, &tom_address) > Uint128::zero());
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::EmergencyWithdraw {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &tom_address).balance,
+        stake_amount
+    );
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount
+    );
+    assert_eq!(
+        earned(&mut app, &staking_reward, &tom_address),
+        Uint128::zero()
+    );
+    assert_eq!(
+        user_positions(&mut app, &staking_reward, &tom_address)
+            .positions
+            .len(),
+        1
+    );
+
+    // only gov or the factory can shut it down, which stops rewards and new stakes
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Shutdown {},
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Shutdown {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert!(query_staking_config(&mut app, &staking_reward).shutdown);
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+    assert_eq!(earned(&mut app, &staking_reward, &tom_address), tom_earned);
+    let res = app.execute_contract(
+        kusd_reward_addr.clone(),
+        staking_reward.clone(),
+        &NotifyRewardAmount {
+            token: None,
+            amount: reward_amount,
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_token.clone(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: staking_reward.to_string(),
+            amount: stake_amount,
+            msg: to_binary(&Stake {}).unwrap(),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // once shut down the locked position can be withdrawn too
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::EmergencyWithdraw {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &tom_address).balance,
+        stake_amount.mul(Uint128::from(2u128))
+    );
+    assert!(user_positions(&mut app, &staking_reward, &tom_address)
+        .positions
+        .is_empty());
+    let staking_state = query_staking_state(&mut app, &staking_reward);
+    assert_eq!(staking_state.total_supply, Uint128::zero());
+    assert_eq!(staking_state.total_lock_bonus, Uint128::zero());
+}
+
+#[test]
+fn test_rescue_tokens_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+    let recipient = Addr::unchecked("recipient".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let partner_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::AddRewardToken {
+            token: partner_token.clone(),
+            distributor: creator.clone(),
+            duration: Uint128::from(1000000u128),
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &tom_address,
+        stake_amount,
+    );
+    stake(
+        &tom_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let res = app.execute_contract(
+        creator.clone(),
+        partner_token.clone(),
+        &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+            spender: staking_reward.to_string(),
+            amount: reward_amount,
+            expires: None,
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &NotifyRewardAmount {
+            token: Some(partner_token.clone()),
+            amount: reward_amount,
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+
+    // tokens sent to the pool by mistake
+    let stray_amount = Uint128::from(1000u128);
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &staking_reward,
+        stray_amount,
+    );
+    cw20_transfer(
+        &mut app,
+        &partner_token,
+        &creator,
+        &staking_reward,
+        stray_amount,
+    );
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(500000u64);
+        block.height += 500000u64;
+    });
+
+    assert!(!rescue_tokens(
+        &tom_address,
+        &mut app,
+        &staking_reward,
+        &staking_token,
+        stray_amount,
+        &recipient,
+    ));
+    // the staked tokens are reserved for the stakers
+    assert!(!rescue_tokens(
+        &creator,
+        &mut app,
+        &staking_reward,
+        &staking_token,
+        stray_amount + Uint128::one(),
+        &recipient,
+    ));
+    assert!(rescue_tokens(
+        &creator,
+        &mut app,
+        &staking_reward,
+        &staking_token,
+        stray_amount,
+        &recipient,
+    ));
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &recipient).balance,
+        stray_amount
+    );
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &staking_reward).balance,
+        stake_amount
+    );
+
+    // so are the partner rewards, earned or still to be streamed
+    assert!(!rescue_tokens(
+        &creator,
+        &mut app,
+        &staking_reward,
+        &partner_token,
+        stray_amount + reward_amount.div(Uint128::from(2u128)),
+        &recipient,
+    ));
+    assert!(rescue_tokens(
+        &creator,
+        &mut app,
+        &staking_reward,
+        &partner_token,
+        stray_amount,
+        &recipient,
+    ));
+    let tom_partner_earned = earned_all(&mut app, &staking_reward, &tom_address).rewards[1].earned;
+    assert!(tom_partner_earned > Uint128::zero());
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetRewards {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &partner_token, &tom_address).balance,
+        tom_partner_earned
+    );
+
+    // after the period and every claim only rounding dust is left
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(500000u64);
+        block.height += 500000u64;
+    });
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetRewards {},
+        &[],
+    );
+    assert!(res.is_ok());
+    let left = cw20_balance(&mut app, &partner_token, &staking_reward).balance;
+    assert!(left < Uint128::from(10u128));
+    if !left.is_zero() {
+        assert!(rescue_tokens(
+            &creator,
+            &mut app,
+            &staking_reward,
+            &partner_token,
+            left,
+            &recipient,
+        ));
+    }
+}
+
+#[test]
+fn test_receipt_token_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+    let jerry_address = Addr::unchecked("jerry".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+
+    let receipt_code_id = store_staking_receipt_contract(&mut app);
+    let staking_reward_code_id = app.store_code(Box::new(
+        ContractWrapper::new_with_empty(execute, instantiate, query).with_reply(reply),
+    ));
+    let mut instance_msg = mock_instantiate_msg(
+        staking_token.clone(),
+        ve_seilor.clone(),
+        boost.clone(),
+        fund.clone(),
+    );
+    instance_msg.receipt_token = Some(ReceiptTokenMsg {
+        code_id: receipt_code_id,
+        name: "Staked Token".to_string(),
+        symbol: "sTKN".to_string(),
+        decimals: 6,
+    });
+    let staking_reward = app
+        .instantiate_contract(
+            staking_reward_code_id,
+            creator.clone(),
+            &instance_msg,
+            &[], // no funds
+            String::from("Staking"),
+            None,
+        )
+        .unwrap();
+    let receipt_token = query_staking_config(&mut app, &staking_reward)
+        .receipt_token
+        .unwrap();
+
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+
+    let lock_duration = Uint128::from(8640000u128);
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::SetLockTiers {
+            lock_tiers: vec![LockTierItem {
+                duration: lock_duration,
+                multiplier: Uint128::from(200000000u128),
+            }],
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+
+    // only the unlocked stake gets receipts
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &tom_address,
+        stake_amount.mul(Uint128::from(2u128)),
+    );
+    stake(
+        &tom_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_token.clone(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: staking_reward.to_string(),
+            amount: stake_amount,
+            msg: to_binary(&StakeLocked {
+                duration: lock_duration,
+            })
+            .unwrap(),
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &receipt_token, &tom_address).balance,
+        stake_amount
+    );
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+
+    // transferring receipts moves the stake, rewards accrued so far stay with tom
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    assert!(tom_earned > Uint128::zero());
+    let moved_amount = stake_amount.multiply_ratio(1u128, 2u128);
+    let res = app.execute_contract(
+        tom_address.clone(),
+        receipt_token.clone(),
+        &cw20_base::msg::ExecuteMsg::Transfer {
+            recipient: jerry_address.to_string(),
+            amount: moved_amount,
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount.mul(Uint128::from(2u128)) - moved_amount
+    );
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &jerry_address),
+        moved_amount
+    );
+    assert_eq!(earned(&mut app, &staking_reward, &tom_address), tom_earned);
+    assert_eq!(
+        earned(&mut app, &staking_reward, &jerry_address),
+        Uint128::zero()
+    );
+
+    // receipts can't move the locked position
+    let res = app.execute_contract(
+        tom_address.clone(),
+        receipt_token.clone(),
+        &cw20_base::msg::ExecuteMsg::Transfer {
+            recipient: jerry_address.to_string(),
+            amount: moved_amount + Uint128::one(),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    // only the receipt token can move stake
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::MoveStake {
+            from: tom_address.clone(),
+            to: jerry_address.clone(),
+            amount: Uint128::one(),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(86400u64);
+        block.height += 100000u64;
+    });
+    assert!(earned(&mut app, &staking_reward, &jerry_address) > Uint128::zero());
+
+    // withdrawing burns the receipts
+    withdraw(&jerry_address, &mut app, &staking_reward, &moved_amount);
+    assert_eq!(
+        cw20_balance(&mut app, &receipt_token, &jerry_address).balance,
+        Uint128::zero()
+    );
+    assert_eq!(
+        cw20_balance(&mut app, &staking_token, &jerry_address).balance,
+        moved_amount
+    );
+    let res = app.execute_contract(
+        tom_address.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::EmergencyWithdraw {},
+        &[],
+    );
+    assert!(res.is_ok());
+    assert_eq!(
+        cw20_balance(&mut app, &receipt_token, &tom_address).balance,
+        Uint128::zero()
+    );
+    assert_eq!(
+        balance_of(&mut app, &staking_reward, &tom_address),
+        stake_amount
+    );
+}
+
+#[test]
+fn test_pool_apr_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+
+    // nothing to earn before the first period
+    let apr_response = pool_apr(&mut app, &staking_reward);
+    assert_eq!(apr_response.apr, Uint128::zero());
+    assert_eq!(apr_response.remaining_rewards, Uint128::zero());
+
+    let reward_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(1000u128));
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+    let stake_amount = Uint128::from(10u128).pow(6).mul(Uint128::from(10000u128));
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &tom_address,
+        stake_amount.clone(),
+    );
+    stake(
+        &tom_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+
+    let duration = query_staking_state(&mut app, &staking_reward).duration;
+    let apr_response = pool_apr(&mut app, &staking_reward);
+    assert_eq!(apr_response.reward_rate, reward_amount / duration);
+    assert_eq!(apr_response.remaining_seconds, duration);
+    assert_eq!(apr_response.reward_supply, stake_amount);
+    // 1000 rewards over 30 days for 10000 staked tokens
+    let expected_apr = reward_amount.multiply_ratio(
+        31536000u128 * 1000000000000000000u128,
+        duration * stake_amount,
+    );
+    assert!(apr_response.apr <= expected_apr);
+    assert!(apr_response.apr + Uint128::from(1000000u128) >= expected_apr);
+
+    // the only staker gets everything emitted, projections stop at the end of the period
+    let elapsed = 100000u64;
+    let projection = projected_earnings(
+        &mut app,
+        &staking_reward,
+        &tom_address,
+        Uint128::from(elapsed),
+    );
+    assert_eq!(projection.earned, Uint128::zero());
+    assert_eq!(projection.boost, Uint128::from(100000000u128));
+    assert_eq!(projection.apr, apr_response.apr);
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(elapsed);
+        block.height += 100000u64;
+    });
+    let tom_earned = earned(&mut app, &staking_reward, &tom_address);
+    assert!(tom_earned <= projection.projected + Uint128::one());
+    assert!(projection.projected <= tom_earned + Uint128::one());
+
+    let projection = projected_earnings(
+        &mut app,
+        &staking_reward,
+        &tom_address,
+        duration.mul(Uint128::from(2u128)),
+    );
+    assert_eq!(projection.earned, tom_earned);
+    assert!(tom_earned + projection.projected <= reward_amount);
+    assert!(tom_earned + projection.projected + Uint128::from(2u128) >= reward_amount);
+}
+
+#[test]
+fn test_reward_schedule_carry_integration() {
+    let creator = Addr::unchecked(CREATOR);
+    let block_time = 10000000u64;
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time.clone()));
+
+    let tom_address = Addr::unchecked("tom".to_string());
+
+    let staking_token = staking_token_instance(&creator, &mut app);
+    let ve_seilor = ve_seilor_instance(&creator, &mut app);
+    let seilor = seilor_instance(&creator, &mut app);
+    let boost = boost_instance(&creator, &mut app, &ve_seilor);
+    let fund = fund_instance(&creator, &mut app, &ve_seilor, &seilor);
+    let staking_reward = starking_reward_instance(
+        &creator,
+        &mut app,
+        &staking_token,
+        &ve_seilor,
+        &boost,
+        &fund,
+    );
+    set_seilor_to_fund(&creator, &mut app, &seilor, &fund);
+    set_ve_seilor_to_fund(&creator, &mut app, &ve_seilor, &fund);
+    set_fund_ve_token_miners(&creator, &mut app, &fund, staking_reward.clone(), true);
+
+    let stake_amount = Uint128::from(10u128).pow(12).mul(Uint128::from(10000u128));
+    cw20_transfer(
+        &mut app,
+        &staking_token,
+        &creator,
+        &tom_address,
+        stake_amount.clone(),
+    );
+    stake(
+        &tom_address,
+        &mut app,
+        &staking_token,
+        &staking_reward,
+        &stake_amount,
+    );
+
+    // a period paying 1000 per second over the 2592000 seconds duration, and an epoch paying as
+    // much starting while it runs
+    let kusd_reward_addr = Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string());
+    let reward_amount = Uint128::from(2592000000u128);
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+    let epoch_start = block_time + 1000u64;
+    let epoch_end = epoch_start + 10000u64;
+    let res = schedule_rewards(
+        &kusd_reward_addr,
+        &mut app,
+        &staking_reward,
+        epoch_start,
+        epoch_end,
+        Uint128::from(10000000u128),
+    );
+    assert!(res);
+
+    // the leftover of the period keeps its rate next to the epoch
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(6000u64);
+        block.height += 100000u64;
+    });
+    assert_eq!(
+        earned(&mut app, &staking_reward, &tom_address),
+        Uint128::from(11000000u128)
+    );
+    let active = query_reward_schedule(&mut app, &staking_reward)
+        .active
+        .unwrap();
+    let rate = Uint256::from(1000u128).mul(Uint256::from(BASE_RATE_18));
+    assert_eq!(active.reward_rate, rate);
+    assert_eq!(active.carried_rate, rate);
+    assert_eq!(active.carried_until, Uint128::from(block_time + 2592000u64));
+
+    // no notify while the epoch runs
+    let res = app.execute_contract(
+        kusd_reward_addr.clone(),
+        staking_reward.clone(),
+        &NotifyRewardAmount {
+            token: None,
+            amount: reward_amount,
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // after the epoch the period goes on alone, and a notify folds its leftover in
+    app.update_block(|block| {
+        block.time = block.time.plus_seconds(15000u64);
+        block.height += 100000u64;
+    });
+    assert_eq!(
+        earned(&mut app, &staking_reward, &tom_address),
+        Uint128::from(31000000u128)
+    );
+    notify_reward_amount(&kusd_reward_addr, &mut app, &staking_reward, &reward_amount);
+    let staking_state = query_staking_state(&mut app, &staking_reward);
+    assert_eq!(staking_state.carried_rate, Uint256::zero());
+    assert_eq!(
+        staking_state.reward_rate,
+        Uint256::from(reward_amount + Uint128::from(2571000000u128))
+            .multiply_ratio(Uint256::from(BASE_RATE_18), Uint256::from(2592000u128))
+    );
+}
+
+fn reward_per_token(app: &mut App, staking_reward: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::RewardPerToken {},
+    );
+    let res: RewardPerTokenResponse = res.unwrap();
+    res.reward_per_token
+}
+
+fn last_time_reward_applicable(app: &mut App, staking_reward: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::LastTimeRewardApplicable {},
+    );
+    let res: LastTimeRewardApplicableResponse = res.unwrap();
+    res.last_time_reward_applicable
+}
+
+fn get_boost(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::GetBoost {
+            account: user.clone(),
+        },
+    );
+    let res: GetBoostResponse = res.unwrap();
+    res.boost
+}
+
+fn earned(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::Earned {
+            account: user.clone(),
+        },
+    );
+    let res: EarnedResponse = res.unwrap();
+    res.earned
+}
+
+fn query_staking_config(app: &mut App, staking_reward: &Addr) -> StakingConfigResponse {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::QueryStakingConfig {},
+    );
+    let res: StakingConfigResponse = res.unwrap();
+    res
+}
+
+fn query_staking_state(app: &mut App, staking_reward: &Addr) -> StakingStateResponse {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::QueryStakingState {},
+    );
+    let res: StakingStateResponse = res.unwrap();
+    res
+}
+fn get_user_updated_at(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::GetUserUpdatedAt {
+            account: user.clone(),
+        },
+    );
+    let res: GetUserUpdatedAtResponse = res.unwrap();
+    res.updated_at
+}
+fn get_user_reward_per_token_paid(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::GetUserRewardPerTokenPaid {
+            account: user.clone(),
+        },
+    );
+    let res: GetUserRewardPerTokenPaidResponse = res.unwrap();
+    res.reward_per_token_paid
+}
+
+fn balance_of(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::BalanceOf {
+            account: user.clone(),
+        },
+    );
+    let res: BalanceOfResponse = res.unwrap();
+    res.balance_of
+}
+
+// fn set_ve_seilor_miners(
+//     creator: &Addr,
+//     app: &mut App,
+//     ve_seilor: &Addr,
+//     contracts: Vec<Addr>,
+//     is_minter: Vec<bool>,
+// ) {
+//     let ve_seilor_miner_msg = ve_seilor::msg::ExecuteMsg::SetMinters {
+//         contracts,
+//         is_minter,
+//     };
+//     let res = app.execute_contract(
+//         creator.clone(),
+//         ve_seilor.clone(),
+//         &ve_seilor_miner_msg,
+//         &[],
+//     );
+//     assert!(res.is_ok());
+// }
+
+fn set_ve_seilor_to_fund(creator: &Addr, app: &mut App, ve_seilor: &Addr, fund: &Addr) {
+    let ve_seilor_config = ve_seilor::msg::ExecuteMsg::UpdateConfig {
+        max_minted: None,
+        fund: Some(fund.clone()),
+    };
+    let res = app.execute_contract(creator.clone(), ve_seilor.clone(), &ve_seilor_config, &[]);
+    assert!(res.is_ok());
+}
+
+fn set_seilor_to_fund(creator: &Addr, app: &mut App, seilor: &Addr, fund: &Addr) {
+    let update_seilor_fund_msg = seilor::msg::ExecuteMsg::UpdateConfig {
+        fund: Some(fund.clone()),
+        distribute: None,
+        cross_chain_swap_contract: None,
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        seilor.clone(),
+        &update_seilor_fund_msg,
+        &[],
+    );
+    assert!(res.is_ok());
+}
+
+fn notify_reward_amount(
+    creator: &Addr,
+    app: &mut App,
+    staking_reward: &Addr,
+    reward_amount: &Uint128,
+) {
+    let reward_msg = NotifyRewardAmount {
+        token: None,
+        amount: reward_amount.clone(),
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        staking_reward.clone(),
+        &reward_msg,
+        &[], // no funds
+    );
+    assert!(res.is_ok());
+}
+
+fn get_reward(user: &Addr, app: &mut App, staking_reward: &Addr) {
+    let res = app.execute_contract(
+        user.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::GetReward {},
+        &[], // no funds
+    );
+    assert!(res.is_ok());
+}
+
+fn withdraw(user: &Addr, app: &mut App, staking_reward: &Addr, amount: &Uint128) {
+    let res = app.execute_contract(
+        user.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::Withdraw {
+            amount: amount.clone(),
+        },
+        &[], // no funds
+    );
+    println!("{:?}", res);
+    assert!(res.is_ok());
+}
+
+fn cw20_balance(app: &mut App, cw20_token: &Addr, user: &Addr) -> BalanceResponse {
+    let query_msg = cw20_base::msg::QueryMsg::Balance {
+        address: user.clone().to_string(),
+    };
+
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(cw20_token.clone().to_string(), &query_msg)
+        .unwrap();
+    res
+}
+
+fn cw20_transfer(
+    app: &mut App,
+    cw20_token: &Addr,
     sender: &Addr,
     recipient: &Addr,
     amount: Uint128,
@@ -631,6 +2430,26 @@ fn stake(
     }
 }
 
+fn working_balance_of(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::WorkingBalanceOf {
+            account: user.clone(),
+        },
+    );
+    let res: WorkingBalanceOfResponse = res.unwrap();
+    res.working_balance
+}
+
+fn ve_fund_mint(minter: &Addr, app: &mut App, fund: &Addr, user: &Addr, amount: Uint128) {
+    let ve_fund_mint_msg = fund::msg::ExecuteMsg::VeFundMint {
+        user: user.clone(),
+        amount,
+    };
+    let res = app.execute_contract(minter.clone(), fund.clone(), &ve_fund_mint_msg, &[]);
+    assert!(res.is_ok());
+}
+
 fn set_fund_ve_token_miners(
     creator: &Addr,
     app: &mut App,
@@ -645,3 +2464,187 @@ fn set_fund_ve_token_miners(
     let res = app.execute_contract(creator.clone(), fund.clone(), &ve_fund_miner_msg, &[]);
     assert!(res.is_ok());
 }
+
+fn boosted_balance_of(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::BoostedBalanceOf {
+            account: user.clone(),
+        },
+    );
+    let res: BoostedBalanceOfResponse = res.unwrap();
+    res.boosted_balance
+}
+
+fn reward_budget(app: &mut App, staking_reward: &Addr) -> RewardBudgetResponse {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::RewardBudget {},
+    );
+    res.unwrap()
+}
+
+fn earned_all(app: &mut App, staking_reward: &Addr, user: &Addr) -> EarnedAllResponse {
+    let res = app.wrap().query_wasm_smart(
+        staking_reward.clone().to_string(),
+        &QueryMsg::EarnedAll {
+            account: user.clone(),
+        },
+    );
+    res.unwrap()
+}
+
+fn user_positions(app: &mut App, staking_reward: &Addr, user: &Addr) -> UserPositionsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            staking_reward.clone(),
+            &QueryMsg::UserPositions {
+                account: user.clone(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+}
+
+fn update_unbonding_period(gov: &Addr, app: &mut App, staking_reward: &Addr, period: u128) {
+    let res = app.execute_contract(
+        gov.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::UpdateStakingConfig {
+            config_msg: UpdateStakingConfigStruct {
+                boost: None,
+                fund: None,
+                reward_controller_addr: None,
+                unbonding_period: Some(Uint128::from(period)),
+                forfeit_mode: None,
+            },
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+}
+
+fn update_forfeit_mode(
+    gov: &Addr,
+    app: &mut App,
+    staking_reward: &Addr,
+    forfeit_mode: ForfeitMode,
+) {
+    let res = app.execute_contract(
+        gov.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::UpdateStakingConfig {
+            config_msg: UpdateStakingConfigStruct {
+                boost: None,
+                fund: None,
+                reward_controller_addr: None,
+                unbonding_period: None,
+                forfeit_mode: Some(forfeit_mode),
+            },
+        },
+        &[],
+    );
+    assert!(res.is_ok());
+}
+
+fn base_earned(app: &mut App, staking_reward: &Addr, user: &Addr) -> Uint128 {
+    let res: BaseEarnedResponse = app
+        .wrap()
+        .query_wasm_smart(
+            staking_reward.clone(),
+            &QueryMsg::BaseEarned {
+                account: user.clone(),
+            },
+        )
+        .unwrap();
+    res.base_earned
+}
+
+fn unbonding_requests(
+    app: &mut App,
+    staking_reward: &Addr,
+    user: &Addr,
+) -> UnbondingRequestsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            staking_reward.clone(),
+            &QueryMsg::UnbondingRequests {
+                account: user.clone(),
+            },
+        )
+        .unwrap()
+}
+
+fn schedule_rewards(
+    sender: &Addr,
+    app: &mut App,
+    staking_reward: &Addr,
+    start: u64,
+    end: u64,
+    amount: Uint128,
+) -> bool {
+    app.execute_contract(
+        sender.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::ScheduleRewards {
+            start: Uint128::from(start),
+            end: Uint128::from(end),
+            amount,
+        },
+        &[],
+    )
+    .is_ok()
+}
+
+fn query_reward_schedule(app: &mut App, staking_reward: &Addr) -> RewardScheduleResponse {
+    app.wrap()
+        .query_wasm_smart(staking_reward.clone(), &QueryMsg::RewardSchedule {})
+        .unwrap()
+}
+
+fn rescue_tokens(
+    gov: &Addr,
+    app: &mut App,
+    staking_reward: &Addr,
+    token: &Addr,
+    amount: Uint128,
+    recipient: &Addr,
+) -> bool {
+    app.execute_contract(
+        gov.clone(),
+        staking_reward.clone(),
+        &ExecuteMsg::RescueTokens {
+            asset: AssetInfo::Token {
+                contract_addr: token.clone(),
+            },
+            amount,
+            recipient: recipient.clone(),
+        },
+        &[],
+    )
+    .is_ok()
+}
+
+fn pool_apr(app: &mut App, staking_reward: &Addr) -> PoolAprResponse {
+    app.wrap()
+        .query_wasm_smart(staking_reward.clone(), &QueryMsg::PoolApr {})
+        .unwrap()
+}
+
+fn projected_earnings(
+    app: &mut App,
+    staking_reward: &Addr,
+    account: &Addr,
+    seconds: Uint128,
+) -> ProjectedEarningsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            staking_reward.clone(),
+            &QueryMsg::ProjectedEarnings {
+                account: account.clone(),
+                seconds,
+            },
+        )
+        .unwrap()
+}
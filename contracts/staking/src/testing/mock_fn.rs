@@ -29,6 +29,10 @@ pub fn mock_instantiate_msg(
         fund,
         reward_controller_addr: Addr::unchecked(REWARD_CONTROLLER_ADDR.clone().to_string()),
         duration: Uint128::from(2592000u128),
+        boost_mode: None,
+        unbonding_period: None,
+        receipt_token: None,
+        factory: None,
     }
 }
 
@@ -46,9 +46,10 @@ pub fn mock_ve_seilor_instance_msg() -> ve_seilor::msg::InstantiateMsg {
     msg
 }
 
-pub fn mock_boost_instance_msg() -> boost::msg::InstantiateMsg {
+pub fn mock_boost_instance_msg(ve_seilor: &Addr) -> boost::msg::InstantiateMsg {
     let msg = boost::msg::InstantiateMsg {
         gov: None,
+        ve_seilor_addr: ve_seilor.clone(),
         ve_seilor_lock_settings: vec![
             VeSeilorLockSetting {
                 duration: Uint128::from(2592000u128),
@@ -1,12 +1,18 @@
-use crate::handler::{update_staking_config, update_staking_duration};
-use crate::msg::UpdateStakingConfigStruct;
-use crate::querier::query_staking_state;
-use crate::state::read_staking_config;
+use crate::constract::migrate;
+use crate::error::ContractError;
+use crate::handler::{seed_reward_budget, update_staking_config, update_staking_duration};
+use crate::helper::BASE_RATE_18;
+use crate::msg::{ForfeitMode, MigrateMsg, UpdateStakingConfigStruct};
+use crate::querier::{query_reward_budget, query_staking_state};
+use crate::state::{
+    read_staking_config, read_staking_state, store_balance_of, store_rewards, store_staking_state,
+};
 use crate::testing::mock_fn::{
     mock_instantiate, mock_instantiate_msg, BOOST_ADDR, FUND_ADDR, REWARD_TOKEN_ADDR,
     STAKING_TOKEN_ADDR,
 };
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{attr, Addr, Uint128, Uint256};
 
 #[test]
 fn test_instantiate() {
@@ -35,6 +41,8 @@ fn test_update_staking_config() {
         boost: Some(Addr::unchecked("new_boost".to_string())),
         fund: Some(Addr::unchecked("new_fund".to_string())),
         reward_controller_addr: Some(Addr::unchecked("new_reward_controller_addr".to_string())),
+        unbonding_period: Some(Uint128::from(604800u128)),
+        forfeit_mode: Some(ForfeitMode::ReturnToController),
     };
     let res = update_staking_config(deps.as_mut(), info.clone(), update_config_msg);
     assert!(res.is_ok());
@@ -57,6 +65,8 @@ fn test_update_staking_config() {
         staking_config.reward_controller_addr,
         Addr::unchecked("new_reward_controller_addr".to_string())
     );
+    assert_eq!(staking_config.unbonding_period, Uint128::from(604800u128));
+    assert_eq!(staking_config.forfeit_mode, ForfeitMode::ReturnToController);
 }
 
 #[test]
@@ -74,3 +84,66 @@ fn test_update_staking_duration() {
     let staking_state = query_staking_state(deps.as_ref()).unwrap();
     assert_eq!(staking_state.duration, Uint128::from(1000u128));
 }
+
+#[test]
+fn test_migrate_seeds_reward_budget() {
+    let staking_token = Addr::unchecked(STAKING_TOKEN_ADDR.clone().to_string());
+    let rewards_token = Addr::unchecked(REWARD_TOKEN_ADDR.clone().to_string());
+    let boost = Addr::unchecked(BOOST_ADDR.clone().to_string());
+    let fund = Addr::unchecked(FUND_ADDR.clone().to_string());
+    let msg = mock_instantiate_msg(staking_token, rewards_token, boost, fund);
+    let (mut deps, mut env, info, _) = mock_instantiate(msg.clone());
+
+    // a pool deployed before the reward budget, paying 1 token per second over 100 seconds
+    let now = Uint128::from(env.block.time.seconds());
+    let tom = Addr::unchecked("tom");
+    let jerry = Addr::unchecked("jerry");
+    let mut staking_state = read_staking_state(&deps.storage).unwrap();
+    staking_state.total_supply = Uint128::from(1000u128);
+    staking_state.reward_rate = Uint256::from(BASE_RATE_18);
+    staking_state.updated_at = now;
+    staking_state.finish_at = now + Uint128::from(100u128);
+    store_staking_state(&mut deps.storage, &staking_state).unwrap();
+    store_balance_of(&mut deps.storage, tom.clone(), &Uint128::from(600u128)).unwrap();
+    store_rewards(&mut deps.storage, tom, &Uint128::from(50u128)).unwrap();
+    store_balance_of(&mut deps.storage, jerry.clone(), &Uint128::from(400u128)).unwrap();
+    store_rewards(&mut deps.storage, jerry, &Uint128::zero()).unwrap();
+    cw2::set_contract_version(
+        &mut deps.storage,
+        "kryptonite.finance:cw20-ve-seilor-staking",
+        "0.1.0",
+    )
+    .unwrap();
+
+    // migrate only budgets the rest of the running period
+    env.block.time = env.block.time.plus_seconds(40);
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+    let reward_budget = query_reward_budget(deps.as_ref()).unwrap();
+    assert_eq!(reward_budget.total_accrued, Uint128::zero());
+    assert_eq!(reward_budget.total_notified, Uint128::from(60u128));
+    assert_eq!(
+        read_staking_state(&deps.storage).unwrap().boosted_supply,
+        Uint128::from(1000u128)
+    );
+
+    // gov adds what each staker had earned at the migration, one account at a time
+    let res = seed_reward_budget(deps.as_mut(), mock_info("tom", &[]), Some(1));
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+    seed_reward_budget(deps.as_mut(), info.clone(), Some(1)).unwrap();
+    let reward_budget = query_reward_budget(deps.as_ref()).unwrap();
+    assert_eq!(reward_budget.total_accrued, Uint128::from(16u128));
+    assert_eq!(reward_budget.total_notified, Uint128::from(76u128));
+    env.block.time = env.block.time.plus_seconds(20);
+    seed_reward_budget(deps.as_mut(), info.clone(), Some(1)).unwrap();
+    let reward_budget = query_reward_budget(deps.as_ref()).unwrap();
+    assert_eq!(reward_budget.total_accrued, Uint128::from(90u128));
+    assert_eq!(reward_budget.total_notified, Uint128::from(150u128));
+    let res = seed_reward_budget(deps.as_mut(), info.clone(), Some(1)).unwrap();
+    assert!(res.attributes.contains(&attr("done", "true")));
+    assert!(seed_reward_budget(deps.as_mut(), info, Some(1)).is_err());
+
+    // migrating again leaves the budget alone
+    migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+    let reward_budget = query_reward_budget(deps.as_ref()).unwrap();
+    assert_eq!(reward_budget.total_notified, Uint128::from(150u128));
+}
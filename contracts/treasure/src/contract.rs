@@ -1,6 +1,6 @@
 use crate::error::ContractError;
 use crate::handler::{
-    accept_gov, receive_cw20, set_gov, update_config, user_unlock, user_withdraw,
+    accept_gov, receive_cw20, rescue_tokens, set_gov, update_config, user_unlock, user_withdraw,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::querier::{query_config_infos, query_user_infos};
@@ -101,6 +101,11 @@ pub fn execute(
         // ExecuteMsg::PreMintNft { mint_num } => pre_mint_nft(deps, env, info, mint_num),
         ExecuteMsg::SetGov { gov } => set_gov(deps, info, gov),
         ExecuteMsg::AcceptGov {} => accept_gov(deps, info),
+        ExecuteMsg::RescueTokens {
+            asset,
+            amount,
+            recipient,
+        } => rescue_tokens(deps, env, info, asset, amount, recipient),
     }
 }
 
@@ -1,3 +1,4 @@
+use common::rescue::RescueError;
 use cosmwasm_std::{OverflowError, StdError};
 use std::num::ParseIntError;
 use thiserror::Error;
@@ -57,4 +58,6 @@ pub enum ContractError {
     InvalidNftStartPreMintTimeAndEndLockTime {},
     #[error("Invalid Nft End Pre Mint Time")]
     InvalidNftEndPreMintTime {},
+    #[error("{0}")]
+    Rescue(#[from] RescueError),
 }
@@ -1,6 +1,6 @@
 use crate::error::ContractError;
 use crate::helper::BASE_RATE_6;
-use crate::msg::{Cw20HookMsg, TreasureConfigMsg};
+use crate::msg::{AssetInfo, Cw20HookMsg, TreasureConfigMsg};
 // use crate::querier::compute_user_dust;
 // use crate::random_rules::get_winning;
 use crate::state::{
@@ -455,3 +455,35 @@ pub fn accept_gov(deps: DepsMut, info: MessageInfo) -> Result<Response, Contract
         attr("gov", config.gov.to_string()),
     ]))
 }
+
+/// ## Description
+/// Rescues tokens sent to the contract by mistake. The locked and unlocked amounts of the lock
+/// token belong to users and are kept back.
+pub fn rescue_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    let config = read_treasure_config(deps.storage)?;
+    if config.gov != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut reserved = Uint128::zero();
+    if asset.is_token(&config.lock_token) {
+        let global_state = read_treasure_state(deps.storage)?;
+        reserved = global_state.current_locked_amount + global_state.current_unlock_amount;
+    }
+    Ok(common::rescue::rescue_tokens(
+        deps.as_ref(),
+        &env,
+        info.sender,
+        asset,
+        amount,
+        recipient,
+        reserved,
+    )?)
+}
@@ -1,3 +1,4 @@
+pub use common::asset::AssetInfo;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128};
 use cw20::Cw20ReceiveMsg;
@@ -44,11 +45,24 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
     UpdateConfig(TreasureConfigMsg),
-    UserWithdraw { amount: Uint128 },
-    UserUnlock { amount: Uint128 },
+    UserWithdraw {
+        amount: Uint128,
+    },
+    UserUnlock {
+        amount: Uint128,
+    },
     // PreMintNft { mint_num: u64 },
-    SetGov { gov: Addr },
+    SetGov {
+        gov: Addr,
+    },
     AcceptGov {},
+    /// Sends tokens that ended up in the contract by mistake to `recipient`,
+    /// locked and unlocked user funds can't be rescued.
+    RescueTokens {
+        asset: AssetInfo,
+        amount: Uint128,
+        recipient: Addr,
+    },
 }
 
 #[cw_serde]
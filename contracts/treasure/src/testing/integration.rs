@@ -330,6 +330,111 @@ fn test_integration() {
     // );
 }
 
+#[test]
+fn test_rescue_tokens() {
+    let block_time = 1688128676u64;
+    let creator = Addr::unchecked(CREATOR);
+    let tom_address = Addr::unchecked("tom");
+    let recipient = Addr::unchecked("recipient");
+    let mut app = mock_app(creator.clone(), vec![], Some(block_time));
+
+    let cw20_contract_id = store_cw20_contract(&mut app);
+    let cw20_token = app
+        .instantiate_contract(
+            cw20_contract_id,
+            creator.clone(),
+            &mock_cw20_instantiate_msg(),
+            &[], // no funds
+            String::from("cw20_token"),
+            None,
+        )
+        .unwrap();
+    let treasure_contract_id = store_treasure_contract(&mut app);
+    let treasure_contact = app
+        .instantiate_contract(
+            treasure_contract_id,
+            creator.clone(),
+            &crate::testing::mock_fn::mock_instantiate_msg(cw20_token.clone()),
+            &[], // no funds
+            String::from("treasure_contact"),
+            None,
+        )
+        .unwrap();
+    app.update_block(|block| {
+        block.time = Timestamp::from_seconds(1688128677 + 1000u64);
+        block.height += 1000000u64;
+    });
+
+    // tom locks and unlocks half of it, both parts stay reserved
+    let tom_lock_amount = Uint128::from(100_000_000u128);
+    let res = transfer_token(
+        &creator,
+        &tom_address,
+        &mut app,
+        &cw20_token,
+        tom_lock_amount,
+    );
+    assert!(res.is_ok());
+    let res = user_lock(
+        &tom_address,
+        &mut app,
+        &cw20_token,
+        &treasure_contact,
+        &tom_lock_amount,
+    );
+    assert!(res.is_ok());
+    let res = user_unlock(
+        &mut app,
+        &treasure_contact,
+        &tom_address,
+        &Uint128::from(50_000_000u128),
+    );
+    assert!(res.is_ok());
+
+    // tokens sent to the contract by mistake
+    let stray_amount = Uint128::from(1000u128);
+    let res = transfer_token(
+        &creator,
+        &treasure_contact,
+        &mut app,
+        &cw20_token,
+        stray_amount,
+    );
+    assert!(res.is_ok());
+
+    let res = rescue_tokens(
+        &tom_address,
+        &mut app,
+        &treasure_contact,
+        &cw20_token,
+        stray_amount,
+        &recipient,
+    );
+    assert!(res.is_err());
+    let res = rescue_tokens(
+        &creator,
+        &mut app,
+        &treasure_contact,
+        &cw20_token,
+        stray_amount + Uint128::one(),
+        &recipient,
+    );
+    assert!(res.is_err());
+    let res = rescue_tokens(
+        &creator,
+        &mut app,
+        &treasure_contact,
+        &cw20_token,
+        stray_amount,
+        &recipient,
+    );
+    assert!(res.is_ok());
+    let recipient_balance = get_token_balance(&mut app, &cw20_token, &recipient);
+    assert_eq!(recipient_balance.balance, stray_amount);
+    let treasure_balance = get_token_balance(&mut app, &cw20_token, &treasure_contact);
+    assert_eq!(treasure_balance.balance, tom_lock_amount);
+}
+
 fn transfer_token(
     from: &Addr,
     to: &Addr,
@@ -355,6 +460,35 @@ fn transfer_token(
     }
 }
 
+fn rescue_tokens(
+    gov: &Addr,
+    app: &mut App,
+    treasure_contact: &Addr,
+    token: &Addr,
+    amount: Uint128,
+    recipient: &Addr,
+) -> StdResult<Response> {
+    let rescue_tokens_msg = crate::msg::ExecuteMsg::RescueTokens {
+        asset: crate::msg::AssetInfo::Token {
+            contract_addr: token.clone(),
+        },
+        amount,
+        recipient: recipient.clone(),
+    };
+    let res = app.execute_contract(
+        gov.clone(),
+        treasure_contact.clone(),
+        &rescue_tokens_msg,
+        &[], // no funds
+    );
+    if res.is_err() {
+        println!("rescue_tokens error: {:?}", res);
+        Err(StdError::generic_err("rescue_tokens error"))
+    } else {
+        Ok(Response::default())
+    }
+}
+
 fn user_unlock(
     app: &mut App,
     treasure_contact: &Addr,
@@ -0,0 +1,66 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, QueryRequest, StdResult, Uint128, WasmMsg,
+    WasmQuery,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use std::fmt;
+
+/// A CW20 token or a native denom held by the contract.
+#[cw_serde]
+pub enum AssetInfo {
+    Token { contract_addr: Addr },
+    NativeToken { denom: String },
+}
+
+impl AssetInfo {
+    /// Pays `amount` of the asset out of the contract.
+    pub fn transfer_msg(&self, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        Ok(match self {
+            AssetInfo::Token { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }),
+            AssetInfo::NativeToken { denom } => CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: denom.clone(),
+                    amount,
+                }],
+            }),
+        })
+    }
+
+    pub fn is_token(&self, addr: &Addr) -> bool {
+        matches!(self, AssetInfo::Token { contract_addr } if contract_addr == addr)
+    }
+}
+
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetInfo::Token { contract_addr } => write!(f, "{}", contract_addr),
+            AssetInfo::NativeToken { denom } => write!(f, "{}", denom),
+        }
+    }
+}
+
+pub fn query_asset_balance(deps: Deps, asset: &AssetInfo, account: &Addr) -> StdResult<Uint128> {
+    match asset {
+        AssetInfo::Token { contract_addr } => {
+            let balance: BalanceResponse =
+                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20QueryMsg::Balance {
+                        address: account.to_string(),
+                    })?,
+                }))?;
+            Ok(balance.balance)
+        }
+        AssetInfo::NativeToken { denom } => Ok(deps.querier.query_balance(account, denom)?.amount),
+    }
+}
@@ -0,0 +1,49 @@
+use crate::asset::{query_asset_balance, AssetInfo};
+use cosmwasm_std::{attr, Addr, Deps, Env, Event, Response, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RescueError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("InvalidRescueAmount")]
+    InvalidRescueAmount {},
+
+    #[error("InsufficientRescuableFunds")]
+    InsufficientRescuableFunds {},
+}
+
+/// ## Description
+/// Sends tokens sent to the contract by mistake to the recipient. `reserved` is the part of the
+/// contract's balance of the asset that belongs to its users and is never rescued, the caller
+/// checks the sender is the gov. The emitted event is the audit trail.
+pub fn rescue_tokens(
+    deps: Deps,
+    env: &Env,
+    gov: Addr,
+    asset: AssetInfo,
+    amount: Uint128,
+    recipient: Addr,
+    reserved: Uint128,
+) -> Result<Response, RescueError> {
+    deps.api.addr_validate(recipient.as_str())?;
+    if amount.is_zero() {
+        return Err(RescueError::InvalidRescueAmount {});
+    }
+    let balance = query_asset_balance(deps, &asset, &env.contract.address)?;
+    if amount > balance.saturating_sub(reserved) {
+        return Err(RescueError::InsufficientRescuableFunds {});
+    }
+
+    Ok(Response::new()
+        .add_message(asset.transfer_msg(&recipient, amount)?)
+        .add_event(Event::new("rescue_tokens").add_attributes(vec![
+            attr("gov", gov),
+            attr("asset", asset.to_string()),
+            attr("amount", amount.to_string()),
+            attr("recipient", recipient.to_string()),
+            attr("reserved", reserved.to_string()),
+        ]))
+        .add_attribute("action", "rescue_tokens"))
+}